@@ -0,0 +1,554 @@
+use crate::commands::{load_config, save_config};
+use crate::recurrence::{self, Zone};
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use tauri::menu::{MenuItemBuilder, Submenu};
+use tauri::{AppHandle, Emitter, Listener, Wry};
+
+const DEFAULT_SNOOZE_MINUTES: u64 = 9;
+const CONFIG_RELOAD_SECS: i64 = 30;
+
+/// Configuração de um timer do tipo `alarm` (`icons[].alarm`).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmConfig {
+    /// Horário local no formato `HH:MM`.
+    pub time: String,
+    #[serde(default)]
+    pub recurrence: AlarmRecurrence,
+    /// Dias da semana para alarmes semanais (0 = domingo ... 6 = sábado).
+    #[serde(default)]
+    pub weekdays: Vec<u64>,
+    /// Nome IANA do fuso (vazio = fuso local do sistema).
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub snooze_minutes: Option<u64>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlarmRecurrence {
+    #[default]
+    Once,
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone)]
+struct AlarmEntry {
+    timer_id: String,
+    name: String,
+    alarm: AlarmConfig,
+}
+
+struct AlarmRuntime {
+    /// Config usado para agendar; se mudar, o alarme é rearmado
+    alarm: AlarmConfig,
+    /// Alarmes não disparam para horários anteriores a este instante
+    armed_at: DateTime<Utc>,
+    last_fired: Option<DateTime<Utc>>,
+    snoozed_until: Option<DateTime<Utc>>,
+    ringing: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingAlarm {
+    pub timer_id: String,
+    pub name: String,
+    /// Próximo disparo em milissegundos desde a epoch (UTC)
+    pub next_fire: Option<i64>,
+    pub ringing: bool,
+    pub snoozed: bool,
+}
+
+static ALARMS: LazyLock<Mutex<HashMap<String, AlarmRuntime>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static TRAY_MENU: Mutex<Option<Submenu<Wry>>> = Mutex::new(None);
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn alarm_entries(config: &Value) -> Vec<AlarmEntry> {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .map(|icons| {
+            icons
+                .iter()
+                .filter(|icon| icon.get("timerType").and_then(|t| t.as_str()) == Some("alarm"))
+                .filter_map(|icon| {
+                    let timer_id = icon.get("id")?.as_str()?.to_string();
+                    let alarm = match serde_json::from_value::<AlarmConfig>(icon.get("alarm")?.clone()) {
+                        Ok(alarm) => alarm,
+                        Err(e) => {
+                            println!("[ALARM] WARNING: Ignoring invalid alarm on {}: {}", timer_id, e);
+                            return None;
+                        }
+                    };
+                    let name = icon
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .unwrap_or("Alarm")
+                        .to_string();
+                    Some(AlarmEntry { timer_id, name, alarm })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Recusa alarmes semanais sem dias ou com dias fora de 0-6.
+pub fn validate_alarms(config: &Value) -> Result<(), String> {
+    for entry in alarm_entries(config) {
+        if entry.alarm.recurrence != AlarmRecurrence::Weekly {
+            continue;
+        }
+        if entry.alarm.weekdays.is_empty() {
+            return Err(format!("Weekly alarm \"{}\" has no weekdays", entry.name));
+        }
+        if let Some(day) = entry
+            .alarm
+            .weekdays
+            .iter()
+            .find(|d| recurrence::weekday_from_index(**d).is_none())
+        {
+            return Err(format!("Invalid weekday on \"{}\": {}", entry.name, day));
+        }
+    }
+    Ok(())
+}
+
+/// Próximo horário agendado (ignorando soneca) depois de `after`.
+fn next_scheduled(alarm: &AlarmConfig, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let zone = Zone::parse(alarm.time_zone.as_deref())
+        .map_err(|e| println!("[ALARM] WARNING: {}", e))
+        .ok()?;
+    let time = recurrence::parse_time(&alarm.time)
+        .map_err(|e| println!("[ALARM] WARNING: {}", e))
+        .ok()?;
+    let weekdays: Vec<_> = match alarm.recurrence {
+        AlarmRecurrence::Weekly => alarm
+            .weekdays
+            .iter()
+            .filter_map(|d| recurrence::weekday_from_index(*d))
+            .collect(),
+        AlarmRecurrence::Once | AlarmRecurrence::Daily => Vec::new(),
+    };
+    // Sem dias válidos o alarme semanal viraria diário
+    if alarm.recurrence == AlarmRecurrence::Weekly && weekdays.is_empty() {
+        println!("[ALARM] WARNING: Weekly alarm without valid weekdays");
+        return None;
+    }
+    recurrence::next_occurrence(&zone, time, &weekdays, after)
+}
+
+impl AlarmRuntime {
+    fn new(alarm: AlarmConfig, now: DateTime<Utc>) -> Self {
+        AlarmRuntime {
+            alarm,
+            armed_at: now,
+            last_fired: None,
+            snoozed_until: None,
+            ringing: false,
+        }
+    }
+
+    /// Próximo disparo: fim da soneca ou próximo horário agendado.
+    fn next_fire(&self) -> Option<DateTime<Utc>> {
+        // A soneca vem antes: alarmes únicos já estão desativados quando são adiados
+        if let Some(snoozed_until) = self.snoozed_until {
+            return Some(snoozed_until);
+        }
+        if !self.alarm.enabled {
+            return None;
+        }
+        if self.alarm.recurrence == AlarmRecurrence::Once && self.last_fired.is_some() {
+            return None;
+        }
+        let after = self.last_fired.unwrap_or(self.armed_at).max(self.armed_at);
+        next_scheduled(&self.alarm, after)
+    }
+}
+
+/// Sincroniza o estado com o config e devolve os alarmes que devem tocar agora.
+fn sync_and_collect_due(
+    alarms: &mut HashMap<String, AlarmRuntime>,
+    entries: &[AlarmEntry],
+    now: DateTime<Utc>,
+) -> Vec<AlarmEntry> {
+    alarms.retain(|id, _| entries.iter().any(|e| &e.timer_id == id));
+
+    let mut due = Vec::new();
+    for entry in entries {
+        let runtime = alarms
+            .entry(entry.timer_id.clone())
+            .or_insert_with(|| AlarmRuntime::new(entry.alarm.clone(), now));
+        if runtime.alarm != entry.alarm {
+            println!("[ALARM] Alarm {} changed, re-arming", entry.timer_id);
+            *runtime = AlarmRuntime::new(entry.alarm.clone(), now);
+        }
+        if runtime.ringing {
+            continue;
+        }
+        if let Some(next) = runtime.next_fire().filter(|next| *next <= now) {
+            if runtime.snoozed_until.take().is_none() {
+                runtime.last_fired = Some(next);
+                // Igual ao config depois de salvo, para não rearmar enquanto toca
+                if runtime.alarm.recurrence == AlarmRecurrence::Once {
+                    runtime.alarm.enabled = false;
+                }
+            }
+            runtime.ringing = true;
+            due.push(entry.clone());
+        }
+    }
+    due
+}
+
+fn upcoming(entries: &[AlarmEntry]) -> Vec<UpcomingAlarm> {
+    let alarms = ALARMS.lock().unwrap();
+    let mut upcoming: Vec<UpcomingAlarm> = entries
+        .iter()
+        .filter_map(|entry| {
+            let runtime = alarms.get(&entry.timer_id)?;
+            let next_fire = runtime.next_fire();
+            if next_fire.is_none() && !runtime.ringing {
+                return None;
+            }
+            Some(UpcomingAlarm {
+                timer_id: entry.timer_id.clone(),
+                name: entry.name.clone(),
+                next_fire: next_fire.map(|n| n.timestamp_millis()),
+                ringing: runtime.ringing,
+                snoozed: runtime.snoozed_until.is_some(),
+            })
+        })
+        .collect();
+    upcoming.sort_by_key(|a| (!a.ringing, a.next_fire.unwrap_or(i64::MAX)));
+    upcoming
+}
+
+fn load_entries(app: &AppHandle) -> Vec<AlarmEntry> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .map(|config| alarm_entries(&config))
+        .unwrap_or_default()
+}
+
+/// Inicia a thread que agenda e dispara os alarmes.
+pub fn start_alarm_scheduler(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[ALARM] Scheduler started");
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let mut entries = load_entries(&app);
+        let mut last_reload = Utc::now();
+        let mut last_upcoming: Vec<UpcomingAlarm> = Vec::new();
+        let mut tray_initialized = false;
+
+        loop {
+            let now = Utc::now();
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || now - last_reload >= Duration::seconds(CONFIG_RELOAD_SECS)
+            {
+                entries = load_entries(&app);
+                last_reload = now;
+            }
+
+            let due = sync_and_collect_due(&mut ALARMS.lock().unwrap(), &entries, now);
+            for entry in due {
+                println!("[ALARM] Alarm {} ({}) is ringing", entry.timer_id, entry.name);
+                let _ = app.emit(
+                    "alarm-triggered",
+                    json!({ "timerId": entry.timer_id, "name": entry.name }),
+                );
+                // Alarmes únicos são desativados ao tocar para não voltarem depois de reiniciar o app
+                if entry.alarm.recurrence == AlarmRecurrence::Once && entry.alarm.enabled {
+                    match disable_alarm_in_config(&app, &entry.timer_id) {
+                        Ok(()) => CONFIG_DIRTY.store(true, Ordering::SeqCst),
+                        Err(e) => println!("[ALARM] WARNING: Failed to disable alarm {}: {}", entry.timer_id, e),
+                    }
+                }
+            }
+
+            let current = upcoming(&entries);
+            if current != last_upcoming || !tray_initialized {
+                refresh_tray_menu(&app, &current);
+                last_upcoming = current;
+                tray_initialized = true;
+            }
+
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+    });
+}
+
+/// Registra o submenu "Upcoming Alarms" do tray para ser atualizado pelo scheduler.
+pub fn set_tray_menu(menu: Submenu<Wry>) {
+    *TRAY_MENU.lock().unwrap() = Some(menu);
+}
+
+fn refresh_tray_menu(app: &AppHandle, upcoming: &[UpcomingAlarm]) {
+    let guard = TRAY_MENU.lock().unwrap();
+    let Some(menu) = guard.as_ref() else {
+        return;
+    };
+
+    if let Ok(items) = menu.items() {
+        for item in items {
+            let _ = menu.remove(&item);
+        }
+    }
+
+    if upcoming.is_empty() {
+        if let Ok(item) = MenuItemBuilder::with_id("alarms_none", "No upcoming alarms")
+            .enabled(false)
+            .build(app)
+        {
+            let _ = menu.append(&item);
+        }
+        return;
+    }
+
+    for alarm in upcoming {
+        let label = if alarm.ringing {
+            format!("{} – ringing", alarm.name)
+        } else {
+            let when = alarm
+                .next_fire
+                .and_then(DateTime::from_timestamp_millis)
+                .map(|n| n.with_timezone(&Local).format("%a %H:%M").to_string())
+                .unwrap_or_default();
+            let snoozed = if alarm.snoozed { " (snoozed)" } else { "" };
+            format!("{} – {}{}", alarm.name, when, snoozed)
+        };
+        if let Ok(item) = MenuItemBuilder::with_id(format!("alarm_info_{}", alarm.timer_id), label)
+            .enabled(false)
+            .build(app)
+        {
+            let _ = menu.append(&item);
+        }
+        if alarm.ringing || alarm.snoozed {
+            if let Ok(item) = MenuItemBuilder::with_id(
+                format!("alarm_snooze_{}", alarm.timer_id),
+                format!("Snooze {}", alarm.name),
+            )
+            .build(app)
+            {
+                let _ = menu.append(&item);
+            }
+            if let Ok(item) = MenuItemBuilder::with_id(
+                format!("alarm_dismiss_{}", alarm.timer_id),
+                format!("Dismiss {}", alarm.name),
+            )
+            .build(app)
+            {
+                let _ = menu.append(&item);
+            }
+        }
+    }
+}
+
+pub fn get_upcoming_alarms(app: &AppHandle) -> Vec<UpcomingAlarm> {
+    upcoming(&load_entries(app))
+}
+
+pub fn snooze_alarm(app: &AppHandle, timer_id: &str, minutes: Option<u64>) -> Result<(), String> {
+    let snoozed_until = {
+        let mut alarms = ALARMS.lock().unwrap();
+        let runtime = alarms
+            .get_mut(timer_id)
+            .ok_or_else(|| format!("Alarm not found: {}", timer_id))?;
+        if !runtime.ringing && runtime.snoozed_until.is_none() {
+            return Err(format!("Alarm is not ringing: {}", timer_id));
+        }
+        let minutes = minutes
+            .or(runtime.alarm.snooze_minutes)
+            .unwrap_or(DEFAULT_SNOOZE_MINUTES)
+            .max(1);
+        let until = Utc::now() + Duration::minutes(minutes as i64);
+        runtime.snoozed_until = Some(until);
+        runtime.ringing = false;
+        until
+    };
+
+    println!("[ALARM] Alarm {} snoozed until {}", timer_id, snoozed_until);
+    let _ = app.emit(
+        "alarm-stopped",
+        json!({ "timerId": timer_id, "reason": "snoozed", "snoozedUntil": snoozed_until.timestamp_millis() }),
+    );
+    Ok(())
+}
+
+pub fn dismiss_alarm(app: &AppHandle, timer_id: &str) -> Result<(), String> {
+    {
+        let mut alarms = ALARMS.lock().unwrap();
+        let runtime = alarms
+            .get_mut(timer_id)
+            .ok_or_else(|| format!("Alarm not found: {}", timer_id))?;
+        runtime.ringing = false;
+        runtime.snoozed_until = None;
+    }
+
+    println!("[ALARM] Alarm {} dismissed", timer_id);
+    let _ = app.emit(
+        "alarm-stopped",
+        json!({ "timerId": timer_id, "reason": "dismissed" }),
+    );
+    Ok(())
+}
+
+fn disable_alarm_in_config(app: &AppHandle, timer_id: &str) -> Result<(), String> {
+    let config_str = load_config(app.clone())?;
+    let mut config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    if let Some(icons) = config.get_mut("icons").and_then(|i| i.as_array_mut()) {
+        for icon in icons {
+            if icon.get("id").and_then(|id| id.as_str()) == Some(timer_id) {
+                if let Some(alarm) = icon.get_mut("alarm") {
+                    alarm["enabled"] = json!(false);
+                }
+            }
+        }
+    }
+
+    let updated_config = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    save_config(app.clone(), updated_config, Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 10, h, m, s).unwrap()
+    }
+
+    fn entry(time: &str, recurrence: AlarmRecurrence) -> AlarmEntry {
+        AlarmEntry {
+            timer_id: "wake".to_string(),
+            name: "Wake up".to_string(),
+            alarm: AlarmConfig {
+                time: time.to_string(),
+                recurrence,
+                weekdays: Vec::new(),
+                time_zone: Some("UTC".to_string()),
+                snooze_minutes: None,
+                enabled: true,
+            },
+        }
+    }
+
+    fn due_ids(alarms: &mut HashMap<String, AlarmRuntime>, entries: &[AlarmEntry], now: DateTime<Utc>) -> Vec<String> {
+        sync_and_collect_due(alarms, entries, now)
+            .into_iter()
+            .map(|e| e.timer_id)
+            .collect()
+    }
+
+    #[test]
+    fn alarm_rings_once_its_time_arrives() {
+        let mut alarms = HashMap::new();
+        let entries = [entry("07:00", AlarmRecurrence::Daily)];
+        assert!(due_ids(&mut alarms, &entries, utc(6, 59, 0)).is_empty());
+        assert!(due_ids(&mut alarms, &entries, utc(6, 59, 59)).is_empty());
+        assert_eq!(due_ids(&mut alarms, &entries, utc(7, 0, 0)), ["wake"]);
+        assert!(alarms["wake"].ringing);
+    }
+
+    #[test]
+    fn alarm_armed_after_its_time_waits_for_the_next_day() {
+        let mut alarms = HashMap::new();
+        let entries = [entry("07:00", AlarmRecurrence::Daily)];
+        assert!(due_ids(&mut alarms, &entries, utc(7, 0, 30)).is_empty());
+        assert_eq!(
+            alarms["wake"].next_fire(),
+            Some(Utc.with_ymd_and_hms(2025, 6, 11, 7, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn dismissed_alarm_does_not_ring_again_in_the_same_minute() {
+        let mut alarms = HashMap::new();
+        let entries = [entry("07:00", AlarmRecurrence::Daily)];
+        due_ids(&mut alarms, &entries, utc(6, 59, 0));
+        assert_eq!(due_ids(&mut alarms, &entries, utc(7, 0, 0)), ["wake"]);
+        // Enquanto toca, os ticks seguintes não disparam de novo
+        assert!(due_ids(&mut alarms, &entries, utc(7, 0, 1)).is_empty());
+
+        alarms.get_mut("wake").unwrap().ringing = false;
+        for second in [2, 30, 59] {
+            assert!(due_ids(&mut alarms, &entries, utc(7, 0, second)).is_empty(), "{}", second);
+        }
+        assert_eq!(
+            alarms["wake"].next_fire(),
+            Some(Utc.with_ymd_and_hms(2025, 6, 11, 7, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn snoozed_alarm_rings_again_when_the_snooze_ends() {
+        let mut alarms = HashMap::new();
+        let entries = [entry("07:00", AlarmRecurrence::Daily)];
+        due_ids(&mut alarms, &entries, utc(6, 59, 0));
+        due_ids(&mut alarms, &entries, utc(7, 0, 0));
+
+        let runtime = alarms.get_mut("wake").unwrap();
+        runtime.ringing = false;
+        runtime.snoozed_until = Some(utc(7, 9, 0));
+        assert_eq!(runtime.next_fire(), Some(utc(7, 9, 0)));
+
+        assert!(due_ids(&mut alarms, &entries, utc(7, 8, 59)).is_empty());
+        assert_eq!(due_ids(&mut alarms, &entries, utc(7, 9, 0)), ["wake"]);
+        let runtime = &alarms["wake"];
+        assert!(runtime.ringing);
+        assert_eq!(runtime.snoozed_until, None);
+        // A soneca não muda o horário agendado
+        assert_eq!(runtime.last_fired, Some(utc(7, 0, 0)));
+    }
+
+    #[test]
+    fn one_time_alarm_is_disabled_after_ringing() {
+        let mut alarms = HashMap::new();
+        let entries = [entry("07:00", AlarmRecurrence::Once)];
+        due_ids(&mut alarms, &entries, utc(6, 59, 0));
+        let due = sync_and_collect_due(&mut alarms, &entries, utc(7, 0, 0));
+        // O scheduler desativa no config quem ainda estava ativo
+        assert_eq!(due.len(), 1);
+        assert!(due[0].alarm.enabled);
+
+        let runtime = alarms.get_mut("wake").unwrap();
+        assert!(!runtime.alarm.enabled);
+        runtime.ringing = false;
+        assert_eq!(runtime.next_fire(), None);
+
+        // O config salvo com `enabled: false` não rearma o alarme
+        let mut disabled = entry("07:00", AlarmRecurrence::Once);
+        disabled.alarm.enabled = false;
+        assert!(due_ids(&mut alarms, &[disabled.clone()], utc(7, 0, 5)).is_empty());
+        assert_eq!(alarms["wake"].last_fired, Some(utc(7, 0, 0)));
+        assert!(due_ids(&mut alarms, &[disabled], Utc.with_ymd_and_hms(2025, 6, 11, 7, 0, 0).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn removed_alarms_are_forgotten() {
+        let mut alarms = HashMap::new();
+        due_ids(&mut alarms, &[entry("07:00", AlarmRecurrence::Daily)], utc(6, 0, 0));
+        assert!(alarms.contains_key("wake"));
+        due_ids(&mut alarms, &[], utc(6, 0, 1));
+        assert!(alarms.is_empty());
+    }
+}
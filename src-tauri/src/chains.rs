@@ -0,0 +1,213 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Regra de encadeamento configurada em `icons[].chain`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainRule {
+    pub id: String,
+    pub trigger: ChainTrigger,
+    /// Fase (ciclo de repetição, começando em 1) para o gatilho `phase`.
+    #[serde(default)]
+    pub phase: Option<u32>,
+    /// Atraso em segundos antes de iniciar o alvo.
+    #[serde(default)]
+    pub delay: Option<u64>,
+    pub target: ChainTarget,
+    /// Quantas vezes a regra pode disparar por cadeia (None = sem limite).
+    #[serde(default)]
+    pub max_runs: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainTrigger {
+    Finished,
+    Phase,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ChainTarget {
+    Timer {
+        #[serde(rename = "timerId")]
+        timer_id: String,
+    },
+    Preset {
+        #[serde(rename = "presetId")]
+        preset_id: String,
+    },
+}
+
+/// Lê as regras de encadeamento de um timer. Regras inválidas são ignoradas.
+pub fn rules_for_timer(config: &Value, timer_id: &str) -> Vec<ChainRule> {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .and_then(|icons| {
+            icons
+                .iter()
+                .find(|icon| icon.get("id").and_then(|id| id.as_str()) == Some(timer_id))
+        })
+        .and_then(|icon| icon.get("chain"))
+        .and_then(|chain| chain.as_array())
+        .map(|rules| {
+            rules
+                .iter()
+                .filter_map(|rule| match serde_json::from_value::<ChainRule>(rule.clone()) {
+                    Ok(rule) => Some(rule),
+                    Err(e) => {
+                        println!("[CHAIN] WARNING: Ignoring invalid chain rule on {}: {}", timer_id, e);
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Recusa configs onde regras sem limite (`maxRuns`) formam um ciclo entre timers,
+/// já que esse ciclo reiniciaria os timers para sempre.
+pub fn validate_chain_rules(config: &Value) -> Result<(), String> {
+    let icons = match config.get("icons").and_then(|i| i.as_array()) {
+        Some(icons) => icons,
+        None => return Ok(()),
+    };
+
+    let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+    for icon in icons {
+        let Some(id) = icon.get("id").and_then(|id| id.as_str()) else {
+            continue;
+        };
+        let targets = rules_for_timer(config, id)
+            .into_iter()
+            .filter(|rule| rule.max_runs.is_none())
+            .filter_map(|rule| match rule.target {
+                ChainTarget::Timer { timer_id } => Some(timer_id),
+                // Presets criam um timer novo, sem regras, então não fecham ciclos
+                ChainTarget::Preset { .. } => None,
+            })
+            .collect();
+        edges.insert(id.to_string(), targets);
+    }
+
+    if let Some(cycle) = find_cycle(&edges) {
+        return Err(format!(
+            "Chain rules loop forever: {}. Set a repeat cap on one of these rules.",
+            cycle.join(" → ")
+        ));
+    }
+    Ok(())
+}
+
+fn find_cycle(edges: &HashMap<String, Vec<String>>) -> Option<Vec<String>> {
+    // 0 = não visitado, 1 = na pilha atual, 2 = concluído
+    let mut state: HashMap<&str, u8> = HashMap::new();
+    let mut stack: Vec<&str> = Vec::new();
+
+    fn visit<'a>(
+        node: &'a str,
+        edges: &'a HashMap<String, Vec<String>>,
+        state: &mut HashMap<&'a str, u8>,
+        stack: &mut Vec<&'a str>,
+    ) -> Option<Vec<String>> {
+        state.insert(node, 1);
+        stack.push(node);
+        for next in edges.get(node).into_iter().flatten() {
+            match state.get(next.as_str()).copied().unwrap_or(0) {
+                1 => {
+                    let start = stack.iter().position(|n| *n == next.as_str()).unwrap_or(0);
+                    let mut cycle: Vec<String> = stack[start..].iter().map(|n| n.to_string()).collect();
+                    cycle.push(next.clone());
+                    return Some(cycle);
+                }
+                0 => {
+                    if let Some(cycle) = visit(next, edges, state, stack) {
+                        return Some(cycle);
+                    }
+                }
+                _ => {}
+            }
+        }
+        stack.pop();
+        state.insert(node, 2);
+        None
+    }
+
+    let mut nodes: Vec<&String> = edges.keys().collect();
+    nodes.sort();
+    for node in nodes {
+        if state.get(node.as_str()).copied().unwrap_or(0) == 0 {
+            if let Some(cycle) = visit(node, edges, &mut state, &mut stack) {
+                return Some(cycle);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn to_timer(timer_id: &str) -> Value {
+        json!({
+            "id": format!("to-{}", timer_id),
+            "trigger": "finished",
+            "target": { "type": "timer", "timerId": timer_id },
+        })
+    }
+
+    #[test]
+    fn rejects_self_loop() {
+        let config = json!({ "icons": [{ "id": "a", "chain": [to_timer("a")] }] });
+        let err = validate_chain_rules(&config).unwrap_err();
+        assert!(err.contains("a → a"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_cycle_between_timers() {
+        let config = json!({ "icons": [
+            { "id": "a", "chain": [to_timer("b")] },
+            { "id": "b", "chain": [to_timer("a")] },
+        ] });
+        let err = validate_chain_rules(&config).unwrap_err();
+        assert!(err.contains("a → b → a"), "{}", err);
+        assert_eq!(
+            find_cycle(&HashMap::from([
+                ("a".to_string(), vec!["b".to_string()]),
+                ("b".to_string(), vec!["a".to_string()]),
+            ])),
+            Some(vec!["a".to_string(), "b".to_string(), "a".to_string()])
+        );
+    }
+
+    #[test]
+    fn accepts_cycle_with_repeat_cap() {
+        let mut capped = to_timer("a");
+        capped["maxRuns"] = json!(3);
+        let config = json!({ "icons": [
+            { "id": "a", "chain": [to_timer("b")] },
+            { "id": "b", "chain": [capped] },
+        ] });
+        assert_eq!(validate_chain_rules(&config), Ok(()));
+    }
+
+    #[test]
+    fn ignores_preset_targets() {
+        // Presets com o mesmo id de um timer não fecham ciclo
+        let preset = json!({
+            "id": "p",
+            "trigger": "finished",
+            "target": { "type": "preset", "presetId": "a" },
+        });
+        let config = json!({ "icons": [
+            { "id": "a", "chain": [to_timer("b")] },
+            { "id": "b", "chain": [preset] },
+        ] });
+        assert_eq!(validate_chain_rules(&config), Ok(()));
+    }
+}
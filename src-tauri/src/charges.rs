@@ -0,0 +1,335 @@
+use crate::commands::load_config;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Listener};
+
+const CONFIG_RELOAD_SECS: u64 = 30;
+/// Recargas de habilidades costumam ser curtas; checar a cada 1 s atrasaria o aviso
+const TICK_MILLIS: u64 = 200;
+
+/// Timer de cargas (`timerType: "charges"`): `charges.max` cargas que recarregam
+/// uma de cada vez, cada recarga levando `timerDuration` segundos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ChargeConfig {
+    max: u32,
+    recharge: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChargeRuntime {
+    available: u32,
+    /// Início da recarga em andamento (None = todas as cargas prontas)
+    recharge_started: Option<Instant>,
+}
+
+/// Estado das cargas de um timer, como exposto para o frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChargeSnapshot {
+    pub timer_id: String,
+    pub available: u32,
+    pub max: u32,
+    pub recharge_seconds: u64,
+    /// Progresso da recarga em andamento (0.0 a 1.0)
+    pub recharge_progress: Option<f64>,
+    pub recharge_remaining_ms: Option<u64>,
+    /// Quando a próxima carga fica pronta, em milissegundos desde a epoch
+    pub next_charge_at: Option<i64>,
+}
+
+static CHARGES: LazyLock<Mutex<HashMap<String, ChargeRuntime>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn charge_config(icon: &Value) -> Option<ChargeConfig> {
+    if icon.get("timerType").and_then(|t| t.as_str()) != Some("charges") {
+        return None;
+    }
+    let max = icon
+        .get("charges")
+        .and_then(|c| c.get("max"))
+        .and_then(|m| m.as_u64())
+        .unwrap_or(1)
+        .clamp(1, u32::MAX as u64) as u32;
+    let recharge = icon.get("timerDuration").and_then(|d| d.as_u64()).unwrap_or(0);
+    Some(ChargeConfig {
+        max,
+        recharge: Duration::from_secs(recharge),
+    })
+}
+
+fn charge_configs(config: &Value) -> HashMap<String, ChargeConfig> {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .map(|icons| {
+            icons
+                .iter()
+                .filter_map(|icon| {
+                    let id = icon.get("id").and_then(|id| id.as_str())?;
+                    Some((id.to_string(), charge_config(icon)?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn load_charge_config(app: &AppHandle, timer_id: &str) -> Result<ChargeConfig, String> {
+    let config_str = load_config(app.clone())?;
+    let config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+    charge_configs(&config)
+        .remove(timer_id)
+        .ok_or_else(|| format!("Timer {} is not a charges timer", timer_id))
+}
+
+impl ChargeRuntime {
+    fn full(config: &ChargeConfig) -> Self {
+        ChargeRuntime {
+            available: config.max,
+            recharge_started: None,
+        }
+    }
+
+    /// Conclui as recargas vencidas até `now` (encadeando a próxima sem acumular atraso)
+    /// e ajusta o estado se o máximo de cargas mudou. Retorna quantas cargas voltaram.
+    fn settle(&mut self, config: &ChargeConfig, now: Instant) -> u32 {
+        if self.available >= config.max {
+            self.available = config.max;
+            self.recharge_started = None;
+            return 0;
+        }
+
+        let mut gained = 0;
+        let mut started = *self.recharge_started.get_or_insert(now);
+        while self.available < config.max && started + config.recharge <= now {
+            started += config.recharge;
+            self.available += 1;
+            gained += 1;
+        }
+        self.recharge_started = (self.available < config.max).then_some(started);
+        gained
+    }
+
+    fn snapshot(&self, timer_id: &str, config: &ChargeConfig, now: Instant) -> ChargeSnapshot {
+        let remaining = self
+            .recharge_started
+            .map(|started| (started + config.recharge).saturating_duration_since(now));
+        let progress = remaining.map(|remaining| {
+            if config.recharge.is_zero() {
+                1.0
+            } else {
+                1.0 - remaining.as_secs_f64() / config.recharge.as_secs_f64()
+            }
+        });
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        ChargeSnapshot {
+            timer_id: timer_id.to_string(),
+            available: self.available,
+            max: config.max,
+            recharge_seconds: config.recharge.as_secs(),
+            recharge_progress: progress,
+            recharge_remaining_ms: remaining.map(|r| r.as_millis() as u64),
+            next_charge_at: remaining.map(|r| now_ms + r.as_millis() as i64),
+        }
+    }
+}
+
+/// Estado atual das cargas de um ícone; None se não for um timer de cargas.
+pub fn charge_state(icon: &Value) -> Option<ChargeSnapshot> {
+    let config = charge_config(icon)?;
+    let timer_id = icon.get("id").and_then(|id| id.as_str())?;
+    let now = Instant::now();
+    // Só leitura: as recargas vencidas são concluídas (e avisadas) pelo runner
+    let mut runtime = *CHARGES
+        .lock()
+        .unwrap()
+        .entry(timer_id.to_string())
+        .or_insert_with(|| ChargeRuntime::full(&config));
+    runtime.settle(&config, now);
+    Some(runtime.snapshot(timer_id, &config, now))
+}
+
+/// Gasta uma carga; a recarga começa agora se nenhuma estiver em andamento,
+/// senão fica na fila atrás da atual.
+pub fn use_charge(app: &AppHandle, timer_id: &str) -> Result<ChargeSnapshot, String> {
+    let config = load_charge_config(app, timer_id)?;
+    let now = Instant::now();
+    let (ready, snapshot) = {
+        let mut charges = CHARGES.lock().unwrap();
+        let runtime = charges
+            .entry(timer_id.to_string())
+            .or_insert_with(|| ChargeRuntime::full(&config));
+        let ready = (runtime.settle(&config, now) > 0).then(|| runtime.snapshot(timer_id, &config, now));
+        if runtime.available == 0 {
+            return Err(format!("No charges available for {}", timer_id));
+        }
+        runtime.available -= 1;
+        runtime.recharge_started.get_or_insert(now);
+        (ready, runtime.snapshot(timer_id, &config, now))
+    };
+
+    // Recargas concluídas aqui antes do runner também precisam do aviso
+    if let Some(ready) = ready {
+        announce_ready(app, &ready);
+    }
+
+    println!("[CHARGES] {} used a charge ({}/{} left)", timer_id, snapshot.available, snapshot.max);
+    let _ = app.emit("charges-changed", &snapshot);
+    Ok(snapshot)
+}
+
+/// Devolve todas as cargas de uma vez.
+pub fn reset_charges(app: &AppHandle, timer_id: &str) -> Result<ChargeSnapshot, String> {
+    let config = load_charge_config(app, timer_id)?;
+    let runtime = ChargeRuntime::full(&config);
+    CHARGES.lock().unwrap().insert(timer_id.to_string(), runtime);
+
+    println!("[CHARGES] {} refilled", timer_id);
+    let snapshot = runtime.snapshot(timer_id, &config, Instant::now());
+    let _ = app.emit("charges-changed", &snapshot);
+    Ok(snapshot)
+}
+
+/// Avisa o overlay de que uma carga (ou todas) ficou pronta.
+fn announce_ready(app: &AppHandle, snapshot: &ChargeSnapshot) {
+    let full = snapshot.available == snapshot.max;
+    println!(
+        "[CHARGES] {} recharged ({}/{}{})",
+        snapshot.timer_id,
+        snapshot.available,
+        snapshot.max,
+        if full { ", all ready" } else { "" }
+    );
+    let _ = app.emit("charges-changed", snapshot);
+    let _ = app.emit(
+        "charge-ready",
+        json!({
+            "timerId": snapshot.timer_id,
+            "available": snapshot.available,
+            "max": snapshot.max,
+            "allReady": full,
+        }),
+    );
+}
+
+fn load_configs(app: &AppHandle) -> HashMap<String, ChargeConfig> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .map(|config| charge_configs(&config))
+        .unwrap_or_default()
+}
+
+/// Thread que conclui recargas e avisa o overlay quando uma carga (ou todas) fica pronta.
+pub fn start_charge_runner(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[CHARGES] Runner started");
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let mut configs = load_configs(&app);
+        let mut last_reload = Instant::now();
+
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || last_reload.elapsed() >= Duration::from_secs(CONFIG_RELOAD_SECS)
+            {
+                configs = load_configs(&app);
+                last_reload = Instant::now();
+            }
+
+            let now = Instant::now();
+            let mut ready = Vec::new();
+            {
+                let mut charges = CHARGES.lock().unwrap();
+                // Timers removidos ou que deixaram de ser de cargas
+                charges.retain(|timer_id, _| configs.contains_key(timer_id));
+                for (timer_id, runtime) in charges.iter_mut() {
+                    let config = &configs[timer_id];
+                    if runtime.settle(config, now) > 0 {
+                        ready.push(runtime.snapshot(timer_id, config, now));
+                    }
+                }
+            }
+
+            for snapshot in ready {
+                announce_ready(&app, &snapshot);
+            }
+
+            std::thread::sleep(Duration::from_millis(TICK_MILLIS));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max: u32, recharge: u64) -> ChargeConfig {
+        ChargeConfig {
+            max,
+            recharge: Duration::from_secs(recharge),
+        }
+    }
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn queued_recharges_chain_without_drift() {
+        let config = config(3, 10);
+        let t0 = Instant::now();
+        let mut runtime = ChargeRuntime {
+            available: 0,
+            recharge_started: Some(t0),
+        };
+        assert_eq!(runtime.settle(&config, t0 + secs(9)), 0);
+        // Duas recargas vencidas; a terceira começou no fim da segunda, não em `now`
+        assert_eq!(runtime.settle(&config, t0 + secs(25)), 2);
+        assert_eq!(runtime.available, 2);
+        assert_eq!(runtime.recharge_started, Some(t0 + secs(20)));
+        assert_eq!(runtime.settle(&config, t0 + secs(30)), 1);
+        assert_eq!(runtime.available, 3);
+        assert_eq!(runtime.recharge_started, None);
+    }
+
+    #[test]
+    fn zero_recharge_refills_at_once() {
+        let config = config(3, 0);
+        let t0 = Instant::now();
+        let mut runtime = ChargeRuntime {
+            available: 0,
+            recharge_started: None,
+        };
+        assert_eq!(runtime.settle(&config, t0), 3);
+        assert_eq!(runtime.available, 3);
+        assert_eq!(runtime.recharge_started, None);
+    }
+
+    #[test]
+    fn follows_max_changes() {
+        let t0 = Instant::now();
+        // Máximo diminuiu abaixo das cargas disponíveis: corta e para a recarga
+        let mut runtime = ChargeRuntime {
+            available: 3,
+            recharge_started: Some(t0),
+        };
+        assert_eq!(runtime.settle(&config(2, 10), t0 + secs(5)), 0);
+        assert_eq!(runtime.available, 2);
+        assert_eq!(runtime.recharge_started, None);
+
+        // Máximo aumentou: a recarga da nova carga começa agora
+        let mut runtime = ChargeRuntime::full(&config(2, 10));
+        assert_eq!(runtime.settle(&config(4, 10), t0), 0);
+        assert_eq!(runtime.available, 2);
+        assert_eq!(runtime.recharge_started, Some(t0));
+    }
+}
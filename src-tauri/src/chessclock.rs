@@ -0,0 +1,522 @@
+use crate::commands::load_config;
+use crate::engine::{self, RunStatus, TimerEvent, TimerEventKind};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Listener};
+
+const CONFIG_RELOAD_SECS: u64 = 30;
+/// Jogadores perdem por frações de segundo; o aviso de tempo esgotado precisa ser rápido
+const TICK_MILLIS: u64 = 100;
+
+/// Relógio de xadrez (`timerType: "chessClock"`): cada jogador em `chessClock.players`
+/// tem um banco de `timerDuration` segundos. Ao passar a vez o jogador ganha
+/// `increment` segundos (Fischer); com `delay`, os primeiros segundos de cada
+/// jogada não descontam do banco.
+#[derive(Debug, Clone, PartialEq)]
+struct ChessClockConfig {
+    players: Vec<String>,
+    bank: Duration,
+    increment: Duration,
+    delay: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct PlayerClock {
+    /// Banco no início da jogada atual (ou agora, para quem não está jogando)
+    remaining: Duration,
+    moves: u32,
+    flagged: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ChessClockRuntime {
+    config: ChessClockConfig,
+    players: Vec<PlayerClock>,
+    active: usize,
+    /// Tempo da jogada atual já contado antes de `running_since`
+    turn_elapsed: Duration,
+    running_since: Option<Instant>,
+    started: bool,
+    /// Sobrou no máximo um jogador com tempo
+    finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerClockState {
+    pub index: usize,
+    pub name: String,
+    pub remaining_ms: u64,
+    pub moves: u32,
+    pub flagged: bool,
+}
+
+/// Estado do relógio de xadrez de um timer, como exposto para o frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChessClockSnapshot {
+    pub timer_id: String,
+    pub status: RunStatus,
+    pub active_player: usize,
+    pub players: Vec<PlayerClockState>,
+    pub bank_seconds: u64,
+    pub increment_seconds: u64,
+    pub delay_seconds: u64,
+    /// Restante do delay da jogada atual
+    pub delay_remaining_ms: u64,
+    pub finished: bool,
+}
+
+static CLOCKS: LazyLock<Mutex<HashMap<String, ChessClockRuntime>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn chess_clock_config(icon: &Value) -> Option<ChessClockConfig> {
+    if icon.get("timerType").and_then(|t| t.as_str()) != Some("chessClock") {
+        return None;
+    }
+    let clock = icon.get("chessClock");
+    let mut players: Vec<String> = clock
+        .and_then(|c| c.get("players"))
+        .and_then(|p| p.as_array())
+        .map(|players| {
+            players
+                .iter()
+                .enumerate()
+                .map(|(i, name)| match name.as_str().map(str::trim) {
+                    Some(name) if !name.is_empty() => name.to_string(),
+                    _ => format!("Player {}", i + 1),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Um relógio de um jogador só não faz sentido
+    while players.len() < 2 {
+        players.push(format!("Player {}", players.len() + 1));
+    }
+    let seconds = |key: &str| {
+        clock
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+    };
+    Some(ChessClockConfig {
+        players,
+        bank: Duration::from_secs(icon.get("timerDuration").and_then(|d| d.as_u64()).unwrap_or(300)),
+        increment: Duration::from_secs(seconds("increment")),
+        delay: Duration::from_secs(seconds("delay")),
+    })
+}
+
+fn chess_clock_configs(config: &Value) -> HashMap<String, ChessClockConfig> {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .map(|icons| {
+            icons
+                .iter()
+                .filter_map(|icon| {
+                    let id = icon.get("id").and_then(|id| id.as_str())?;
+                    Some((id.to_string(), chess_clock_config(icon)?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn load_configs(app: &AppHandle) -> HashMap<String, ChessClockConfig> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .map(|config| chess_clock_configs(&config))
+        .unwrap_or_default()
+}
+
+impl ChessClockRuntime {
+    fn new(config: &ChessClockConfig) -> Self {
+        ChessClockRuntime {
+            config: config.clone(),
+            players: config
+                .players
+                .iter()
+                .map(|_| PlayerClock {
+                    remaining: config.bank,
+                    moves: 0,
+                    flagged: false,
+                })
+                .collect(),
+            active: 0,
+            turn_elapsed: Duration::ZERO,
+            running_since: None,
+            started: false,
+            finished: false,
+        }
+    }
+
+    fn turn_elapsed_at(&self, now: Instant) -> Duration {
+        self.turn_elapsed
+            + self
+                .running_since
+                .map(|since| now.saturating_duration_since(since))
+                .unwrap_or_default()
+    }
+
+    /// Quanto a jogada atual já descontou do banco (o delay não conta).
+    fn turn_cost_at(&self, now: Instant) -> Duration {
+        self.turn_elapsed_at(now).saturating_sub(self.config.delay)
+    }
+
+    fn remaining_at(&self, player: usize, now: Instant) -> Duration {
+        let clock = &self.players[player];
+        if player == self.active && self.started && !self.finished {
+            clock.remaining.saturating_sub(self.turn_cost_at(now))
+        } else {
+            clock.remaining
+        }
+    }
+
+    fn status(&self) -> RunStatus {
+        match (self.running_since, self.started && !self.finished) {
+            (Some(_), _) => RunStatus::Running,
+            (None, true) => RunStatus::Paused,
+            (None, false) => RunStatus::Idle,
+        }
+    }
+
+    /// Próximo jogador que ainda tem tempo, em ordem circular.
+    fn next_player(&self) -> usize {
+        let count = self.players.len();
+        (1..=count)
+            .map(|offset| (self.active + offset) % count)
+            .find(|&i| !self.players[i].flagged)
+            .unwrap_or(self.active)
+    }
+
+    /// Fecha a jogada do jogador ativo e começa a do próximo em `now`.
+    fn pass_turn(&mut self, now: Instant) {
+        let cost = self.turn_cost_at(now);
+        let increment = self.config.increment;
+        let clock = &mut self.players[self.active];
+        clock.remaining = clock.remaining.saturating_sub(cost) + increment;
+        clock.moves += 1;
+        self.active = self.next_player();
+        self.turn_elapsed = Duration::ZERO;
+        self.running_since = Some(now);
+    }
+
+    /// Marca como sem tempo os jogadores cujo banco zerou até `now` e passa a vez
+    /// adiante. Retorna os jogadores que perderam por tempo.
+    fn settle(&mut self, now: Instant) -> Vec<usize> {
+        let mut flagged = Vec::new();
+        while self.running_since.is_some() && !self.finished {
+            let clock = &self.players[self.active];
+            let cost = self.turn_cost_at(now);
+            if cost < clock.remaining {
+                break;
+            }
+            // Momento exato em que o banco zerou, para a jogada seguinte não herdar atraso
+            let overshoot = cost - clock.remaining;
+            let flagged_at = now.checked_sub(overshoot).unwrap_or(now);
+            self.players[self.active].remaining = Duration::ZERO;
+            self.players[self.active].flagged = true;
+            flagged.push(self.active);
+
+            if self.players.iter().filter(|p| !p.flagged).count() <= 1 {
+                self.finished = true;
+                self.running_since = None;
+                self.turn_elapsed = Duration::ZERO;
+            } else {
+                self.active = self.next_player();
+                self.turn_elapsed = Duration::ZERO;
+                self.running_since = Some(flagged_at);
+            }
+        }
+        flagged
+    }
+
+    fn snapshot(&self, timer_id: &str, now: Instant) -> ChessClockSnapshot {
+        let delay_remaining = if self.started && !self.finished {
+            self.config.delay.saturating_sub(self.turn_elapsed_at(now))
+        } else {
+            Duration::ZERO
+        };
+        ChessClockSnapshot {
+            timer_id: timer_id.to_string(),
+            status: self.status(),
+            active_player: self.active,
+            players: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(index, clock)| PlayerClockState {
+                    index,
+                    name: self.config.players[index].clone(),
+                    remaining_ms: self.remaining_at(index, now).as_millis() as u64,
+                    moves: clock.moves,
+                    flagged: clock.flagged,
+                })
+                .collect(),
+            bank_seconds: self.config.bank.as_secs(),
+            increment_seconds: self.config.increment.as_secs(),
+            delay_seconds: self.config.delay.as_secs(),
+            delay_remaining_ms: delay_remaining.as_millis() as u64,
+            finished: self.finished,
+        }
+    }
+}
+
+fn emit_flagged(app: &AppHandle, snapshot: &ChessClockSnapshot, flagged: &[usize]) {
+    if flagged.is_empty() {
+        return;
+    }
+    let config = engine::load_config_value(app).unwrap_or(Value::Null);
+    for &player in flagged {
+        let name = &snapshot.players[player].name;
+        println!("[CHESS_CLOCK] {} player {} ({}) ran out of time", snapshot.timer_id, player, name);
+        let _ = app.emit(
+            "chess-clock-flagged",
+            json!({
+                "timerId": snapshot.timer_id,
+                "player": player,
+                "name": name,
+                "finished": snapshot.finished,
+            }),
+        );
+        // Para hooks e webhooks, ficar sem tempo é o fim do timer daquele jogador
+        let event = TimerEvent {
+            timer_id: snapshot.timer_id.clone(),
+            event: TimerEventKind::Finished,
+            phase: None,
+            remaining: Some(0),
+            instance: None,
+            seed: None,
+            draw: None,
+            detail: Some(json!({ "player": player, "playerName": name, "gameOver": snapshot.finished })),
+        };
+        engine::dispatch_event(&config, &event, None);
+    }
+}
+
+/// Aplica `update` ao relógio de um timer (recriando-o se a configuração mudou) e avisa o overlay.
+fn update_clock(
+    app: &AppHandle,
+    timer_id: &str,
+    update: impl FnOnce(&mut ChessClockRuntime, Instant),
+) -> Result<ChessClockSnapshot, String> {
+    let config = load_configs(app)
+        .remove(timer_id)
+        .ok_or_else(|| format!("Timer {} is not a chess clock", timer_id))?;
+    let now = Instant::now();
+    let (snapshot, flagged) = {
+        let mut clocks = CLOCKS.lock().unwrap();
+        let runtime = clocks
+            .entry(timer_id.to_string())
+            .or_insert_with(|| ChessClockRuntime::new(&config));
+        if runtime.config != config {
+            *runtime = ChessClockRuntime::new(&config);
+        }
+        let flagged = runtime.settle(now);
+        update(runtime, now);
+        (runtime.snapshot(timer_id, now), flagged)
+    };
+
+    emit_flagged(app, &snapshot, &flagged);
+    let _ = app.emit("chess-clock-changed", &snapshot);
+    Ok(snapshot)
+}
+
+/// Estado atual do relógio de um ícone; None se não for um relógio de xadrez.
+pub fn chess_clock_state(icon: &Value) -> Option<ChessClockSnapshot> {
+    let config = chess_clock_config(icon)?;
+    let timer_id = icon.get("id").and_then(|id| id.as_str())?;
+    let now = Instant::now();
+    let clocks = CLOCKS.lock().unwrap();
+    let snapshot = match clocks.get(timer_id) {
+        Some(runtime) if runtime.config == config => runtime.snapshot(timer_id, now),
+        _ => ChessClockRuntime::new(&config).snapshot(timer_id, now),
+    };
+    Some(snapshot)
+}
+
+/// Status do relógio, para quem só conhece timers comuns (regras de processo).
+pub fn run_status(timer_id: &str) -> Option<RunStatus> {
+    CLOCKS.lock().unwrap().get(timer_id).map(|runtime| runtime.status())
+}
+
+/// Atalho do relógio: começa a partida, retoma se estiver pausada ou passa a vez.
+pub fn pass_turn(app: &AppHandle, timer_id: &str) -> Result<ChessClockSnapshot, String> {
+    let snapshot = update_clock(app, timer_id, |runtime, now| {
+        if runtime.finished {
+            return;
+        }
+        if !runtime.started {
+            runtime.started = true;
+            runtime.running_since = Some(now);
+        } else if runtime.running_since.is_none() {
+            runtime.running_since = Some(now);
+        } else {
+            runtime.pass_turn(now);
+        }
+    })?;
+    println!(
+        "[CHESS_CLOCK] {} {:?}, {} to move",
+        timer_id, snapshot.status, snapshot.players[snapshot.active_player].name
+    );
+    Ok(snapshot)
+}
+
+/// Pausa ou retoma a jogada atual sem passar a vez.
+pub fn toggle_chess_clock(app: &AppHandle, timer_id: &str) -> Result<ChessClockSnapshot, String> {
+    let snapshot = update_clock(app, timer_id, |runtime, now| {
+        if runtime.finished || !runtime.started {
+            return;
+        }
+        match runtime.running_since.take() {
+            Some(since) => runtime.turn_elapsed += now.saturating_duration_since(since),
+            None => runtime.running_since = Some(now),
+        }
+    })?;
+    println!("[CHESS_CLOCK] {} {:?}", timer_id, snapshot.status);
+    Ok(snapshot)
+}
+
+/// Devolve o banco inteiro a todos os jogadores e volta ao primeiro.
+pub fn reset_chess_clock(app: &AppHandle, timer_id: &str) -> Result<ChessClockSnapshot, String> {
+    let snapshot = update_clock(app, timer_id, |runtime, _| {
+        *runtime = ChessClockRuntime::new(&runtime.config);
+    })?;
+    println!("[CHESS_CLOCK] {} reset", timer_id);
+    Ok(snapshot)
+}
+
+/// Thread que detecta jogadores sem tempo enquanto o relógio corre.
+pub fn start_chess_clock_runner(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[CHESS_CLOCK] Runner started");
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let mut configs = load_configs(&app);
+        let mut last_reload = Instant::now();
+
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || last_reload.elapsed() >= Duration::from_secs(CONFIG_RELOAD_SECS)
+            {
+                configs = load_configs(&app);
+                last_reload = Instant::now();
+            }
+
+            let now = Instant::now();
+            let mut changed = Vec::new();
+            {
+                let mut clocks = CLOCKS.lock().unwrap();
+                // Timers removidos ou que deixaram de ser relógios; mudanças na
+                // configuração recomeçam a partida
+                clocks.retain(|timer_id, runtime| configs.get(timer_id) == Some(&runtime.config));
+                for (timer_id, runtime) in clocks.iter_mut() {
+                    let flagged = runtime.settle(now);
+                    if !flagged.is_empty() {
+                        changed.push((runtime.snapshot(timer_id, now), flagged));
+                    }
+                }
+            }
+
+            for (snapshot, flagged) in changed {
+                emit_flagged(&app, &snapshot, &flagged);
+                let _ = app.emit("chess-clock-changed", &snapshot);
+            }
+
+            std::thread::sleep(Duration::from_millis(TICK_MILLIS));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    /// Partida começada em `t0` com `players` jogadores e banco de 10 s.
+    fn started(players: usize, increment: u64, delay: u64) -> (ChessClockRuntime, Instant) {
+        let config = ChessClockConfig {
+            players: (1..=players).map(|i| format!("Player {}", i)).collect(),
+            bank: secs(10),
+            increment: secs(increment),
+            delay: secs(delay),
+        };
+        let t0 = Instant::now();
+        let mut runtime = ChessClockRuntime::new(&config);
+        runtime.started = true;
+        runtime.running_since = Some(t0);
+        (runtime, t0)
+    }
+
+    #[test]
+    fn flags_the_player_whose_bank_runs_out() {
+        let (mut runtime, t0) = started(2, 0, 0);
+        assert!(runtime.settle(t0 + secs(9)).is_empty());
+        assert_eq!(runtime.settle(t0 + secs(12)), [0]);
+        assert!(runtime.players[0].flagged);
+        assert_eq!(runtime.players[0].remaining, Duration::ZERO);
+        // Com um jogador só sobrando a partida acaba
+        assert!(runtime.finished);
+        assert_eq!(runtime.status(), RunStatus::Idle);
+        assert!(runtime.settle(t0 + secs(30)).is_empty());
+    }
+
+    #[test]
+    fn next_player_starts_when_the_bank_ran_out() {
+        let (mut runtime, t0) = started(3, 0, 0);
+        assert_eq!(runtime.settle(t0 + secs(15)), [0]);
+        assert!(!runtime.finished);
+        assert_eq!(runtime.active, 1);
+        assert_eq!(runtime.running_since, Some(t0 + secs(10)));
+        assert_eq!(runtime.remaining_at(1, t0 + secs(15)), secs(5));
+
+        // Dois jogadores perdem no mesmo settle e a partida acaba
+        let (mut runtime, t0) = started(3, 0, 0);
+        assert_eq!(runtime.settle(t0 + secs(25)), [0, 1]);
+        assert!(runtime.finished);
+    }
+
+    #[test]
+    fn skips_flagged_players() {
+        let (mut runtime, t0) = started(3, 0, 0);
+        assert_eq!(runtime.settle(t0 + secs(10)), [0]);
+        runtime.pass_turn(t0 + secs(11));
+        assert_eq!(runtime.active, 2);
+        runtime.pass_turn(t0 + secs(12));
+        assert_eq!(runtime.active, 1);
+        assert_eq!(runtime.players[1].moves, 1);
+        assert_eq!(runtime.players[2].moves, 1);
+    }
+
+    #[test]
+    fn increment_is_added_after_each_move() {
+        let (mut runtime, t0) = started(2, 2, 0);
+        runtime.pass_turn(t0 + secs(3));
+        assert_eq!(runtime.players[0].remaining, secs(9));
+        assert_eq!(runtime.players[0].moves, 1);
+        assert_eq!(runtime.active, 1);
+        assert_eq!(runtime.remaining_at(1, t0 + secs(4)), secs(9));
+    }
+
+    #[test]
+    fn delay_is_not_taken_from_the_bank() {
+        let (mut runtime, t0) = started(2, 0, 3);
+        runtime.pass_turn(t0 + secs(2));
+        assert_eq!(runtime.players[0].remaining, secs(10));
+
+        // Jogada do jogador 2 começou em t0 + 2: 3 s de delay e depois 10 s de banco
+        assert!(runtime.settle(t0 + secs(14)).is_empty());
+        assert_eq!(runtime.remaining_at(1, t0 + secs(14)), secs(1));
+        assert_eq!(runtime.settle(t0 + secs(15)), [1]);
+        assert!(runtime.finished);
+    }
+}
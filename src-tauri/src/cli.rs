@@ -0,0 +1,729 @@
+use crate::commands::{self, load_config};
+use crate::engine::{self, RunStatus};
+use crate::history::{self, RunLabel, TriggerSource};
+use crate::logwatch;
+use crate::quick;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const EXIT_OK: i32 = 0;
+/// A instância recebeu o pedido, mas a ação falhou (ou o timer não iniciou)
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_NOT_RUNNING: i32 = 3;
+/// Timer ou preset não encontrado
+pub const EXIT_NOT_FOUND: i32 = 4;
+
+/// Mesmo valor de `identifier` em tauri.conf.json: o cliente precisa achar a pasta de dados
+/// sem um `AppHandle`.
+const APP_IDENTIFIER: &str = "com.lucas-diniz.simple-overlay-timer";
+/// Porta e token da instância em execução, na pasta de dados do app
+const ENDPOINT_FILE: &str = "cli.json";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+const USAGE: &str = "Usage: simple-overlay-timer [--json] <command> [options]
+
+Commands:
+  start <timer> [--label <text>] [--note <text>]   Start a timer by id or name
+  quick <duration> [--save]                        Start a quick timer (90, 5m, 1:30, 1.5h);
+                                                   --save adds it to the config instead
+  preset <preset>                                  Create a timer from a preset (id or name)
+  reset-all                                        Reset every timer
+  help                                             Show this help
+
+The app must already be running. With --json the result is printed as one JSON object.
+
+A running timer follows its trigger policy, so start may restart, pause, reset or stack it;
+the action applied is printed, and start exits with 1 when the timer did not start.
+
+Exit codes: 0 ok, 1 action failed or nothing started, 2 bad usage, 3 app not running, 4 timer or preset not found";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Endpoint {
+    port: u16,
+    token: String,
+    pid: u32,
+}
+
+/// Ação pedida pela linha de comando.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+enum CliAction {
+    Start {
+        timer: String,
+        label: Option<String>,
+        note: Option<String>,
+    },
+    Quick {
+        seconds: u64,
+        save: bool,
+    },
+    Preset {
+        preset: String,
+    },
+    ResetAll,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CliRequest {
+    token: String,
+    #[serde(flatten)]
+    action: CliAction,
+}
+
+/// Resposta da instância, impressa como está no modo `--json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CliResponse {
+    ok: bool,
+    message: String,
+    #[serde(default)]
+    data: Value,
+    exit_code: i32,
+}
+
+impl CliResponse {
+    fn success(message: String, data: Value) -> Self {
+        CliResponse { ok: true, message, data, exit_code: EXIT_OK }
+    }
+
+    /// Pedido entregue, mas sem o efeito pedido
+    fn not_done(message: String, data: Value) -> Self {
+        CliResponse { ok: false, message, data, exit_code: EXIT_FAILED }
+    }
+
+    fn error(exit_code: i32, message: String) -> Self {
+        CliResponse { ok: false, message, data: Value::Null, exit_code }
+    }
+}
+
+/// Executa um subcomando e devolve o código de saída; `None` quando não há argumentos
+/// e o app deve abrir normalmente.
+pub fn run_from_args(args: Vec<String>) -> Option<i32> {
+    // O macOS passa -psn_* ao abrir pelo Finder
+    let args: Vec<String> = args.into_iter().filter(|a| !a.starts_with("-psn_")).collect();
+    if args.is_empty() {
+        return None;
+    }
+    attach_console();
+
+    let (json_output, args) = split_json_flag(&args);
+
+    let response = match parse_action(args) {
+        Ok(Some(action)) => send(action),
+        Ok(None) => {
+            println!("{}", USAGE);
+            return Some(EXIT_OK);
+        }
+        Err(message) => CliResponse::error(EXIT_USAGE, message),
+    };
+
+    if json_output {
+        println!("{}", serde_json::to_string(&response).unwrap_or_default());
+    } else if response.ok {
+        println!("{}", response.message);
+    } else {
+        eprintln!("Error: {}", response.message);
+        if response.exit_code == EXIT_USAGE {
+            eprintln!("Run with --help for usage.");
+        }
+    }
+    Some(response.exit_code)
+}
+
+/// `--json` só vale antes do subcomando; depois dele é argumento (ex.: nome de um timer).
+fn split_json_flag(args: &[String]) -> (bool, &[String]) {
+    match args.split_first() {
+        Some((first, rest)) if first == "--json" => (true, rest),
+        _ => (false, args),
+    }
+}
+
+/// `Ok(None)` = pedir ajuda.
+fn parse_action(args: &[String]) -> Result<Option<CliAction>, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(None);
+    };
+
+    let mut positional: Vec<String> = Vec::new();
+    let mut label = None;
+    let mut note = None;
+    let mut save = false;
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "--label" | "--note" => {
+                let value = inline
+                    .or_else(|| rest.next().cloned())
+                    .ok_or_else(|| format!("{} needs a value", flag))?;
+                if flag == "--label" {
+                    label = Some(value);
+                } else {
+                    note = Some(value);
+                }
+            }
+            "--save" => save = true,
+            "--help" | "-h" => return Ok(None),
+            _ if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    if (label.is_some() || note.is_some()) && command != "start" {
+        return Err("--label and --note only apply to start".to_string());
+    }
+    if save && command != "quick" {
+        return Err("--save only applies to quick".to_string());
+    }
+
+    let single = |what: &str| -> Result<String, String> {
+        match positional.as_slice() {
+            [value] => Ok(value.clone()),
+            [] => Err(format!("{} needs a {}", command, what)),
+            _ => Err(format!("Too many arguments for {}", command)),
+        }
+    };
+
+    match command.as_str() {
+        "help" | "--help" | "-h" => Ok(None),
+        "start" => Ok(Some(CliAction::Start { timer: single("timer id or name")?, label, note })),
+        "quick" => {
+            let text = single("duration")?;
+            let seconds = logwatch::parse_duration(&text)
+                .ok_or_else(|| format!("Invalid duration: {} (try 90, 5m, 1:30 or 1.5h)", text))?;
+            Ok(Some(CliAction::Quick { seconds, save }))
+        }
+        "preset" => Ok(Some(CliAction::Preset { preset: single("preset id or name")? })),
+        "reset-all" => {
+            if !positional.is_empty() {
+                return Err("reset-all takes no arguments".to_string());
+            }
+            Ok(Some(CliAction::ResetAll))
+        }
+        _ => Err(format!("Unknown command: {}", command)),
+    }
+}
+
+/// Pasta de dados do app, calculada como o Tauri faz (`data_dir` + identifier).
+fn endpoint_path() -> Option<PathBuf> {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|h| h.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| home().map(|h| h.join(".local").join("share")))
+    };
+    Some(base?.join(APP_IDENTIFIER).join(ENDPOINT_FILE))
+}
+
+/// Envia a ação à instância em execução e espera a resposta.
+fn send(action: CliAction) -> CliResponse {
+    let not_running = || CliResponse::error(EXIT_NOT_RUNNING, "Overlay Timer is not running".to_string());
+    let Some(endpoint) = endpoint_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str::<Endpoint>(&text).ok())
+    else {
+        return not_running();
+    };
+
+    let request = CliRequest { token: endpoint.token.clone(), action };
+    match exchange(&endpoint, &request) {
+        Ok(response) => response,
+        // Arquivo de uma execução anterior que não terminou direito
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused => not_running(),
+        Err(e) => CliResponse::error(
+            EXIT_NOT_RUNNING,
+            format!("Could not reach the running instance (pid {}): {}", endpoint.pid, e),
+        ),
+    }
+}
+
+fn exchange(endpoint: &Endpoint, request: &CliRequest) -> std::io::Result<CliResponse> {
+    let address = SocketAddr::from(([127, 0, 0, 1], endpoint.port));
+    let mut stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    serde_json::from_str(&reply).map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(windows)]
+fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // Em release o app não tem console; escreve no terminal de quem chamou
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+/// Token aleatório (gerador do sistema) que o cliente precisa ler do arquivo para falar
+/// com a instância.
+fn new_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Could not generate a token: {}", e))?;
+    Ok(hex::encode(bytes))
+}
+
+fn write_endpoint(app: &AppHandle, endpoint: &Endpoint) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(dir.join(ENDPOINT_FILE))
+        .map_err(|e| format!("Failed to write {}: {}", ENDPOINT_FILE, e))?;
+    let text = serde_json::to_string(endpoint).map_err(|e| e.to_string())?;
+    file.write_all(text.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", ENDPOINT_FILE, e))
+}
+
+/// Abre a porta local que recebe os subcomandos da linha de comando.
+pub fn start_control_server(app: AppHandle) {
+    let listener = match TcpListener::bind(("127.0.0.1", 0)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("[CLI] WARNING: Could not open control port: {}", e);
+            return;
+        }
+    };
+    let token = match new_token() {
+        Ok(token) => token,
+        Err(e) => {
+            println!("[CLI] WARNING: {}", e);
+            return;
+        }
+    };
+    let endpoint = Endpoint {
+        port: listener.local_addr().map(|a| a.port()).unwrap_or(0),
+        token,
+        pid: std::process::id(),
+    };
+    if let Err(e) = write_endpoint(&app, &endpoint) {
+        println!("[CLI] WARNING: {}", e);
+        return;
+    }
+    println!("[CLI] Listening for commands on 127.0.0.1:{}", endpoint.port);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let app = app.clone();
+                    let token = endpoint.token.clone();
+                    std::thread::spawn(move || handle_connection(&app, stream, &token));
+                }
+                Err(e) => println!("[CLI] WARNING: Failed to accept connection: {}", e),
+            }
+        }
+    });
+}
+
+fn handle_connection(app: &AppHandle, stream: TcpStream, token: &str) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+
+    let mut line = String::new();
+    if let Err(e) = BufReader::new(stream.take(MAX_REQUEST_BYTES)).read_line(&mut line) {
+        println!("[CLI] WARNING: Failed to read request: {}", e);
+        return;
+    }
+
+    let response = match serde_json::from_str::<CliRequest>(&line) {
+        Ok(request) if request.token == token => {
+            println!("[CLI] Received {:?}", request.action);
+            execute(app, request.action).unwrap_or_else(|(code, message)| CliResponse::error(code, message))
+        }
+        Ok(_) => CliResponse::error(EXIT_FAILED, "Invalid token".to_string()),
+        Err(e) => CliResponse::error(EXIT_USAGE, format!("Invalid request: {}", e)),
+    };
+    if !response.ok {
+        println!("[CLI] WARNING: {}", response.message);
+    }
+
+    let mut reply = serde_json::to_string(&response).unwrap_or_default();
+    reply.push('\n');
+    let _ = writer.write_all(reply.as_bytes());
+}
+
+/// Nome normalizado para comparar com o que foi digitado: "Boss CD" == "boss-cd".
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// O que o overlay faz com `start-specific-timer`, igual ao start do OverlayIcon: timers
+/// ativos seguem a política de disparo (`triggerPolicy`) e alguns tipos só alternam.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum StartOutcome {
+    Started,
+    Restarted,
+    Stacked,
+    Resumed,
+    Paused,
+    Reset,
+    Acknowledged,
+    TurnPassed,
+    ChargeUsed,
+    /// Ativo com a política `ignore`
+    Ignored,
+    /// Alarmes e contagens até reset não são iniciados à mão
+    NotStartable,
+}
+
+impl StartOutcome {
+    /// Começa uma execução nova (onde o rótulo pedido se aplica)
+    fn starts_run(self) -> bool {
+        matches!(self, StartOutcome::Started | StartOutcome::Restarted | StartOutcome::Stacked)
+    }
+
+    /// Se o timer fica contando depois do pedido
+    fn counts(self) -> bool {
+        self.starts_run()
+            || matches!(self, StartOutcome::Resumed | StartOutcome::TurnPassed | StartOutcome::ChargeUsed)
+    }
+
+    fn describe(self, name: &str, policy: &str) -> String {
+        match self {
+            StartOutcome::Started => format!("Started {}", name),
+            StartOutcome::Restarted => format!("Restarted {}", name),
+            StartOutcome::Stacked => format!("Started another instance of {}", name),
+            StartOutcome::Resumed => format!("Resumed {}", name),
+            StartOutcome::Paused => format!("Paused {} (it was running; trigger policy \"{}\")", name, policy),
+            StartOutcome::Reset => format!("Reset {} (it was running; trigger policy \"{}\")", name, policy),
+            StartOutcome::Acknowledged => format!("Acknowledged the overtime of {}; it did not restart", name),
+            StartOutcome::TurnPassed => format!("Passed the turn on {}", name),
+            StartOutcome::ChargeUsed => format!("Used a charge of {}", name),
+            StartOutcome::Ignored => format!("{} is already running (trigger policy \"{}\")", name, policy),
+            StartOutcome::NotStartable => format!("{} runs on its own schedule and cannot be started", name),
+        }
+    }
+}
+
+fn trigger_policy(icon: &Value) -> &str {
+    icon.get("triggerPolicy").and_then(|p| p.as_str()).unwrap_or("restart")
+}
+
+fn start_outcome(icon: &Value, status: RunStatus) -> StartOutcome {
+    match icon.get("timerType").and_then(|t| t.as_str()).unwrap_or("countdown") {
+        // Alarmes tocam na hora marcada e contagens até reset rodam sozinhas
+        "alarm" | "reset" => return StartOutcome::NotStartable,
+        "charges" => return StartOutcome::ChargeUsed,
+        "pomodoro" => {
+            return match status {
+                RunStatus::Running => StartOutcome::Paused,
+                RunStatus::Paused => StartOutcome::Resumed,
+                _ => StartOutcome::Started,
+            }
+        }
+        "chessClock" => {
+            return match status {
+                RunStatus::Running => StartOutcome::TurnPassed,
+                RunStatus::Paused => StartOutcome::Resumed,
+                RunStatus::Idle | RunStatus::Overtime => StartOutcome::Started,
+            }
+        }
+        _ => {}
+    }
+    match (status, trigger_policy(icon)) {
+        (RunStatus::Idle, _) => StartOutcome::Started,
+        (RunStatus::Overtime, _) => StartOutcome::Acknowledged,
+        (_, "ignore") => StartOutcome::Ignored,
+        (RunStatus::Running, "pause") => StartOutcome::Paused,
+        (RunStatus::Paused, "pause") => StartOutcome::Resumed,
+        (_, "reset") => StartOutcome::Reset,
+        (_, "stack") => StartOutcome::Stacked,
+        _ => StartOutcome::Restarted,
+    }
+}
+
+/// Resposta do `start`: código 1 quando o timer não ficou contando.
+fn start_response(outcome: StartOutcome, timer_id: &str, name: &str, policy: &str) -> CliResponse {
+    let message = outcome.describe(name, policy);
+    let data = json!({ "timerId": timer_id, "name": name, "outcome": outcome });
+    if outcome.counts() {
+        CliResponse::success(message, data)
+    } else {
+        CliResponse::not_done(message, data)
+    }
+}
+
+/// Item de uma lista do config pelo id ou pelo nome.
+fn find_by_id_or_name(items: &[Value], query: &str, kind: &str) -> Result<Value, (i32, String)> {
+    let id_of = |item: &Value| item.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    if let Some(item) = items.iter().find(|item| id_of(item) == query) {
+        return Ok(item.clone());
+    }
+
+    let wanted = slug(query);
+    let matches: Vec<&Value> = items
+        .iter()
+        .filter(|item| item.get("name").and_then(|v| v.as_str()).map(slug).as_deref() == Some(wanted.as_str()))
+        .collect();
+    match matches.as_slice() {
+        [item] => Ok((*item).clone()),
+        [] => Err((EXIT_NOT_FOUND, format!("No {} named \"{}\"", kind, query))),
+        _ => Err((
+            EXIT_USAGE,
+            format!(
+                "More than one {} is named \"{}\"; use the id: {}",
+                kind,
+                query,
+                matches.iter().map(|item| id_of(item)).collect::<Vec<_>>().join(", ")
+            ),
+        )),
+    }
+}
+
+fn load_list(app: &AppHandle, key: &str) -> Result<Vec<Value>, (i32, String)> {
+    let config_str = load_config(app.clone()).map_err(|e| (EXIT_FAILED, e))?;
+    let mut config: Value =
+        serde_json::from_str(&config_str).map_err(|e| (EXIT_FAILED, format!("Failed to parse config: {}", e)))?;
+    if key == "icons" {
+        quick::with_ephemeral(&mut config);
+    }
+    Ok(config.get(key).and_then(|v| v.as_array()).cloned().unwrap_or_default())
+}
+
+fn execute(app: &AppHandle, action: CliAction) -> Result<CliResponse, (i32, String)> {
+    let failed = |e: String| (EXIT_FAILED, e);
+    match action {
+        CliAction::Start { timer, label, note } => {
+            let icon = find_by_id_or_name(&load_list(app, "icons")?, &timer, "timer")?;
+            let timer_id = icon.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let name = icon.get("name").and_then(|v| v.as_str()).unwrap_or(&timer_id).to_string();
+
+            let outcome = start_outcome(&icon, engine::run_status(&timer_id));
+            let label = RunLabel::new(label, note);
+            if outcome.starts_run() && label != RunLabel::default() {
+                history::note_label(&timer_id, label);
+            }
+            // Sem efeito no overlay; para alarmes, evita dispensar um que esteja tocando
+            if !matches!(outcome, StartOutcome::Ignored | StartOutcome::NotStartable) {
+                commands::start_timer_from(app, timer_id.clone(), TriggerSource::Cli).map_err(failed)?;
+            }
+
+            Ok(start_response(outcome, &timer_id, &name, trigger_policy(&icon)))
+        }
+        CliAction::Quick { seconds, save } => {
+            if save {
+                commands::quick_create_timer(app.clone(), seconds, Some(false)).map_err(failed)?;
+                return Ok(CliResponse::success(
+                    format!("Added a {}s timer to the config", seconds),
+                    json!({ "durationSeconds": seconds }),
+                ));
+            }
+            let timer_id = quick::create_ephemeral(app, seconds, TriggerSource::Cli).map_err(failed)?;
+            Ok(CliResponse::success(
+                format!("Started a {}s quick timer ({})", seconds, timer_id),
+                json!({ "timerId": timer_id, "durationSeconds": seconds }),
+            ))
+        }
+        CliAction::Preset { preset } => {
+            let preset = find_by_id_or_name(&load_list(app, "timerPresets")?, &preset, "preset")?;
+            let preset_id = preset.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+            let timer_id = commands::create_timer_from_preset_with_id(app, preset_id).map_err(failed)?;
+            Ok(CliResponse::success(
+                format!("Created timer {} from preset {}", timer_id, preset_id),
+                json!({ "timerId": timer_id, "presetId": preset_id }),
+            ))
+        }
+        CliAction::ResetAll => {
+            history::note_trigger_all(TriggerSource::Cli);
+            let overlay = app
+                .get_webview_window("overlay")
+                .ok_or_else(|| failed("Overlay window not found".to_string()))?;
+            overlay
+                .emit("reset-all-timers", ())
+                .map_err(|e| failed(format!("Failed to emit reset event: {}", e)))?;
+            Ok(CliResponse::success("Reset all timers".to_string(), Value::Null))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn json_flag_only_counts_before_the_subcommand() {
+        let before = args(&["--json", "start", "boss"]);
+        assert_eq!(split_json_flag(&before), (true, &before[1..]));
+        // Depois do subcomando é o nome do timer
+        let after = args(&["start", "--json"]);
+        assert_eq!(split_json_flag(&after), (false, &after[..]));
+        assert_eq!(
+            parse_action(&after),
+            Err("Unknown option: --json".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_subcommands_and_options() {
+        let cases: Vec<(&[&str], Option<CliAction>)> = vec![
+            (&["quick", "5m", "--save"], Some(CliAction::Quick { seconds: 300, save: true })),
+            (&["quick", "90"], Some(CliAction::Quick { seconds: 90, save: false })),
+            (
+                &["start", "Boss CD", "--label=raid", "--note", "pull 3"],
+                Some(CliAction::Start {
+                    timer: "Boss CD".to_string(),
+                    label: Some("raid".to_string()),
+                    note: Some("pull 3".to_string()),
+                }),
+            ),
+            (&["preset", "tea"], Some(CliAction::Preset { preset: "tea".to_string() })),
+            (&["reset-all"], Some(CliAction::ResetAll)),
+            (&["help"], None),
+            (&["start", "--help"], None),
+            (&[], None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_action(&args(input)), Ok(expected), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn bad_usage_exits_with_2() {
+        let cases: [&[&str]; 9] = [
+            &["bogus"],
+            &["start"],
+            &["start", "a", "b"],
+            &["start", "boss", "--label"],
+            &["quick", "soon"],
+            &["quick", "5m", "--verbose"],
+            &["preset", "tea", "--save"],
+            &["reset-all", "now"],
+            &["--json", "quick"],
+        ];
+        for input in cases {
+            assert_eq!(run_from_args(args(input)), Some(EXIT_USAGE), "{:?}", input);
+        }
+        assert_eq!(run_from_args(args(&["help"])), Some(EXIT_OK));
+        assert_eq!(run_from_args(Vec::new()), None);
+    }
+
+    #[test]
+    fn slug_ignores_case_and_punctuation() {
+        let cases = [
+            ("Boss CD", "boss-cd"),
+            ("boss-cd", "boss-cd"),
+            ("  Boss__CD! ", "boss-cd"),
+            ("Chá 5'", "chá-5"),
+            ("---", ""),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(slug(input), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn finds_by_exact_id_then_by_name() {
+        let items = vec![
+            json!({ "id": "boss", "name": "Tea" }),
+            json!({ "id": "tea-1", "name": "Boss CD" }),
+            json!({ "id": "tea-2", "name": "boss cd" }),
+            json!({ "id": "egg", "name": "Soft Egg" }),
+        ];
+        let id = |query: &str| find_by_id_or_name(&items, query, "timer").map(|item| item["id"].clone());
+
+        // O id exato ganha de um nome igual
+        assert_eq!(id("boss"), Ok(json!("boss")));
+        assert_eq!(id("SOFT egg"), Ok(json!("egg")));
+        assert_eq!(id("tea"), Ok(json!("boss")));
+
+        let (code, message) = id("Boss-CD").unwrap_err();
+        assert_eq!(code, EXIT_USAGE);
+        assert!(message.contains("tea-1, tea-2"), "{}", message);
+        assert_eq!(id("nope").unwrap_err().0, EXIT_NOT_FOUND);
+    }
+
+    #[test]
+    fn start_follows_the_trigger_policy() {
+        let timer = |policy: Option<&str>| json!({ "id": "t", "timerType": "countdown", "triggerPolicy": policy });
+        let cases = [
+            (timer(None), RunStatus::Idle, StartOutcome::Started),
+            (timer(None), RunStatus::Running, StartOutcome::Restarted),
+            (timer(Some("ignore")), RunStatus::Running, StartOutcome::Ignored),
+            (timer(Some("pause")), RunStatus::Running, StartOutcome::Paused),
+            (timer(Some("pause")), RunStatus::Paused, StartOutcome::Resumed),
+            (timer(Some("reset")), RunStatus::Paused, StartOutcome::Reset),
+            (timer(Some("stack")), RunStatus::Running, StartOutcome::Stacked),
+            (timer(Some("stack")), RunStatus::Overtime, StartOutcome::Acknowledged),
+            (json!({ "timerType": "alarm" }), RunStatus::Idle, StartOutcome::NotStartable),
+            (json!({ "timerType": "pomodoro" }), RunStatus::Running, StartOutcome::Paused),
+            (json!({ "timerType": "chessClock" }), RunStatus::Running, StartOutcome::TurnPassed),
+            (json!({ "timerType": "charges" }), RunStatus::Idle, StartOutcome::ChargeUsed),
+        ];
+        for (icon, status, expected) in cases {
+            assert_eq!(start_outcome(&icon, status), expected, "{} {:?}", icon, status);
+        }
+    }
+
+    #[test]
+    fn start_exits_non_zero_when_the_timer_does_not_count() {
+        let cases = [
+            (StartOutcome::Started, EXIT_OK),
+            (StartOutcome::Restarted, EXIT_OK),
+            (StartOutcome::Stacked, EXIT_OK),
+            (StartOutcome::Resumed, EXIT_OK),
+            (StartOutcome::TurnPassed, EXIT_OK),
+            (StartOutcome::ChargeUsed, EXIT_OK),
+            (StartOutcome::Paused, EXIT_FAILED),
+            (StartOutcome::Reset, EXIT_FAILED),
+            (StartOutcome::Acknowledged, EXIT_FAILED),
+            (StartOutcome::Ignored, EXIT_FAILED),
+            (StartOutcome::NotStartable, EXIT_FAILED),
+        ];
+        for (outcome, code) in cases {
+            let response = start_response(outcome, "t", "Tea", "pause");
+            assert_eq!((response.exit_code, response.ok), (code, code == EXIT_OK), "{:?}", outcome);
+            assert_eq!(response.data["outcome"], serde_json::to_value(outcome).unwrap());
+        }
+        assert_eq!(
+            start_response(StartOutcome::Paused, "t", "Tea", "pause").message,
+            "Paused Tea (it was running; trigger policy \"pause\")"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::commands::load_config;
+use crate::engine;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+const TICK_MILLIS: u64 = 500;
+/// Atrasos menores que isso são só o agendador do sistema, não suspensão
+const SUSPEND_THRESHOLD_SECS: f64 = 3.0;
+/// Sincronizações de NTP costumam corrigir poucos milissegundos; só saltos maiores contam
+const JUMP_THRESHOLD_SECS: f64 = 2.0;
+
+/// O que um timer faz enquanto o computador está suspenso (`suspendPolicy`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuspendPolicy {
+    /// Continua contando o tempo real, como se não tivesse dormido (padrão)
+    RealTime,
+    /// Congela durante a suspensão e continua de onde parou
+    Pause,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CorrectionReason {
+    Suspend,
+    ClockJump,
+}
+
+/// Correção aplicada a uma execução, enviada ao overlay (`timer-corrected`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerCorrection {
+    pub timer_id: String,
+    pub instance: u32,
+    pub reason: CorrectionReason,
+    pub policy: SuspendPolicy,
+    /// Quanto somar ao início da execução no overlay (medido com `Date.now()`)
+    pub shift_ms: i64,
+}
+
+/// Relógio monotônico que continua contando durante a suspensão, quando o sistema oferece um.
+#[cfg(target_os = "linux")]
+fn boot_clock_secs() -> Option<f64> {
+    // /proc/uptime usa CLOCK_BOOTTIME, que inclui o tempo suspenso
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    uptime.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(windows)]
+fn boot_clock_secs() -> Option<f64> {
+    // GetTickCount64 continua contando enquanto o Windows dorme
+    let millis = unsafe { windows::Win32::System::SystemInformation::GetTickCount64() };
+    Some(millis as f64 / 1000.0)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn boot_clock_secs() -> Option<f64> {
+    None
+}
+
+fn wall_clock_secs() -> f64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+fn suspend_policies(app: &AppHandle) -> HashMap<String, SuspendPolicy> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|config| config.get("icons").and_then(|i| i.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|icon| {
+            let id = icon.get("id")?.as_str()?;
+            let policy = match icon.get("suspendPolicy").and_then(|p| p.as_str()) {
+                Some("pause") => SuspendPolicy::Pause,
+                _ => SuspendPolicy::RealTime,
+            };
+            Some((id.to_string(), policy))
+        })
+        .collect()
+}
+
+/// O que aconteceu entre duas leituras dos relógios.
+#[derive(Debug, Clone, Copy, Default)]
+struct ClockAnomaly {
+    /// Tempo real em que o processo ficou parado além do esperado (suspensão)
+    suspended_secs: f64,
+    /// Parte da suspensão que o `Instant` contou (depende do sistema)
+    monotonic_counted_secs: f64,
+    /// Quanto o relógio de parede andou além do tempo real (negativo = voltou)
+    wall_jump_secs: f64,
+}
+
+fn classify(monotonic: f64, wall: f64, boot: Option<f64>) -> ClockAnomaly {
+    let expected = TICK_MILLIS as f64 / 1000.0;
+    let monotonic_extra = (monotonic - expected).max(0.0);
+    let mut anomaly = match boot {
+        Some(real) => ClockAnomaly {
+            suspended_secs: (real - expected).max(0.0),
+            monotonic_counted_secs: monotonic_extra,
+            wall_jump_secs: wall - real,
+        },
+        // Sem relógio de boot não dá para separar suspensão de um salto para frente;
+        // suspensão é bem mais comum, então salto positivo grande conta como suspensão
+        None => {
+            let jump = wall - monotonic;
+            if jump > SUSPEND_THRESHOLD_SECS {
+                ClockAnomaly {
+                    suspended_secs: monotonic_extra + jump,
+                    monotonic_counted_secs: monotonic_extra,
+                    wall_jump_secs: 0.0,
+                }
+            } else {
+                ClockAnomaly {
+                    suspended_secs: monotonic_extra,
+                    monotonic_counted_secs: monotonic_extra,
+                    wall_jump_secs: jump,
+                }
+            }
+        }
+    };
+    if anomaly.suspended_secs < SUSPEND_THRESHOLD_SECS {
+        anomaly.suspended_secs = 0.0;
+        anomaly.monotonic_counted_secs = 0.0;
+    }
+    if anomaly.wall_jump_secs.abs() < JUMP_THRESHOLD_SECS {
+        anomaly.wall_jump_secs = 0.0;
+    }
+    anomaly
+}
+
+/// Corrige as execuções ativas no engine e no overlay conforme a política de cada timer.
+fn apply_corrections(app: &AppHandle, anomaly: ClockAnomaly) {
+    let policies = suspend_policies(app);
+    let reason = if anomaly.suspended_secs > 0.0 {
+        CorrectionReason::Suspend
+    } else {
+        CorrectionReason::ClockJump
+    };
+    let overlay = app.get_webview_window("overlay");
+
+    for (timer_id, instance) in engine::active_runs() {
+        let policy = policies.get(&timer_id).copied().unwrap_or(SuspendPolicy::RealTime);
+        // Tempo que a execução deveria ganhar com a suspensão
+        let desired = match policy {
+            SuspendPolicy::RealTime => anomaly.suspended_secs,
+            SuspendPolicy::Pause => 0.0,
+        };
+        engine::shift_run(&timer_id, instance, desired - anomaly.monotonic_counted_secs);
+
+        // O overlay mede com o relógio de parede, que inclui a suspensão e o salto
+        let shift_secs = anomaly.wall_jump_secs + (anomaly.suspended_secs - desired);
+        let correction = TimerCorrection {
+            timer_id,
+            instance,
+            reason,
+            policy,
+            shift_ms: (shift_secs * 1000.0).round() as i64,
+        };
+        println!(
+            "[CLOCK] Correcting {}#{} by {}ms ({:?}, {:?})",
+            correction.timer_id, correction.instance, correction.shift_ms, correction.reason, correction.policy
+        );
+        if let Some(overlay) = &overlay {
+            let _ = overlay.emit("timer-corrected", &correction);
+        }
+    }
+}
+
+/// Thread que compara o relógio monotônico com o de parede (e o de boot) para detectar
+/// suspensão/retomada e mudanças no relógio do sistema (NTP, horário de verão, ajuste manual).
+pub fn start_clock_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[CLOCK] Monitor started (boot clock available: {})", boot_clock_secs().is_some());
+        let mut last_monotonic = Instant::now();
+        let mut last_wall = wall_clock_secs();
+        let mut last_boot = boot_clock_secs();
+
+        loop {
+            std::thread::sleep(Duration::from_millis(TICK_MILLIS));
+
+            let monotonic = Instant::now();
+            let wall = wall_clock_secs();
+            let boot = boot_clock_secs();
+            let anomaly = classify(
+                monotonic.duration_since(last_monotonic).as_secs_f64(),
+                wall - last_wall,
+                boot.zip(last_boot).map(|(now, before)| now - before),
+            );
+            last_monotonic = monotonic;
+            last_wall = wall;
+            last_boot = boot;
+
+            if anomaly.suspended_secs == 0.0 && anomaly.wall_jump_secs == 0.0 {
+                continue;
+            }
+
+            if anomaly.suspended_secs > 0.0 {
+                println!("[CLOCK] Resumed after ~{:.1}s suspended", anomaly.suspended_secs);
+                let _ = app.emit(
+                    "system-resumed",
+                    json!({ "suspendedMs": (anomaly.suspended_secs * 1000.0).round() as i64 }),
+                );
+            }
+            if anomaly.wall_jump_secs != 0.0 {
+                println!("[CLOCK] Wall clock jumped by {:.1}s", anomaly.wall_jump_secs);
+                let _ = app.emit(
+                    "clock-jumped",
+                    json!({ "deltaMs": (anomaly.wall_jump_secs * 1000.0).round() as i64 }),
+                );
+            }
+
+            apply_corrections(&app, anomaly);
+            // Libera os timers do overlay que seguraram a contagem ao ver o salto
+            let _ = app.emit("clock-corrected", ());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(cases: &[(&str, f64, f64, Option<f64>, (f64, f64, f64))]) {
+        for &(name, monotonic, wall, boot, expected) in cases {
+            let anomaly = classify(monotonic, wall, boot);
+            let got = (
+                anomaly.suspended_secs,
+                anomaly.monotonic_counted_secs,
+                anomaly.wall_jump_secs,
+            );
+            assert_eq!(got, expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn classifies_with_boot_clock() {
+        // (caso, monotônico, parede, boot) → (suspenso, contado pelo monotônico, salto)
+        check(&[
+            ("suspend", 0.5, 60.5, Some(60.5), (60.0, 0.0, 0.0)),
+            ("suspend counted by monotonic", 60.5, 60.5, Some(60.5), (60.0, 60.0, 0.0)),
+            ("forward jump", 0.5, 3600.5, Some(0.5), (0.0, 0.0, 3600.0)),
+            ("backward jump", 0.5, -59.5, Some(0.5), (0.0, 0.0, -60.0)),
+            ("suspend and jump", 0.5, 130.5, Some(60.5), (60.0, 0.0, 70.0)),
+            ("jitter", 0.7, 1.5, Some(0.9), (0.0, 0.0, 0.0)),
+        ]);
+    }
+
+    #[test]
+    fn classifies_without_boot_clock() {
+        check(&[
+            // Salto grande para frente é tratado como suspensão
+            ("suspend", 0.5, 60.5, None, (60.0, 0.0, 0.0)),
+            ("suspend counted by monotonic", 60.5, 60.5, None, (60.0, 60.0, 0.0)),
+            ("backward jump", 0.5, -59.5, None, (0.0, 0.0, -60.0)),
+            ("small forward jump", 0.5, 3.0, None, (0.0, 0.0, 2.5)),
+            ("jitter", 0.6, 1.5, None, (0.0, 0.0, 0.0)),
+        ]);
+    }
+}
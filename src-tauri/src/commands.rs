@@ -3,6 +3,25 @@ use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::alarms::{self, UpcomingAlarm};
+use crate::chains;
+use crate::charges::{self, ChargeSnapshot};
+use crate::chessclock::{self, ChessClockSnapshot};
+use crate::engine::{self, Lap, TimerEvent, TimerStateSnapshot};
+use crate::export::{self, ExportFilter, ExportFormat};
+use crate::goals::{self, GoalProgress};
+use crate::history::{self, HistoryEntry, HistoryQuery, RunLabel, RunRecord, TriggerSource};
+use crate::hooks::{self, EventHookStatus};
+use crate::logwatch::{self, LogWatchStatus};
+use crate::pomodoro::{self, PomodoroSnapshot};
+use crate::processes::{self, ProcessRuleStatus};
+use crate::profiles;
+use crate::quick;
+use crate::random::{self, RandomRoll};
+use crate::schedules;
+use crate::stats::{self, StatsGroupBy, StatsRange, StatsReport};
+use crate::webhooks::{self, WebhookStatus};
+
 #[tauri::command]
 pub fn open_overlay_devtools(app: AppHandle) -> Result<(), String> {
     log_command(
@@ -72,6 +91,42 @@ pub fn save_icon_from_path(
     Ok(format!("icons/{}.png", icon_id))
 }
 
+/// Valida as partes do config que o backend interpreta antes de gravar.
+fn validate_config(value: &Value, command: &str) -> Result<(), String> {
+    chains::validate_chain_rules(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid chain rules: {}", e));
+        e
+    })?;
+    schedules::validate_schedules(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid schedule: {}", e));
+        e
+    })?;
+    logwatch::validate_log_watchers(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid log watcher: {}", e));
+        e
+    })?;
+    alarms::validate_alarms(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid alarm: {}", e));
+        e
+    })?;
+    processes::validate_process_rules(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid process rule: {}", e));
+        e
+    })?;
+    goals::validate_goals(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid goal: {}", e));
+        e
+    })?;
+    hooks::validate_event_hooks(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid event hook: {}", e));
+        e
+    })?;
+    webhooks::validate_webhooks(value).map_err(|e| {
+        log_command(command, &format!("ERROR: Invalid webhook: {}", e));
+        e
+    })
+}
+
 #[tauri::command]
 pub fn save_config(app: AppHandle, config: String, emit_event: Option<bool>) -> Result<(), String> {
     log_command("save_config", "Starting...");
@@ -94,6 +149,11 @@ pub fn save_config(app: AppHandle, config: String, emit_event: Option<bool>) ->
         format!("Failed to create app data dir: {}", e)
     })?;
 
+    let config_value = serde_json::from_str::<Value>(&config).ok();
+    if let Some(config_value) = &config_value {
+        validate_config(config_value, "save_config")?;
+    }
+
     let config_path = app_data_dir.join("config.json");
     log_command(
         "save_config",
@@ -120,6 +180,10 @@ pub fn save_config(app: AppHandle, config: String, emit_event: Option<bool>) ->
                 // Não falhar o comando se o evento não puder ser emitido
             })
             .ok();
+        // Timers que saem do overlay (troca de perfil, timer removido) não reportam o fim
+        if let Some(config_value) = &config_value {
+            profiles::settle_hidden_runs(&app, config_value, TriggerSource::Api);
+        }
     } else {
         log_command(
             "save_config",
@@ -156,6 +220,10 @@ pub fn save_config_silent(app: AppHandle, config: String) -> Result<(), String>
         format!("Failed to create app data dir: {}", e)
     })?;
 
+    if let Ok(config_value) = serde_json::from_str::<Value>(&config) {
+        validate_config(&config_value, "save_config_silent")?;
+    }
+
     let config_path = app_data_dir.join("config.json");
     log_command(
         "save_config_silent",
@@ -268,11 +336,26 @@ pub fn set_overlay_click_through(app: AppHandle, ignore: bool) -> Result<(), Str
 }
 
 #[tauri::command]
-pub fn start_timer(app: AppHandle, timer_id: String) -> Result<(), String> {
+pub fn start_timer(
+    app: AppHandle,
+    timer_id: String,
+    label: Option<String>,
+    note: Option<String>,
+) -> Result<(), String> {
     log_command(
         "start_timer",
-        &format!("Request to start timer: {}", timer_id),
+        &format!("Request to start timer: {} (label {:?})", timer_id, label),
     );
+    let label = RunLabel::new(label, note);
+    if label != RunLabel::default() {
+        history::note_label(&timer_id, label);
+    }
+    start_timer_from(&app, timer_id, TriggerSource::Api)
+}
+
+/// Pede ao overlay para iniciar um timer, registrando a origem no histórico.
+pub fn start_timer_from(app: &AppHandle, timer_id: String, source: TriggerSource) -> Result<(), String> {
+    history::note_trigger(&timer_id, source);
     if let Some(overlay_window) = app.get_webview_window("overlay") {
         overlay_window
             .emit("start-specific-timer", timer_id)
@@ -283,17 +366,8 @@ pub fn start_timer(app: AppHandle, timer_id: String) -> Result<(), String> {
     }
 }
 
-#[tauri::command]
-pub fn quick_create_timer(app: AppHandle, duration: u64) -> Result<(), String> {
-    log_command(
-        "quick_create_timer",
-        &format!("Creating timer with duration: {}s", duration),
-    );
-
-    let config_str = load_config(app.clone())?;
-    let mut config: Value =
-        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
-
+/// Primeiro atalho `Alt+F1`..`Alt+F12` ainda não usado na configuração.
+pub fn free_quick_keybind(config: &Value) -> Option<String> {
     let icons = config
         .get("icons")
         .and_then(|i| i.as_array())
@@ -302,28 +376,63 @@ pub fn quick_create_timer(app: AppHandle, duration: u64) -> Result<(), String> {
 
     let mut used_keybinds: Vec<String> = icons
         .iter()
-        .filter_map(|icon| {
-            icon.get("keybind")
-                .and_then(|k| k.as_str())
-                .map(|s| s.to_string())
-        })
+        .flat_map(|icon| [icon.get("keybind"), icon.get("lapKeybind")])
+        .filter_map(|k| k.and_then(|k| k.as_str()).map(|s| s.to_string()))
         .collect();
 
+    let adjust_lists = icons
+        .iter()
+        .filter_map(|icon| icon.get("adjustKeybinds"))
+        .chain(config.get("adjustKeybinds"))
+        .filter_map(|list| list.as_array());
+    for list in adjust_lists {
+        used_keybinds.extend(
+            list.iter()
+                .filter_map(|a| a.get("keybind").and_then(|k| k.as_str()))
+                .map(|s| s.to_string()),
+        );
+    }
+
     if let Some(reset_keybind) = config.get("resetAllTimersKeybind").and_then(|k| k.as_str()) {
         used_keybinds.push(reset_keybind.to_string());
     }
 
-    let mut f_key = 1;
-    let mut available_keybind = format!("Alt+F{}", f_key);
-    while used_keybinds.contains(&available_keybind) && f_key <= 12 {
-        f_key += 1;
-        available_keybind = format!("Alt+F{}", f_key);
+    if let Some(groups) = config.get("timerGroups").and_then(|g| g.as_array()) {
+        used_keybinds.extend(
+            groups
+                .iter()
+                .filter_map(|g| g.get("keybind").and_then(|k| k.as_str()))
+                .map(|s| s.to_string()),
+        );
     }
 
-    if f_key > 12 {
-        return Err("No available Alt+F* shortcuts (F1-F12 all in use)".to_string());
+    (1..=12)
+        .map(|f_key| format!("Alt+F{}", f_key))
+        .find(|keybind| !used_keybinds.contains(keybind))
+}
+
+/// Cria um timer rápido. Com `ephemeral`, ele só existe na memória: começa na hora,
+/// aparece no overlay e some ao terminar (a menos que seja mantido com `keep_quick_timer`).
+#[tauri::command]
+pub fn quick_create_timer(app: AppHandle, duration: u64, ephemeral: Option<bool>) -> Result<(), String> {
+    log_command(
+        "quick_create_timer",
+        &format!("Creating timer with duration: {}s (ephemeral: {})", duration, ephemeral.unwrap_or(false)),
+    );
+
+    if ephemeral.unwrap_or(false) {
+        let timer_id = quick::create_ephemeral(&app, duration, TriggerSource::Api)?;
+        log_command("quick_create_timer", &format!("Ephemeral timer started: {}", timer_id));
+        return Ok(());
     }
 
+    let config_str = load_config(app.clone())?;
+    let mut config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    let available_keybind = free_quick_keybind(&config)
+        .ok_or_else(|| "No available Alt+F* shortcuts (F1-F12 all in use)".to_string())?;
+
     log_command(
         "quick_create_timer",
         &format!("Using keybind: {}", available_keybind),
@@ -338,7 +447,11 @@ pub fn quick_create_timer(app: AppHandle, duration: u64) -> Result<(), String> {
         "timerType": "countdown"
     });
 
-    let mut icons_vec = icons;
+    let mut icons_vec = config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .cloned()
+        .unwrap_or_default();
     icons_vec.push(new_icon);
     config["icons"] = json!(icons_vec);
 
@@ -351,6 +464,19 @@ pub fn quick_create_timer(app: AppHandle, duration: u64) -> Result<(), String> {
     Ok(())
 }
 
+/// Timers rápidos efêmeros em execução (mesmo formato dos ícones da configuração).
+#[tauri::command]
+pub fn get_quick_timers() -> Result<Vec<Value>, String> {
+    Ok(quick::ephemeral_timers())
+}
+
+/// Promove um timer rápido efêmero para a configuração, sem interromper a execução.
+#[tauri::command]
+pub fn keep_quick_timer(app: AppHandle, timer_id: String) -> Result<(), String> {
+    log_command("keep_quick_timer", &format!("Keeping quick timer: {}", timer_id));
+    quick::keep(&app, &timer_id)
+}
+
 #[tauri::command]
 pub fn create_timer_from_preset(app: AppHandle, preset_id: String) -> Result<(), String> {
     log_command(
@@ -358,6 +484,16 @@ pub fn create_timer_from_preset(app: AppHandle, preset_id: String) -> Result<(),
         &format!("Creating timer from preset: {}", preset_id),
     );
 
+    create_timer_from_preset_with_id(&app, &preset_id)?;
+    log_command(
+        "create_timer_from_preset",
+        "Timer created successfully from preset",
+    );
+    Ok(())
+}
+
+/// Cria um timer a partir de um preset e devolve o id do novo timer.
+pub fn create_timer_from_preset_with_id(app: &AppHandle, preset_id: &str) -> Result<String, String> {
     let config_str = load_config(app.clone())?;
     let mut config: Value =
         serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
@@ -369,7 +505,7 @@ pub fn create_timer_from_preset(app: AppHandle, preset_id: String) -> Result<(),
 
     let preset = presets
         .iter()
-        .find(|p| p.get("id").and_then(|id| id.as_str()) == Some(preset_id.as_str()))
+        .find(|p| p.get("id").and_then(|id| id.as_str()) == Some(preset_id))
         .ok_or_else(|| "Preset not found".to_string())?;
 
     let duration = preset
@@ -402,7 +538,7 @@ pub fn create_timer_from_preset(app: AppHandle, preset_id: String) -> Result<(),
     let new_icon_id = format!("icon-{}", timestamp);
 
     let mut new_icon = json!({
-        "id": new_icon_id,
+        "id": new_icon_id.clone(),
         "name": name,
         "iconName": icon_name,
         "timerDuration": duration,
@@ -427,13 +563,393 @@ pub fn create_timer_from_preset(app: AppHandle, preset_id: String) -> Result<(),
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
     save_config(app.clone(), updated_config, Some(true))?;
+    Ok(new_icon_id)
+}
+
+/// Id especial usado para registrar o atalho de um grupo (`__timer_group__:<group_id>`).
+#[cfg(windows)]
+pub const TIMER_GROUP_SHORTCUT_PREFIX: &str = "__timer_group__:";
+
+/// Id especial usado para registrar o atalho de volta de um cronômetro (`__timer_lap__:<timer_id>`).
+#[cfg(windows)]
+pub const TIMER_LAP_SHORTCUT_PREFIX: &str = "__timer_lap__:";
+
+/// Id especial dos atalhos de ajuste (`__timer_adjust__:<delta>:<timer_id>`; `*` = timer ativo mais recente).
+#[cfg(windows)]
+pub const TIMER_ADJUST_SHORTCUT_PREFIX: &str = "__timer_adjust__:";
+
+/// Executa uma ação (`start`, `pause` ou `reset`) em todos os timers de um grupo.
+pub fn run_timer_group_action(
+    app: &AppHandle,
+    group_id: &str,
+    action: &str,
+    source: TriggerSource,
+) -> Result<(), String> {
+    let event = match action {
+        "start" => "start-specific-timer",
+        "pause" => "pause-only-timer",
+        "reset" => "reset-specific-timer",
+        _ => return Err(format!("Unknown group action: {}", action)),
+    };
+
+    let config_str = load_config(app.clone())?;
+    let config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    let group = config
+        .get("timerGroups")
+        .and_then(|g| g.as_array())
+        .and_then(|groups| {
+            groups
+                .iter()
+                .find(|g| g.get("id").and_then(|id| id.as_str()) == Some(group_id))
+        })
+        .ok_or_else(|| format!("Timer group not found: {}", group_id))?;
+
+    let timer_ids: Vec<&str> = group
+        .get("timerIds")
+        .and_then(|ids| ids.as_array())
+        .map(|ids| ids.iter().filter_map(|id| id.as_str()).collect())
+        .unwrap_or_default();
+
+    let overlay_window = app
+        .get_webview_window("overlay")
+        .ok_or_else(|| "Overlay window not found".to_string())?;
+
+    for timer_id in &timer_ids {
+        history::note_trigger(timer_id, source);
+        overlay_window
+            .emit(event, timer_id)
+            .map_err(|e| format!("Failed to emit {} event: {}", event, e))?;
+    }
+
+    println!(
+        "[GROUP] {} applied to {} timers in group {}",
+        action,
+        timer_ids.len(),
+        group_id
+    );
+    Ok(())
+}
+
+/// Executa a ação configurada em `keybindAction` do grupo (padrão: `start`).
+#[cfg(windows)]
+pub fn trigger_timer_group_keybind(app: &AppHandle, group_id: &str) -> Result<(), String> {
+    let config_str = load_config(app.clone())?;
+    let config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    let action = config
+        .get("timerGroups")
+        .and_then(|g| g.as_array())
+        .and_then(|groups| {
+            groups
+                .iter()
+                .find(|g| g.get("id").and_then(|id| id.as_str()) == Some(group_id))
+        })
+        .and_then(|g| g.get("keybindAction"))
+        .and_then(|a| a.as_str())
+        .unwrap_or("start")
+        .to_string();
+
+    run_timer_group_action(app, group_id, &action, TriggerSource::Keybind)
+}
+
+#[tauri::command]
+pub fn start_timer_group(app: AppHandle, group_id: String) -> Result<(), String> {
     log_command(
-        "create_timer_from_preset",
-        "Timer created successfully from preset",
+        "start_timer_group",
+        &format!("Starting timers in group: {}", group_id),
+    );
+    run_timer_group_action(&app, &group_id, "start", TriggerSource::Api)
+}
+
+#[tauri::command]
+pub fn pause_timer_group(app: AppHandle, group_id: String) -> Result<(), String> {
+    log_command(
+        "pause_timer_group",
+        &format!("Pausing timers in group: {}", group_id),
+    );
+    run_timer_group_action(&app, &group_id, "pause", TriggerSource::Api)
+}
+
+#[tauri::command]
+pub fn reset_timer_group(app: AppHandle, group_id: String) -> Result<(), String> {
+    log_command(
+        "reset_timer_group",
+        &format!("Resetting timers in group: {}", group_id),
     );
+    run_timer_group_action(&app, &group_id, "reset", TriggerSource::Api)
+}
+
+/// Recebe eventos do ciclo de vida dos timers do overlay (início, fase, fim, cancelamento).
+#[tauri::command]
+pub fn report_timer_event(app: AppHandle, event: TimerEvent) -> Result<(), String> {
+    engine::handle_timer_event(&app, event);
     Ok(())
 }
 
+#[tauri::command]
+pub fn snooze_alarm(app: AppHandle, timer_id: String, minutes: Option<u64>) -> Result<(), String> {
+    log_command("snooze_alarm", &format!("Snoozing alarm: {}", timer_id));
+    alarms::snooze_alarm(&app, &timer_id, minutes)
+}
+
+#[tauri::command]
+pub fn dismiss_alarm(app: AppHandle, timer_id: String) -> Result<(), String> {
+    log_command("dismiss_alarm", &format!("Dismissing alarm: {}", timer_id));
+    alarms::dismiss_alarm(&app, &timer_id)
+}
+
+#[tauri::command]
+pub fn get_upcoming_alarms(app: AppHandle) -> Result<Vec<UpcomingAlarm>, String> {
+    Ok(alarms::get_upcoming_alarms(&app))
+}
+
+/// Estado atual de todos os timers (restante, decorrido, status), incluindo contagens até reset.
+#[tauri::command]
+pub fn get_timer_states(app: AppHandle) -> Result<Vec<TimerStateSnapshot>, String> {
+    engine::timer_states(&app)
+}
+
+#[tauri::command]
+pub fn lap_timer(app: AppHandle, timer_id: String) -> Result<Lap, String> {
+    log_command("lap_timer", &format!("Recording lap on: {}", timer_id));
+    engine::record_lap(&app, &timer_id)
+}
+
+#[tauri::command]
+pub fn get_laps(timer_id: String) -> Result<Vec<Lap>, String> {
+    Ok(engine::laps(&timer_id))
+}
+
+/// Adiciona ou remove tempo de um timer rodando, pausado ou em intervalo.
+#[tauri::command]
+pub fn adjust_timer(app: AppHandle, timer_id: String, delta_seconds: i64) -> Result<(), String> {
+    log_command(
+        "adjust_timer",
+        &format!("Adjusting timer {} by {}s", timer_id, delta_seconds),
+    );
+    engine::adjust_timer(&app, &timer_id, delta_seconds)
+}
+
+/// Ajusta o timer ativo iniciado mais recentemente (atalhos globais de ajuste).
+#[tauri::command]
+pub fn adjust_active_timer(app: AppHandle, delta_seconds: i64) -> Result<(), String> {
+    let timer_id = engine::latest_active_timer().ok_or_else(|| "No active timer to adjust".to_string())?;
+    adjust_timer(app, timer_id, delta_seconds)
+}
+
+/// Reconhece um timer em overtime, parando a contagem negativa.
+#[tauri::command]
+pub fn acknowledge_timer(app: AppHandle, timer_id: String) -> Result<(), String> {
+    log_command("acknowledge_timer", &format!("Acknowledging timer: {}", timer_id));
+    history::note_trigger(&timer_id, TriggerSource::Api);
+    engine::acknowledge_timer(&app, &timer_id)
+}
+
+/// Gasta uma carga de um timer de cargas e enfileira a recarga.
+#[tauri::command]
+pub fn use_charge(app: AppHandle, timer_id: String) -> Result<ChargeSnapshot, String> {
+    log_command("use_charge", &format!("Using a charge of timer: {}", timer_id));
+    charges::use_charge(&app, &timer_id)
+}
+
+/// Devolve todas as cargas de um timer de cargas.
+#[tauri::command]
+pub fn reset_charges(app: AppHandle, timer_id: String) -> Result<ChargeSnapshot, String> {
+    log_command("reset_charges", &format!("Refilling charges of timer: {}", timer_id));
+    charges::reset_charges(&app, &timer_id)
+}
+
+/// Inicia, pausa ou retoma a fase atual de um timer Pomodoro.
+#[tauri::command]
+pub fn toggle_pomodoro(app: AppHandle, timer_id: String) -> Result<PomodoroSnapshot, String> {
+    log_command("toggle_pomodoro", &format!("Toggling Pomodoro timer: {}", timer_id));
+    pomodoro::toggle_pomodoro(&app, &timer_id)
+}
+
+/// Pula para a próxima fase do ciclo Pomodoro.
+#[tauri::command]
+pub fn skip_pomodoro_phase(app: AppHandle, timer_id: String) -> Result<PomodoroSnapshot, String> {
+    log_command("skip_pomodoro_phase", &format!("Skipping phase of Pomodoro timer: {}", timer_id));
+    pomodoro::skip_phase(&app, &timer_id)
+}
+
+/// Volta ao primeiro foco do conjunto.
+#[tauri::command]
+pub fn restart_pomodoro_cycle(app: AppHandle, timer_id: String) -> Result<PomodoroSnapshot, String> {
+    log_command("restart_pomodoro_cycle", &format!("Restarting cycle of Pomodoro timer: {}", timer_id));
+    pomodoro::restart_cycle(&app, &timer_id)
+}
+
+/// Zera o contador de ciclos concluídos hoje.
+#[tauri::command]
+pub fn reset_pomodoro_day(app: AppHandle, timer_id: String) -> Result<PomodoroSnapshot, String> {
+    log_command("reset_pomodoro_day", &format!("Resetting day counter of Pomodoro timer: {}", timer_id));
+    pomodoro::reset_day(&app, &timer_id)
+}
+
+/// Passa a vez no relógio de xadrez (ou começa a partida / retoma se estiver pausada).
+#[tauri::command]
+pub fn pass_turn(app: AppHandle, timer_id: String) -> Result<ChessClockSnapshot, String> {
+    log_command("pass_turn", &format!("Passing turn on chess clock: {}", timer_id));
+    chessclock::pass_turn(&app, &timer_id)
+}
+
+/// Pausa ou retoma o relógio de xadrez sem passar a vez.
+#[tauri::command]
+pub fn toggle_chess_clock(app: AppHandle, timer_id: String) -> Result<ChessClockSnapshot, String> {
+    log_command("toggle_chess_clock", &format!("Toggling chess clock: {}", timer_id));
+    chessclock::toggle_chess_clock(&app, &timer_id)
+}
+
+/// Devolve o banco de tempo inteiro a todos os jogadores.
+#[tauri::command]
+pub fn reset_chess_clock(app: AppHandle, timer_id: String) -> Result<ChessClockSnapshot, String> {
+    log_command("reset_chess_clock", &format!("Resetting chess clock: {}", timer_id));
+    chessclock::reset_chess_clock(&app, &timer_id)
+}
+
+/// Sorteia a duração da próxima execução de um timer com duração aleatória.
+#[tauri::command]
+pub fn roll_random_duration(app: AppHandle, timer_id: String) -> Result<RandomRoll, String> {
+    random::roll_duration(&app, &timer_id)
+}
+
+/// Recomeça a sequência aleatória de um timer a partir de uma semente, para repetir uma sessão.
+#[tauri::command]
+pub fn reseed_random_timer(app: AppHandle, timer_id: String, seed: Option<u64>) -> Result<u64, String> {
+    log_command("reseed_random_timer", &format!("Reseeding timer {} with {:?}", timer_id, seed));
+    random::reseed(&app, &timer_id, seed)
+}
+
+/// Estado dos gatilhos de log: se o arquivo está sendo lido, disparos e erros.
+#[tauri::command]
+pub fn get_log_watch_status() -> Result<Vec<LogWatchStatus>, String> {
+    Ok(logwatch::log_watch_status())
+}
+
+/// Estado das regras de processo: se o programa está aberto e o último disparo.
+#[tauri::command]
+pub fn get_process_rule_status() -> Result<Vec<ProcessRuleStatus>, String> {
+    Ok(processes::process_rule_status())
+}
+
+/// Execuções concluídas registradas no histórico persistente, da mais recente para a mais antiga.
+#[tauri::command]
+pub fn get_run_history(app: AppHandle, limit: Option<usize>) -> Result<Vec<RunRecord>, String> {
+    Ok(history::recent_runs(&app, limit))
+}
+
+/// Eventos do histórico persistente (início, fim, reset...), do mais recente para o mais antigo.
+#[tauri::command]
+pub fn query_history(app: AppHandle, query: Option<HistoryQuery>) -> Result<Vec<HistoryEntry>, String> {
+    Ok(history::query(&app, &query.unwrap_or_default()))
+}
+
+/// Estatísticas de uso (execuções, conclusão, tempo total e médio, sequência, horários) por timer, dia ou semana.
+#[tauri::command]
+pub fn get_stats(
+    app: AppHandle,
+    range: Option<StatsRange>,
+    group_by: Option<StatsGroupBy>,
+) -> Result<StatsReport, String> {
+    Ok(stats::get_stats(&app, range.unwrap_or_default(), group_by.unwrap_or_default()))
+}
+
+/// Resumo semanal em Markdown; `week_offset` 0 = semana atual, -1 = anterior.
+#[tauri::command]
+pub fn get_weekly_summary(app: AppHandle, week_offset: Option<i32>) -> Result<String, String> {
+    Ok(stats::weekly_summary(&app, week_offset.unwrap_or(0)))
+}
+
+/// Define rótulo e anotação de uma execução. Sem `run_started_at`, vale para a execução ativa
+/// do timer ou, se ele estiver parado, para a próxima. Retorna o início da execução rotulada.
+#[tauri::command]
+pub fn label_run(
+    app: AppHandle,
+    timer_id: String,
+    run_started_at: Option<i64>,
+    label: Option<String>,
+    note: Option<String>,
+) -> Result<Option<i64>, String> {
+    log_command(
+        "label_run",
+        &format!("{} run {:?} -> {:?}", timer_id, run_started_at, label),
+    );
+    let label = RunLabel::new(label, note);
+    let active = engine::label_active_run(&timer_id, &label, run_started_at);
+    match run_started_at.or(active) {
+        Some(started_at) => {
+            history::label_run(&app, &timer_id, started_at, &label)?;
+            Ok(Some(started_at))
+        }
+        None => {
+            history::note_label(&timer_id, label);
+            Ok(None)
+        }
+    }
+}
+
+/// Rótulos usados recentemente, para sugerir ao rotular uma execução.
+#[tauri::command]
+pub fn get_recent_labels(app: AppHandle, limit: Option<usize>) -> Result<Vec<String>, String> {
+    Ok(history::recent_labels(&app, limit.unwrap_or(20)))
+}
+
+/// Progresso do período atual e sequência de cada meta e limite configurado em `goals`.
+#[tauri::command]
+pub fn get_goal_progress(app: AppHandle) -> Result<Vec<GoalProgress>, String> {
+    Ok(goals::goal_progress(&app))
+}
+
+/// Resultado da última execução de cada hook de evento (saída, código, erro).
+#[tauri::command]
+pub fn get_event_hook_status() -> Result<Vec<EventHookStatus>, String> {
+    Ok(hooks::hook_status())
+}
+
+/// Roda um hook uma vez com um evento de exemplo; o resultado aparece em `get_event_hook_status`.
+#[tauri::command]
+pub fn test_event_hook(app: AppHandle, hook: Value) -> Result<(), String> {
+    log_command("test_event_hook", &hook.to_string());
+    let config_str = load_config(app)?;
+    let config: Value = serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+    hooks::test_hook(&config, &hook)
+}
+
+/// Última entrega de cada webhook (código HTTP, tentativas, erro) e contagem de entregas.
+#[tauri::command]
+pub fn get_webhook_status() -> Result<Vec<WebhookStatus>, String> {
+    Ok(webhooks::webhook_status())
+}
+
+/// Envia um evento `test` para o webhook informado; o resultado aparece em `get_webhook_status`.
+#[tauri::command]
+pub fn test_webhook(webhook: Value) -> Result<(), String> {
+    log_command("test_webhook", &webhook.get("url").map(|u| u.to_string()).unwrap_or_default());
+    webhooks::test_webhook(&webhook)
+}
+
+/// Exporta as sessões do histórico (CSV, JSON Lines, iCalendar ou Timewarrior) para o arquivo escolhido.
+#[tauri::command]
+pub fn export_history(
+    app: AppHandle,
+    path: String,
+    format: ExportFormat,
+    filter: Option<ExportFilter>,
+) -> Result<usize, String> {
+    log_command("export_history", &format!("{:?} -> {}", format, path));
+    export::export_history(&app, &path, format, &filter.unwrap_or_default())
+}
+
+/// Próximos disparos de uma expressão cron (ms desde a epoch), usado para validar e pré-visualizar.
+#[tauri::command]
+pub fn preview_schedule(cron: String, time_zone: Option<String>, count: Option<usize>) -> Result<Vec<i64>, String> {
+    schedules::preview_schedule(&cron, time_zone.as_deref(), count.unwrap_or(3).min(20))
+}
+
 #[cfg(windows)]
 use crate::keyboard_hook;
 
@@ -464,6 +980,34 @@ pub fn register_low_level_shortcut(
             } else {
                 println!("[KEYBOARD_HOOK] WARNING: Overlay window not found when trying to emit reset-all-timers");
             }
+        } else if let Some(group_id) = icon_id_clone.strip_prefix(TIMER_GROUP_SHORTCUT_PREFIX) {
+            // Carregar o config fora do hook para não travar o teclado
+            let app_group = app_clone.clone();
+            let group_id = group_id.to_string();
+            std::thread::spawn(move || {
+                if let Err(e) = trigger_timer_group_keybind(&app_group, &group_id) {
+                    println!("[KEYBOARD_HOOK] ERROR: Failed to trigger group {}: {}", group_id, e);
+                }
+            });
+        } else if let Some(spec) = icon_id_clone.strip_prefix(TIMER_ADJUST_SHORTCUT_PREFIX) {
+            let result = match spec.split_once(':') {
+                Some((delta, "*")) => delta
+                    .parse::<i64>()
+                    .map_err(|e| e.to_string())
+                    .and_then(|delta| adjust_active_timer(app_clone.clone(), delta)),
+                Some((delta, timer_id)) => delta
+                    .parse::<i64>()
+                    .map_err(|e| e.to_string())
+                    .and_then(|delta| adjust_timer(app_clone.clone(), timer_id.to_string(), delta)),
+                None => Err(format!("Invalid adjust shortcut id: {}", spec)),
+            };
+            if let Err(e) = result {
+                println!("[KEYBOARD_HOOK] WARNING: Could not adjust timer: {}", e);
+            }
+        } else if let Some(timer_id) = icon_id_clone.strip_prefix(TIMER_LAP_SHORTCUT_PREFIX) {
+            if let Err(e) = engine::record_lap(&app_clone, timer_id) {
+                println!("[KEYBOARD_HOOK] WARNING: Could not record lap on {}: {}", timer_id, e);
+            }
         } else {
             let _ = app_clone.emit("shortcut-triggered", icon_id_clone.clone());
         }
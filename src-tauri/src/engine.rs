@@ -0,0 +1,774 @@
+use crate::chains::{self, ChainRule, ChainTarget, ChainTrigger};
+use crate::charges::{self, ChargeSnapshot};
+use crate::chessclock::{self, ChessClockSnapshot};
+use crate::commands::{create_timer_from_preset_with_id, load_config};
+use crate::history::{self, HistoryEntry, HistoryEventKind, RunLabel, TriggerSource};
+use crate::hooks;
+use crate::pomodoro::{self, PomodoroSnapshot};
+use crate::quick;
+use crate::resets;
+use crate::webhooks;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Evento do ciclo de vida de um timer, reportado pelo overlay.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerEvent {
+    pub timer_id: String,
+    pub event: TimerEventKind,
+    /// Fase (ciclo de repetição, começando em 1) para eventos `phase`.
+    #[serde(default)]
+    pub phase: Option<u32>,
+    /// Segundos restantes (ou decorridos, para cronômetros) no momento do evento.
+    #[serde(default)]
+    pub remaining: Option<u64>,
+    /// Instância paralela do timer (política `stack`); None ou 0 = instância principal.
+    #[serde(default)]
+    pub instance: Option<u32>,
+    /// Semente e sorteio da duração aleatória usada em `started`.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub draw: Option<u64>,
+    /// Dados extras de timers controlados no Rust (fase do Pomodoro, jogador do relógio),
+    /// repassados a hooks e webhooks.
+    #[serde(skip)]
+    pub detail: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerEventKind {
+    Started,
+    Phase,
+    Finished,
+    Cancelled,
+    Paused,
+    Resumed,
+    /// Overtime reconhecido (atalho, reset ou `acknowledge_timer`)
+    Acknowledged,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Idle,
+    Running,
+    Paused,
+    /// Contagem regressiva terminou e continua contando até ser reconhecida
+    Overtime,
+}
+
+#[derive(Debug, Clone)]
+struct TimerRun {
+    status: RunStatus,
+    counts_up: bool,
+    /// Restante (contagem regressiva) ou decorrido (cronômetro) em `since`
+    seconds: f64,
+    since: Instant,
+    phase: u32,
+    started_at: DateTime<Utc>,
+    /// Duração de cada fase iniciada (contagens regressivas)
+    durations: Vec<u64>,
+    random: Option<(u64, u64)>,
+    label: RunLabel,
+}
+
+impl TimerRun {
+    fn current_seconds(&self) -> f64 {
+        if !matches!(self.status, RunStatus::Running | RunStatus::Overtime) {
+            return self.seconds;
+        }
+        let elapsed = self.since.elapsed().as_secs_f64();
+        if self.counts_up {
+            self.seconds + elapsed
+        } else {
+            (self.seconds - elapsed).max(0.0)
+        }
+    }
+
+    /// Tempo efetivamente contado na execução: fases da contagem regressiva (sem intervalos)
+    /// mais o overtime, ou o decorrido do cronômetro.
+    fn counted_seconds(&self) -> f64 {
+        let planned: u64 = self.durations.iter().sum();
+        match (self.status, self.counts_up) {
+            (RunStatus::Overtime, _) => planned as f64 + self.current_seconds(),
+            (_, true) => self.current_seconds(),
+            (_, false) => (planned as f64 - self.current_seconds()).max(0.0),
+        }
+    }
+}
+
+/// Estado atual de um timer, igual para todos os tipos.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerStateSnapshot {
+    pub timer_id: String,
+    pub name: Option<String>,
+    pub timer_type: String,
+    pub status: RunStatus,
+    pub remaining_seconds: Option<u64>,
+    pub elapsed_seconds: Option<u64>,
+    pub duration_seconds: Option<u64>,
+    pub phase: Option<u32>,
+    /// Segundos desde que a contagem chegou a zero, em modo overtime
+    pub overtime_seconds: Option<u64>,
+    /// Próximo reset em milissegundos desde a epoch (UTC), para contagens até reset
+    pub next_reset_at: Option<i64>,
+    /// Instâncias paralelas em andamento (política `stack`), além da principal
+    pub stacked_instances: Vec<TimerInstanceState>,
+    /// Cargas disponíveis e progresso da recarga, para timers de cargas
+    pub charges: Option<ChargeSnapshot>,
+    /// Fase, tempo restante e ciclos do dia, para timers Pomodoro
+    pub pomodoro: Option<PomodoroSnapshot>,
+    /// Banco de tempo de cada jogador e jogador da vez, para relógios de xadrez
+    pub chess_clock: Option<ChessClockSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerInstanceState {
+    pub instance: u32,
+    pub status: RunStatus,
+    pub remaining_seconds: Option<u64>,
+    pub elapsed_seconds: Option<u64>,
+}
+
+/// Timer + instância (0 = principal)
+type RunKey = (String, u32);
+
+fn primary_key(timer_id: &str) -> RunKey {
+    (timer_id.to_string(), 0)
+}
+
+/// Volta registrada em um cronômetro.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lap {
+    /// Número da volta, começando em 1
+    pub index: u32,
+    /// Tempo total do cronômetro no momento da volta
+    pub split_ms: u64,
+    /// Tempo desde a volta anterior
+    pub delta_ms: u64,
+    /// Momento da volta em milissegundos desde a epoch (UTC)
+    pub recorded_at: i64,
+}
+
+#[derive(Default)]
+struct EngineState {
+    /// Timers iniciados por uma cadeia e ainda não confirmados pelo overlay -> origem da cadeia
+    pending_chain_starts: HashMap<String, String>,
+    /// Timer -> timer que iniciou a cadeia atual
+    chain_origins: HashMap<String, String>,
+    /// (origem, regra) -> vezes que a regra já disparou nesta cadeia
+    chain_fire_counts: HashMap<(String, String), u32>,
+    /// Timers ativos (rodando, pausados ou em overtime), por instância
+    runs: HashMap<RunKey, TimerRun>,
+    /// Voltas do cronômetro na execução atual (mantidas só durante a sessão)
+    laps: HashMap<String, Vec<Lap>>,
+}
+
+static ENGINE: LazyLock<Mutex<EngineState>> = LazyLock::new(|| Mutex::new(EngineState::default()));
+
+pub fn handle_timer_event(app: &AppHandle, event: TimerEvent) {
+    println!(
+        "[ENGINE] Timer {} reported {:?} (phase {:?})",
+        event.timer_id, event.event, event.phase
+    );
+
+    let config = match load_config_value(app) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("[ENGINE] WARNING: Could not load config to evaluate chains: {}", e);
+            return;
+        }
+    };
+
+    let entry = track_run(&config, &event).map(|mut entry| {
+        // Ações pedidas pelo usuário sem origem anotada vieram de um atalho do overlay
+        if !matches!(entry.event, HistoryEventKind::Repeated | HistoryEventKind::Finished) {
+            entry.source = history::take_trigger(&event.timer_id, entry.event).or(Some(TriggerSource::Keybind));
+        }
+        history::append(app, &entry);
+        entry
+    });
+    quick::on_timer_event(app, &event);
+    dispatch_event(&config, &event, entry.as_ref());
+
+    let (trigger, phase) = match event.event {
+        TimerEventKind::Started => {
+            track_start(&event.timer_id);
+            // A primeira execução conta como fase 1
+            (ChainTrigger::Phase, Some(1))
+        }
+        TimerEventKind::Phase => (ChainTrigger::Phase, event.phase),
+        TimerEventKind::Finished => (ChainTrigger::Finished, None),
+        TimerEventKind::Cancelled => (ChainTrigger::Cancelled, None),
+        TimerEventKind::Paused | TimerEventKind::Resumed | TimerEventKind::Acknowledged => return,
+    };
+
+    for rule in chains::rules_for_timer(&config, &event.timer_id) {
+        if rule.trigger != trigger {
+            continue;
+        }
+        if trigger == ChainTrigger::Phase && rule.phase != phase {
+            continue;
+        }
+        if let Some(origin) = claim_chain_run(&event.timer_id, &rule) {
+            schedule_chain_target(app.clone(), origin, rule);
+        }
+    }
+}
+
+/// Repassa um evento às integrações externas. Timers do overlay chegam por
+/// `handle_timer_event`; Pomodoro e relógio de xadrez, que rodam no Rust, chamam direto.
+pub fn dispatch_event(config: &Value, event: &TimerEvent, entry: Option<&HistoryEntry>) {
+    hooks::on_timer_event(config, event, entry);
+    webhooks::on_timer_event(config, event, entry);
+}
+
+fn find_icon<'a>(config: &'a Value, timer_id: &str) -> Option<&'a Value> {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())?
+        .iter()
+        .find(|icon| icon.get("id").and_then(|id| id.as_str()) == Some(timer_id))
+}
+
+/// Atualiza o estado de execução usado por `timer_states`.
+/// Retorna a entrada do histórico persistente correspondente ao evento (sem a origem).
+fn track_run(config: &Value, event: &TimerEvent) -> Option<HistoryEntry> {
+    let icon = find_icon(config, &event.timer_id);
+    let key: RunKey = (event.timer_id.clone(), event.instance.unwrap_or(0));
+    let counts_up = icon.and_then(|i| i.get("timerType")).and_then(|t| t.as_str()) == Some("stopwatch");
+    // Instâncias paralelas terminam direto, sem overtime
+    let overtime_enabled = !counts_up
+        && key.1 == 0
+        && icon.and_then(|i| i.get("overtime")).and_then(|o| o.as_bool()) == Some(true);
+    let duration = icon
+        .and_then(|i| i.get("timerDuration"))
+        .and_then(|d| d.as_u64())
+        .unwrap_or(0);
+    let initial = if counts_up { 0 } else { duration };
+    let name = icon
+        .and_then(|i| i.get("name"))
+        .and_then(|n| n.as_str())
+        .map(|n| n.to_string());
+    let now = Utc::now();
+
+    let mut engine = ENGINE.lock().unwrap();
+    if event.event == TimerEventKind::Started && key.1 == 0 {
+        engine.laps.remove(&event.timer_id);
+    }
+    let runs = &mut engine.runs;
+
+    let run = match event.event {
+        TimerEventKind::Started | TimerEventKind::Phase => {
+            let (started_at, mut durations, random, label) = match (event.event, runs.get(&key)) {
+                (TimerEventKind::Phase, Some(run)) => {
+                    (run.started_at, run.durations.clone(), run.random, run.label.clone())
+                }
+                _ => (
+                    now,
+                    Vec::new(),
+                    event.seed.zip(event.draw),
+                    history::take_label(&event.timer_id).unwrap_or_default(),
+                ),
+            };
+            let seconds = event.remaining.unwrap_or(initial);
+            if !counts_up {
+                durations.push(seconds);
+            }
+            let run = TimerRun {
+                status: RunStatus::Running,
+                counts_up,
+                seconds: seconds as f64,
+                since: Instant::now(),
+                phase: event.phase.unwrap_or(1),
+                started_at,
+                durations,
+                random,
+                label,
+            };
+            runs.insert(key.clone(), run.clone());
+            Some(run)
+        }
+        TimerEventKind::Paused | TimerEventKind::Resumed => {
+            if let Some(run) = runs.get_mut(&key) {
+                let seconds = event
+                    .remaining
+                    .map(|r| r as f64)
+                    .unwrap_or_else(|| run.current_seconds());
+                run.seconds = seconds;
+                run.since = Instant::now();
+                run.status = if event.event == TimerEventKind::Paused {
+                    RunStatus::Paused
+                } else {
+                    RunStatus::Running
+                };
+            }
+            runs.get(&key).cloned()
+        }
+        TimerEventKind::Finished if overtime_enabled => {
+            if let Some(run) = runs.get_mut(&key) {
+                run.status = RunStatus::Overtime;
+                run.counts_up = true;
+                run.seconds = 0.0;
+                run.since = Instant::now();
+            }
+            runs.get(&key).cloned()
+        }
+        TimerEventKind::Finished | TimerEventKind::Cancelled | TimerEventKind::Acknowledged => runs.remove(&key),
+    }?;
+
+    let kind = match event.event {
+        TimerEventKind::Started => HistoryEventKind::Started,
+        TimerEventKind::Phase => HistoryEventKind::Repeated,
+        TimerEventKind::Paused => HistoryEventKind::Paused,
+        TimerEventKind::Resumed => HistoryEventKind::Resumed,
+        TimerEventKind::Finished => HistoryEventKind::Finished,
+        TimerEventKind::Cancelled => HistoryEventKind::Cancelled,
+        TimerEventKind::Acknowledged => HistoryEventKind::Acknowledged,
+    };
+    Some(HistoryEntry {
+        timer_id: event.timer_id.clone(),
+        name,
+        event: kind,
+        at: now.timestamp_millis(),
+        run_started_at: Some(run.started_at.timestamp_millis()),
+        planned_seconds: run.durations.last().copied(),
+        actual_seconds: (kind != HistoryEventKind::Started).then(|| run.counted_seconds().round() as u64),
+        repeat_count: run.phase.saturating_sub(1),
+        source: None,
+        instance: (key.1 > 0).then_some(key.1),
+        label: run.label.label,
+        note: run.label.note,
+        random_seed: run.random.map(|(seed, _)| seed),
+        random_draw: run.random.map(|(_, draw)| draw),
+    })
+}
+
+/// Troca o rótulo da execução principal ativa de um timer. Retorna o início da execução
+/// (ms desde a epoch), ou None se o timer não estiver ativo.
+pub fn label_active_run(timer_id: &str, label: &RunLabel, started_at: Option<i64>) -> Option<i64> {
+    let mut engine = ENGINE.lock().unwrap();
+    let run = engine.runs.get_mut(&primary_key(timer_id))?;
+    let run_started_at = run.started_at.timestamp_millis();
+    if started_at.is_some_and(|at| at != run_started_at) {
+        return None;
+    }
+    run.label = label.clone();
+    Some(run_started_at)
+}
+
+/// Pede ao overlay para reconhecer um timer em overtime.
+pub fn acknowledge_timer(app: &AppHandle, timer_id: &str) -> Result<(), String> {
+    let in_overtime = ENGINE
+        .lock()
+        .unwrap()
+        .runs
+        .get(&primary_key(timer_id))
+        .is_some_and(|run| run.status == RunStatus::Overtime);
+    if !in_overtime {
+        return Err(format!("Timer {} is not in overtime", timer_id));
+    }
+
+    let overlay_window = app
+        .get_webview_window("overlay")
+        .ok_or_else(|| "Overlay window not found".to_string())?;
+    overlay_window
+        .emit("acknowledge-timer", timer_id)
+        .map_err(|e| format!("Failed to emit acknowledge event: {}", e))
+}
+
+/// Soma `delta_seconds` ao tempo restante (ou decorrido, em cronômetros) de um timer ativo,
+/// sem deixar passar de zero, e repassa o ajuste para o overlay.
+pub fn adjust_timer(app: &AppHandle, timer_id: &str, delta_seconds: i64) -> Result<(), String> {
+    {
+        let mut engine = ENGINE.lock().unwrap();
+        let run = engine
+            .runs
+            .get_mut(&primary_key(timer_id))
+            .ok_or_else(|| format!("Timer {} is not running", timer_id))?;
+        if run.status == RunStatus::Overtime {
+            return Err(format!("Timer {} already finished", timer_id));
+        }
+        // Contagem regressiva zerada ainda aberta está no intervalo entre repetições: o overlay
+        // ajusta só o intervalo e a próxima fase chega com a própria duração
+        let in_interval = !run.counts_up && run.current_seconds() <= 0.0;
+        if !in_interval {
+            run.seconds = (run.current_seconds() + delta_seconds as f64).max(0.0);
+            run.since = Instant::now();
+        }
+    }
+
+    println!("[ENGINE] Adjusting timer {} by {}s", timer_id, delta_seconds);
+    let overlay_window = app
+        .get_webview_window("overlay")
+        .ok_or_else(|| "Overlay window not found".to_string())?;
+    overlay_window
+        .emit(
+            "adjust-timer",
+            // Só a instância principal foi ajustada no engine
+            json!({ "timerId": timer_id, "instance": 0, "deltaSeconds": delta_seconds }),
+        )
+        .map_err(|e| format!("Failed to emit adjust event: {}", e))
+}
+
+/// Estado da instância principal de um timer (Idle se não estiver rodando).
+pub fn run_status(timer_id: &str) -> RunStatus {
+    if let Some(status) = pomodoro::run_status(timer_id).or_else(|| chessclock::run_status(timer_id)) {
+        return status;
+    }
+    ENGINE
+        .lock()
+        .unwrap()
+        .runs
+        .get(&primary_key(timer_id))
+        .map(|run| run.status)
+        .unwrap_or(RunStatus::Idle)
+}
+
+/// Execuções contando agora (rodando ou em overtime), por timer e instância.
+pub fn active_runs() -> Vec<(String, u32)> {
+    ENGINE
+        .lock()
+        .unwrap()
+        .runs
+        .iter()
+        .filter(|(_, run)| matches!(run.status, RunStatus::Running | RunStatus::Overtime))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Execuções abertas (rodando, pausadas ou em overtime), por timer e instância.
+pub fn open_runs() -> Vec<(String, u32)> {
+    ENGINE.lock().unwrap().runs.keys().cloned().collect()
+}
+
+/// Execuções ainda abertas com o que já contaram: (timer, início em ms, rótulo, segundos).
+pub fn open_run_totals() -> Vec<(String, i64, Option<String>, f64)> {
+    ENGINE
+        .lock()
+        .unwrap()
+        .runs
+        .iter()
+        .map(|((timer_id, _), run)| {
+            (
+                timer_id.clone(),
+                run.started_at.timestamp_millis(),
+                run.label.label.clone(),
+                run.counted_seconds(),
+            )
+        })
+        .collect()
+}
+
+/// Contagens regressivas rodando: (timer, instância, início em ms, fase, segundos restantes).
+pub fn running_countdowns() -> Vec<(String, u32, i64, u32, f64)> {
+    ENGINE
+        .lock()
+        .unwrap()
+        .runs
+        .iter()
+        .filter(|(_, run)| run.status == RunStatus::Running && !run.counts_up)
+        .map(|((timer_id, instance), run)| {
+            (
+                timer_id.clone(),
+                *instance,
+                run.started_at.timestamp_millis(),
+                run.phase,
+                run.current_seconds(),
+            )
+        })
+        .collect()
+}
+
+/// Soma `seconds` ao tempo já contado de uma execução ativa (negativo desconta),
+/// usado para corrigir suspensões que o relógio monotônico contou ou deixou de contar.
+pub fn shift_run(timer_id: &str, instance: u32, seconds: f64) {
+    let mut engine = ENGINE.lock().unwrap();
+    if let Some(run) = engine.runs.get_mut(&(timer_id.to_string(), instance)) {
+        let current = run.current_seconds();
+        run.seconds = if run.counts_up {
+            (current + seconds).max(0.0)
+        } else {
+            (current - seconds).max(0.0)
+        };
+        run.since = Instant::now();
+    }
+}
+
+/// Timer rodando ou pausado iniciado mais recentemente (alvo dos atalhos globais de ajuste).
+pub fn latest_active_timer() -> Option<String> {
+    ENGINE
+        .lock()
+        .unwrap()
+        .runs
+        .iter()
+        .filter(|((_, instance), run)| {
+            *instance == 0 && matches!(run.status, RunStatus::Running | RunStatus::Paused)
+        })
+        .max_by_key(|(_, run)| run.started_at)
+        .map(|((id, _), _)| id.clone())
+}
+
+/// Registra uma volta no cronômetro em execução e avisa o overlay e integrações (`timer-lap`).
+pub fn record_lap(app: &AppHandle, timer_id: &str) -> Result<Lap, String> {
+    let lap = {
+        let mut engine = ENGINE.lock().unwrap();
+        let run = engine
+            .runs
+            .get(&primary_key(timer_id))
+            .cloned()
+            .ok_or_else(|| format!("Stopwatch {} is not running", timer_id))?;
+        if !run.counts_up || run.status == RunStatus::Overtime {
+            return Err("Laps are only available for stopwatches".to_string());
+        }
+
+        let split_ms = (run.current_seconds() * 1000.0) as u64;
+        let laps = engine.laps.entry(timer_id.to_string()).or_default();
+        let previous = laps.last().map(|l| l.split_ms).unwrap_or(0);
+        let lap = Lap {
+            index: laps.len() as u32 + 1,
+            split_ms,
+            delta_ms: split_ms.saturating_sub(previous),
+            recorded_at: Utc::now().timestamp_millis(),
+        };
+        laps.push(lap.clone());
+        lap
+    };
+
+    println!(
+        "[ENGINE] Lap {} on {}: split {}ms, delta {}ms",
+        lap.index, timer_id, lap.split_ms, lap.delta_ms
+    );
+    let _ = app.emit("timer-lap", json!({ "timerId": timer_id, "lap": lap }));
+    Ok(lap)
+}
+
+/// Voltas registradas na execução atual (ou na última) do cronômetro.
+pub fn laps(timer_id: &str) -> Vec<Lap> {
+    ENGINE
+        .lock()
+        .unwrap()
+        .laps
+        .get(timer_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Estado atual de todos os timers configurados. Contagens até reset são calculadas
+/// a partir da regra de recorrência; os demais vêm dos eventos reportados pelo overlay.
+pub fn timer_states(app: &AppHandle) -> Result<Vec<TimerStateSnapshot>, String> {
+    let config = load_config_value(app)?;
+    let icons = config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let runs = ENGINE.lock().unwrap().runs.clone();
+    let now = Utc::now();
+
+    let states = icons
+        .iter()
+        .filter_map(|icon| {
+            let timer_id = icon.get("id").and_then(|id| id.as_str())?.to_string();
+            let name = icon.get("name").and_then(|n| n.as_str()).map(|n| n.to_string());
+            let timer_type = icon
+                .get("timerType")
+                .and_then(|t| t.as_str())
+                .unwrap_or("countdown")
+                .to_string();
+            let duration = icon.get("timerDuration").and_then(|d| d.as_u64());
+
+            let mut state = TimerStateSnapshot {
+                timer_id,
+                name,
+                timer_type,
+                status: RunStatus::Idle,
+                remaining_seconds: None,
+                elapsed_seconds: None,
+                duration_seconds: None,
+                phase: None,
+                overtime_seconds: None,
+                next_reset_at: None,
+                stacked_instances: Vec::new(),
+                charges: None,
+                pomodoro: None,
+                chess_clock: None,
+            };
+
+            let mut stacked: Vec<TimerInstanceState> = runs
+                .iter()
+                .filter(|((id, instance), _)| *id == state.timer_id && *instance > 0)
+                .map(|((_, instance), run)| {
+                    let seconds = run.current_seconds();
+                    TimerInstanceState {
+                        instance: *instance,
+                        status: run.status,
+                        remaining_seconds: (!run.counts_up).then(|| seconds.ceil() as u64),
+                        elapsed_seconds: run.counts_up.then_some(seconds as u64),
+                    }
+                })
+                .collect();
+            stacked.sort_by_key(|s| s.instance);
+            state.stacked_instances = stacked;
+            let primary = primary_key(&state.timer_id);
+
+            match state.timer_type.as_str() {
+                "reset" => match resets::reset_config(icon).and_then(|reset| resets::next_reset(&reset, now)) {
+                    Ok(window) => {
+                        state.status = RunStatus::Running;
+                        state.remaining_seconds = Some((window.next - now).num_seconds().max(0) as u64);
+                        state.duration_seconds = Some(window.period_secs.max(0) as u64);
+                        state.next_reset_at = Some(window.next.timestamp_millis());
+                    }
+                    Err(e) => println!("[ENGINE] WARNING: Reset timer {} has an invalid rule: {}", state.timer_id, e),
+                },
+                "alarm" => {}
+                "charges" => {
+                    state.charges = charges::charge_state(icon);
+                    if let Some(charges) = &state.charges {
+                        state.status = if charges.recharge_remaining_ms.is_some() {
+                            RunStatus::Running
+                        } else {
+                            RunStatus::Idle
+                        };
+                        state.remaining_seconds = charges.recharge_remaining_ms.map(|ms| ms.div_ceil(1000));
+                        state.duration_seconds = Some(charges.recharge_seconds);
+                    }
+                }
+                "pomodoro" => {
+                    state.pomodoro = pomodoro::pomodoro_state(app, icon);
+                    if let Some(pomodoro) = &state.pomodoro {
+                        state.status = pomodoro.status;
+                        state.remaining_seconds = Some(pomodoro.remaining_ms.div_ceil(1000));
+                        state.duration_seconds = Some(pomodoro.phase_seconds);
+                        state.phase = Some(pomodoro.cycle);
+                    }
+                }
+                "chessClock" => {
+                    state.chess_clock = chessclock::chess_clock_state(icon);
+                    if let Some(clock) = &state.chess_clock {
+                        state.status = clock.status;
+                        state.remaining_seconds = Some(clock.players[clock.active_player].remaining_ms.div_ceil(1000));
+                        state.duration_seconds = Some(clock.bank_seconds);
+                    }
+                }
+                "stopwatch" => {
+                    let run = runs.get(&primary);
+                    state.status = run.map(|r| r.status).unwrap_or(RunStatus::Idle);
+                    state.elapsed_seconds = Some(run.map(|r| r.current_seconds() as u64).unwrap_or(0));
+                }
+                _ => {
+                    let run = runs.get(&primary);
+                    state.status = run.map(|r| r.status).unwrap_or(RunStatus::Idle);
+                    state.duration_seconds = duration;
+                    state.phase = run.map(|r| r.phase);
+                    match run {
+                        Some(run) if run.status == RunStatus::Overtime => {
+                            state.remaining_seconds = Some(0);
+                            state.overtime_seconds = Some(run.current_seconds() as u64);
+                        }
+                        _ => {
+                            state.remaining_seconds = run
+                                .map(|r| r.current_seconds().ceil() as u64)
+                                .or(duration);
+                        }
+                    }
+                }
+            }
+            Some(state)
+        })
+        .collect();
+    Ok(states)
+}
+
+fn track_start(timer_id: &str) {
+    let mut engine = ENGINE.lock().unwrap();
+    if let Some(origin) = engine.pending_chain_starts.remove(timer_id) {
+        engine.chain_origins.insert(timer_id.to_string(), origin);
+    } else {
+        // Início manual (atalho, tray, API): começa uma cadeia nova
+        engine.chain_origins.insert(timer_id.to_string(), timer_id.to_string());
+        engine
+            .chain_fire_counts
+            .retain(|(origin, _), _| origin != timer_id);
+    }
+}
+
+/// Conta um disparo da regra e devolve a origem da cadeia, ou None se o limite foi atingido.
+fn claim_chain_run(timer_id: &str, rule: &ChainRule) -> Option<String> {
+    let mut engine = ENGINE.lock().unwrap();
+    let origin = engine
+        .chain_origins
+        .get(timer_id)
+        .cloned()
+        .unwrap_or_else(|| timer_id.to_string());
+
+    let count = engine
+        .chain_fire_counts
+        .entry((origin.clone(), rule.id.clone()))
+        .or_insert(0);
+    if let Some(max_runs) = rule.max_runs {
+        if *count >= max_runs {
+            println!(
+                "[CHAIN] Rule {} on {} reached its repeat cap ({})",
+                rule.id, timer_id, max_runs
+            );
+            return None;
+        }
+    }
+    *count += 1;
+    Some(origin)
+}
+
+fn schedule_chain_target(app: AppHandle, origin: String, rule: ChainRule) {
+    std::thread::spawn(move || {
+        if let Some(delay) = rule.delay.filter(|d| *d > 0) {
+            std::thread::sleep(std::time::Duration::from_secs(delay));
+        }
+
+        let target_id = match &rule.target {
+            ChainTarget::Timer { timer_id } => timer_id.clone(),
+            ChainTarget::Preset { preset_id } => match create_timer_from_preset_with_id(&app, preset_id) {
+                Ok(id) => id,
+                Err(e) => {
+                    println!("[CHAIN] ERROR: Failed to create timer from preset {}: {}", preset_id, e);
+                    return;
+                }
+            },
+        };
+
+        println!("[CHAIN] Rule {} starting timer {}", rule.id, target_id);
+        ENGINE
+            .lock()
+            .unwrap()
+            .pending_chain_starts
+            .insert(target_id.clone(), origin);
+        history::note_trigger(&target_id, TriggerSource::Chain);
+
+        if let Some(overlay_window) = app.get_webview_window("overlay") {
+            let _ = overlay_window.emit("start-specific-timer", target_id);
+        } else {
+            println!("[CHAIN] WARNING: Overlay window not found when starting chained timer");
+        }
+    });
+}
+
+pub(crate) fn load_config_value(app: &AppHandle) -> Result<Value, String> {
+    let config_str = load_config(app.clone())?;
+    let mut config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+    // Timers rápidos efêmeros não estão no arquivo, mas contam como timers normais
+    quick::with_ephemeral(&mut config);
+    Ok(config)
+}
@@ -0,0 +1,306 @@
+use crate::history::{self, HistoryQuery};
+use crate::stats::{self, Outcome, Run};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::fs;
+use tauri::AppHandle;
+
+/// Limite de uma linha de conteúdo do iCalendar, sem o CRLF (RFC 5545 §3.1)
+const ICS_LINE_OCTETS: usize = 75;
+
+/// Formatos aceitos por `export_history`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    /// Um objeto JSON por linha
+    Jsonl,
+    /// iCalendar com um VEVENT por sessão
+    Ics,
+    /// Array JSON aceito por `timew import`
+    Timewarrior,
+}
+
+/// Filtros da exportação; campos ausentes não filtram.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFilter {
+    /// Milissegundos desde a epoch, comparados com o início da sessão
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub timer_ids: Option<Vec<String>>,
+    /// Só execuções com um destes rótulos
+    pub labels: Option<Vec<String>>,
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Completed => "completed",
+        Outcome::Cancelled => "cancelled",
+    }
+}
+
+fn run_name(run: &Run) -> &str {
+    run.name.as_deref().unwrap_or(&run.timer_id)
+}
+
+/// Nome do timer seguido do rótulo, quando houver ("Focus – invoice review")
+fn run_title(run: &Run) -> String {
+    match &run.label {
+        Some(label) => format!("{} – {}", run_name(run), label),
+        None => run_name(run).to_string(),
+    }
+}
+
+/// Campo de texto do CSV. Valores que começam como fórmula ganham um `'` na frente,
+/// para planilhas não executarem o que o usuário digitou em nomes e anotações.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn to_csv(runs: &[Run]) -> String {
+    let mut out = String::from("timer_id,name,label,note,start,end,duration_seconds,planned_seconds,repeats,outcome\n");
+    for run in runs {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&run.timer_id),
+            csv_field(run_name(run)),
+            csv_field(run.label.as_deref().unwrap_or_default()),
+            csv_field(run.note.as_deref().unwrap_or_default()),
+            run.started.to_rfc3339_opts(SecondsFormat::Secs, false),
+            run.ended.to_rfc3339_opts(SecondsFormat::Secs, false),
+            run.seconds,
+            run.planned_seconds.map(|s| s.to_string()).unwrap_or_default(),
+            run.repeat_count,
+            outcome_name(run.outcome)
+        );
+    }
+    out
+}
+
+fn run_json(run: &Run) -> Value {
+    json!({
+        "timerId": run.timer_id,
+        "name": run_name(run),
+        "label": run.label,
+        "note": run.note,
+        "start": run.started.to_rfc3339_opts(SecondsFormat::Secs, false),
+        "end": run.ended.to_rfc3339_opts(SecondsFormat::Secs, false),
+        "durationSeconds": run.seconds,
+        "plannedSeconds": run.planned_seconds,
+        "repeats": run.repeat_count,
+        "outcome": outcome_name(run.outcome),
+    })
+}
+
+fn to_jsonl(runs: &[Run]) -> String {
+    runs.iter().map(|run| format!("{}\n", run_json(run))).collect()
+}
+
+/// Data-hora UTC no formato básico do iCalendar e do Timewarrior (`20261018T142500Z`)
+fn utc_stamp(time: &DateTime<Local>) -> String {
+    time.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+/// Quebra uma linha longa em linhas de até 75 octetos; as continuações começam com
+/// um espaço. Nunca corta um caractere UTF-8 no meio.
+fn ics_fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > ICS_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+fn to_ics(runs: &[Run]) -> String {
+    let now = utc_stamp(&Local::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//overlay-timer//history export//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for run in runs {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:{}-{}@overlay-timer",
+            ics_text(&run.timer_id),
+            run.started.timestamp_millis()
+        ));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!("DTSTART:{}", utc_stamp(&run.started)));
+        lines.push(format!("DTEND:{}", utc_stamp(&run.ended)));
+        lines.push(format!("SUMMARY:{}", ics_text(&run_title(run))));
+        let mut description = format!("{}s counted ({})", run.seconds, outcome_name(run.outcome));
+        if let Some(note) = &run.note {
+            description = format!("{}\n{}", note, description);
+        }
+        lines.push(format!("DESCRIPTION:{}", ics_text(&description)));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    // iCalendar exige CRLF
+    lines.iter().map(|line| ics_fold(line) + "\r\n").collect()
+}
+
+fn to_timewarrior(runs: &[Run]) -> Result<String, String> {
+    let intervals: Vec<Value> = runs
+        .iter()
+        .map(|run| {
+            let mut tags = vec![run_name(run).to_string()];
+            tags.extend(run.label.clone());
+            json!({
+                "start": utc_stamp(&run.started),
+                "end": utc_stamp(&run.ended),
+                "tags": tags,
+                "annotation": run
+                    .note
+                    .clone()
+                    .unwrap_or_else(|| format!("{} ({})", run.timer_id, outcome_name(run.outcome))),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&intervals).map_err(|e| e.to_string())
+}
+
+/// Exporta as sessões do histórico (uma por execução terminada) para `path`.
+/// Retorna quantas sessões foram escritas.
+pub fn export_history(
+    app: &AppHandle,
+    path: &str,
+    format: ExportFormat,
+    filter: &ExportFilter,
+) -> Result<usize, String> {
+    let entries = history::query(
+        app,
+        &HistoryQuery {
+            limit: Some(usize::MAX),
+            ..Default::default()
+        },
+    );
+    let runs: Vec<Run> = stats::collect_runs(&entries)
+        .into_iter()
+        .filter(|run| filter.from.is_none_or(|from| run.started.timestamp_millis() >= from))
+        .filter(|run| filter.to.is_none_or(|to| run.started.timestamp_millis() <= to))
+        .filter(|run| {
+            filter
+                .timer_ids
+                .as_ref()
+                .is_none_or(|ids| ids.is_empty() || ids.contains(&run.timer_id))
+        })
+        .filter(|run| {
+            filter.labels.as_ref().is_none_or(|labels| {
+                labels.is_empty() || run.label.as_ref().is_some_and(|label| labels.contains(label))
+            })
+        })
+        .collect();
+
+    let content = match format {
+        ExportFormat::Csv => to_csv(&runs),
+        ExportFormat::Jsonl => to_jsonl(&runs),
+        ExportFormat::Ics => to_ics(&runs),
+        ExportFormat::Timewarrior => to_timewarrior(&runs)?,
+    };
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    println!("[EXPORT] {} session(s) as {:?} to {}", runs.len(), format, path);
+    Ok(runs.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn run(name: &str, note: Option<&str>) -> Run {
+        let started = Local.timestamp_opt(1_790_000_000, 0).unwrap();
+        Run {
+            timer_id: "focus".to_string(),
+            name: Some(name.to_string()),
+            started,
+            ended: started + chrono::Duration::minutes(25),
+            outcome: Outcome::Completed,
+            seconds: 1500,
+            planned_seconds: Some(1500),
+            repeat_count: 0,
+            label: None,
+            note: note.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn csv_field_quotes_when_needed() {
+        assert_eq!(csv_field("Focus"), "Focus");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("one\ntwo"), "\"one\ntwo\"");
+    }
+
+    #[test]
+    fn csv_field_neutralizes_formulas() {
+        assert_eq!(csv_field("=SUM(A1:A2)"), "'=SUM(A1:A2)");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-2"), "'-2");
+        assert_eq!(csv_field("@cmd"), "'@cmd");
+        assert_eq!(csv_field("=HYPERLINK(\"x\",\"y\")"), "\"'=HYPERLINK(\"\"x\"\",\"\"y\"\")\"");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+
+    #[test]
+    fn ics_text_escapes_specials_and_line_breaks() {
+        assert_eq!(ics_text("a,b;c\\d"), "a\\,b\\;c\\\\d");
+        assert_eq!(ics_text("one\r\ntwo\rthree\nfour"), "one\\ntwo\\nthree\\nfour");
+    }
+
+    #[test]
+    fn ics_fold_splits_at_75_octets() {
+        let folded = ics_fold(&"x".repeat(160));
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.iter().map(|line| line.len()).collect::<Vec<_>>(), [75, 75, 12]);
+        assert!(lines[1..].iter().all(|line| line.starts_with(' ')));
+        assert_eq!(folded.replace("\r\n ", ""), "x".repeat(160));
+
+        // Caracteres de vários octetos não são cortados
+        let folded = ics_fold(&"é".repeat(40));
+        assert!(folded.split("\r\n").all(|line| line.len() <= ICS_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), "é".repeat(40));
+    }
+
+    #[test]
+    fn to_ics_folds_long_lines_and_ends_with_crlf() {
+        let ics = to_ics(&[run(&"Long focus session ".repeat(6), Some("line one\r\nline two"))]);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= ICS_LINE_OCTETS));
+        assert!(!ics.replace("\r\n", "").contains(['\r', '\n']));
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains("SUMMARY:Long focus session Long focus session"));
+        assert!(unfolded.contains("DESCRIPTION:line one\\nline two\\n1500s counted (completed)\r\n"));
+    }
+}
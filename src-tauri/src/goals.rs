@@ -0,0 +1,659 @@
+use crate::commands::load_config;
+use crate::engine;
+use crate::history::{self, HistoryQuery};
+use crate::stats::{self, format_duration, Outcome, Run};
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+/// Intervalo entre verificações de metas e limites
+const CHECK_INTERVAL_SECS: u64 = 30;
+/// Fração do limite a partir da qual o aviso de "quase no fim" é enviado
+const DEFAULT_WARN_AT: f64 = 0.9;
+/// Quantos períodos para trás a sequência pode contar
+const MAX_STREAK_PERIODS: u32 = 366;
+
+/// Meta (chegar a um mínimo) ou limite (não passar de um máximo).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalKind {
+    #[default]
+    Goal,
+    Budget,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalPeriod {
+    #[default]
+    Day,
+    Week,
+}
+
+/// O que é somado: um timer, um grupo de timers ou um rótulo de execução.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalScope {
+    Timer,
+    Group,
+    Label,
+}
+
+/// Execuções (concluídas, para metas) ou segundos contados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalMetric {
+    Runs,
+    Seconds,
+}
+
+/// Definição em `goals` no config.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoalDefinition {
+    id: String,
+    name: Option<String>,
+    #[serde(default)]
+    kind: GoalKind,
+    #[serde(default)]
+    period: GoalPeriod,
+    scope: GoalScope,
+    /// Id do timer ou do grupo, ou o texto do rótulo
+    target: String,
+    metric: GoalMetric,
+    amount: u64,
+    warn_at: Option<f64>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub id: String,
+    pub name: String,
+    pub kind: GoalKind,
+    pub period: GoalPeriod,
+    pub metric: GoalMetric,
+    pub amount: u64,
+    /// Valor no período atual (execuções em andamento entram nos segundos)
+    pub current: u64,
+    /// current / amount, sem limite superior
+    pub fraction: f64,
+    /// Meta atingida, ou limite ainda respeitado
+    pub met: bool,
+    /// Limite passou do aviso (ou estourou)
+    pub warning: bool,
+    /// Períodos seguidos cumpridos, contando o atual se já cumprido
+    pub streak: u32,
+    /// Início e fim do período atual (ms desde a epoch)
+    pub period_start: i64,
+    pub period_end: i64,
+}
+
+/// (meta, início do período, aviso)
+type NotifiedKey = (String, NaiveDate, &'static str);
+
+/// Avisos já enviados
+static NOTIFIED: LazyLock<Mutex<HashSet<NotifiedKey>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+fn goal_definitions(config: &Value) -> Vec<GoalDefinition> {
+    config
+        .get("goals")
+        .and_then(|g| g.as_array())
+        .map(|goals| {
+            goals
+                .iter()
+                .filter_map(|goal| match serde_json::from_value::<GoalDefinition>(goal.clone()) {
+                    Ok(goal) => Some(goal),
+                    Err(e) => {
+                        println!("[GOALS] WARNING: Ignoring invalid goal: {}", e);
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Valida `goals` antes de salvar a configuração.
+pub fn validate_goals(config: &Value) -> Result<(), String> {
+    let Some(goals) = config.get("goals").and_then(|g| g.as_array()) else {
+        return Ok(());
+    };
+    for goal in goals {
+        let goal: GoalDefinition = serde_json::from_value(goal.clone()).map_err(|e| e.to_string())?;
+        if goal.target.trim().is_empty() {
+            return Err(format!("Goal {} has no target", goal.id));
+        }
+        if goal.amount == 0 {
+            return Err(format!("Goal {}: amount must be at least 1", goal.id));
+        }
+        if goal.warn_at.is_some_and(|warn_at| !(warn_at > 0.0 && warn_at <= 1.0)) {
+            return Err(format!("Goal {}: warnAt must be between 0 and 1", goal.id));
+        }
+    }
+    Ok(())
+}
+
+fn period_start(period: GoalPeriod, date: NaiveDate) -> NaiveDate {
+    match period {
+        GoalPeriod::Day => date,
+        GoalPeriod::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+    }
+}
+
+fn period_length(period: GoalPeriod) -> Duration {
+    match period {
+        GoalPeriod::Day => Duration::days(1),
+        GoalPeriod::Week => Duration::weeks(1),
+    }
+}
+
+fn local_millis(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+        .map(|dt| dt.timestamp_millis())
+        .unwrap_or_default()
+}
+
+/// Timers que contam para a meta; None = qualquer timer (escopo por rótulo)
+fn scope_timers(goal: &GoalDefinition, config: &Value) -> Option<Vec<String>> {
+    match goal.scope {
+        GoalScope::Timer => Some(vec![goal.target.clone()]),
+        GoalScope::Group => Some(
+            config
+                .get("timerGroups")
+                .and_then(|g| g.as_array())
+                .and_then(|groups| {
+                    groups
+                        .iter()
+                        .find(|g| g.get("id").and_then(|id| id.as_str()) == Some(goal.target.as_str()))
+                })
+                .and_then(|group| group.get("timerIds"))
+                .and_then(|ids| ids.as_array())
+                .map(|ids| ids.iter().filter_map(|id| id.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+        ),
+        GoalScope::Label => None,
+    }
+}
+
+fn matches_scope(goal: &GoalDefinition, timers: &Option<Vec<String>>, timer_id: &str, label: Option<&str>) -> bool {
+    match timers {
+        Some(timers) => timers.iter().any(|id| id == timer_id),
+        None => label.is_some_and(|label| label.eq_ignore_ascii_case(goal.target.trim())),
+    }
+}
+
+/// Quanto cada execução soma à meta
+fn run_value(goal: &GoalDefinition, run: &Run) -> u64 {
+    match goal.metric {
+        GoalMetric::Seconds => run.seconds,
+        // Metas contam só execuções concluídas; limites contam todas as iniciadas
+        GoalMetric::Runs => match (goal.kind, run.outcome) {
+            (GoalKind::Goal, Outcome::Cancelled) => 0,
+            _ => 1,
+        },
+    }
+}
+
+fn is_met(goal: &GoalDefinition, value: u64) -> bool {
+    match goal.kind {
+        GoalKind::Goal => value >= goal.amount,
+        GoalKind::Budget => value <= goal.amount,
+    }
+}
+
+/// Execução ainda aberta: (timer, início em ms, rótulo, segundos contados)
+type OpenRun = (String, i64, Option<String>, f64);
+
+fn evaluate(
+    goal: &GoalDefinition,
+    config: &Value,
+    runs: &[Run],
+    open_runs: &[OpenRun],
+    earliest: Option<NaiveDate>,
+    today: NaiveDate,
+) -> GoalProgress {
+    let timers = scope_timers(goal, config);
+    let current_start = period_start(goal.period, today);
+
+    let mut per_period: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for run in runs {
+        if matches_scope(goal, &timers, &run.timer_id, run.label.as_deref()) {
+            *per_period
+                .entry(period_start(goal.period, run.started.date_naive()))
+                .or_default() += run_value(goal, run);
+        }
+    }
+    // Execuções abertas contam o tempo já decorrido no período atual
+    if goal.metric == GoalMetric::Seconds {
+        for (timer_id, started_at, label, seconds) in open_runs {
+            let in_period = *started_at >= local_millis(current_start);
+            if in_period && matches_scope(goal, &timers, timer_id, label.as_deref()) {
+                *per_period.entry(current_start).or_default() += *seconds as u64;
+            }
+        }
+    }
+
+    let current = per_period.get(&current_start).copied().unwrap_or(0);
+    let met = is_met(goal, current);
+    let fraction = if goal.amount == 0 {
+        if current == 0 { 0.0 } else { 1.0 }
+    } else {
+        current as f64 / goal.amount as f64
+    };
+    let warning = goal.kind == GoalKind::Budget && fraction >= goal.warn_at.unwrap_or(DEFAULT_WARN_AT);
+
+    // Metas ainda não cumpridas hoje não quebram a sequência; limites contam até o primeiro uso registrado
+    let mut streak = 0;
+    let mut start = current_start;
+    if goal.kind == GoalKind::Goal && !met {
+        start -= period_length(goal.period);
+    }
+    let first_period = earliest.map(|date| period_start(goal.period, date));
+    for _ in 0..MAX_STREAK_PERIODS {
+        if first_period.is_none_or(|first| start < first) {
+            break;
+        }
+        if !is_met(goal, per_period.get(&start).copied().unwrap_or(0)) {
+            break;
+        }
+        streak += 1;
+        start -= period_length(goal.period);
+    }
+
+    GoalProgress {
+        id: goal.id.clone(),
+        name: goal.name.clone().unwrap_or_else(|| goal.target.clone()),
+        kind: goal.kind,
+        period: goal.period,
+        metric: goal.metric,
+        amount: goal.amount,
+        current,
+        fraction,
+        met,
+        warning,
+        streak,
+        period_start: local_millis(current_start),
+        period_end: local_millis(current_start + period_length(goal.period)) - 1,
+    }
+}
+
+fn load_config_value(app: &AppHandle) -> Option<Value> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+}
+
+/// Progresso de cada meta e limite ativo. Com `with_streaks`, lê o histórico inteiro para
+/// calcular as sequências; sem, só o período atual.
+fn progress(app: &AppHandle, with_streaks: bool) -> Vec<GoalProgress> {
+    let Some(config) = load_config_value(app) else { return Vec::new() };
+    let goals: Vec<GoalDefinition> = goal_definitions(&config).into_iter().filter(|g| g.enabled).collect();
+    if goals.is_empty() {
+        return Vec::new();
+    }
+
+    // A semana atual cobre também o dia atual
+    let from = (!with_streaks).then(|| local_millis(period_start(GoalPeriod::Week, Local::now().date_naive())));
+    let entries = history::query(
+        app,
+        &HistoryQuery {
+            from,
+            limit: Some(usize::MAX),
+            ..Default::default()
+        },
+    );
+    let earliest = entries
+        .iter()
+        .map(|entry| entry.at)
+        .min()
+        .and_then(|at| Local.timestamp_millis_opt(at).single())
+        .map(|at| at.date_naive());
+    let runs = stats::collect_runs(&entries);
+    let open_runs = engine::open_run_totals();
+    let today = Local::now().date_naive();
+    goals
+        .iter()
+        .map(|goal| evaluate(goal, &config, &runs, &open_runs, earliest, today))
+        .collect()
+}
+
+/// Progresso e sequências de todas as metas e limites.
+pub fn goal_progress(app: &AppHandle) -> Vec<GoalProgress> {
+    progress(app, true)
+}
+
+fn describe(progress: &GoalProgress) -> String {
+    let value = |v: u64| match progress.metric {
+        GoalMetric::Runs => v.to_string(),
+        GoalMetric::Seconds => format_duration(v),
+    };
+    let period = match progress.period {
+        GoalPeriod::Day => "today",
+        GoalPeriod::Week => "this week",
+    };
+    let unit = if progress.metric == GoalMetric::Runs { " runs" } else { "" };
+    format!(
+        "{}: {} of {}{} {}",
+        progress.name,
+        value(progress.current),
+        value(progress.amount),
+        unit,
+        period
+    )
+}
+
+fn notify(app: &AppHandle, title: &str, body: &str) {
+    println!("[GOALS] {}: {}", title, body);
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        println!("[GOALS] WARNING: Failed to show notification: {}", e);
+    }
+}
+
+/// Avisos ainda não enviados no período de cada meta, já marcados como enviados.
+fn new_alerts<'a>(
+    progress: &'a [GoalProgress],
+    notified: &mut HashSet<NotifiedKey>,
+) -> Vec<(&'static str, &'a GoalProgress)> {
+    let mut alerts = Vec::new();
+    for progress in progress {
+        let Some(period) = Local
+            .timestamp_millis_opt(progress.period_start)
+            .single()
+            .map(|at| at.date_naive())
+        else {
+            continue;
+        };
+        let alert = match progress.kind {
+            GoalKind::Goal if progress.met => Some(("met", "Goal reached")),
+            GoalKind::Budget if !progress.met => Some(("exceeded", "Budget exceeded")),
+            GoalKind::Budget if progress.warning => Some(("warning", "Budget almost used")),
+            _ => None,
+        };
+        let Some((key, title)) = alert else { continue };
+        if notified.insert((progress.id.clone(), period, key)) {
+            alerts.push((title, progress));
+        }
+    }
+    alerts
+}
+
+/// Notifica metas atingidas e limites perto do fim (ou estourados), uma vez por período.
+/// Na primeira passada só marca o que já estava assim, para não repetir avisos ao abrir o app.
+fn check(app: &AppHandle, silent: bool) {
+    let progress = progress(app, false);
+    let alerts = new_alerts(&progress, &mut NOTIFIED.lock().unwrap());
+    if silent {
+        return;
+    }
+    for (title, progress) in alerts {
+        notify(app, title, &describe(progress));
+    }
+}
+
+/// Inicia a thread que acompanha metas e limites.
+pub fn start_goal_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[GOALS] Monitor started");
+        let mut first = true;
+        loop {
+            check(&app, first);
+            first = false;
+            std::thread::sleep(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use serde_json::json;
+
+    fn date(day: u32) -> NaiveDate {
+        // Outubro de 2026: dia 12 é segunda-feira
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap()
+    }
+
+    fn run(timer_id: &str, day: u32, seconds: u64, outcome: Outcome, label: Option<&str>) -> Run {
+        Run {
+            timer_id: timer_id.to_string(),
+            name: None,
+            started: at(day, 10),
+            ended: at(day, 10) + Duration::seconds(seconds as i64),
+            outcome,
+            seconds,
+            planned_seconds: None,
+            repeat_count: 0,
+            label: label.map(String::from),
+            note: None,
+        }
+    }
+
+    fn goal(definition: Value) -> GoalDefinition {
+        let mut base = json!({ "id": "g", "scope": "timer", "target": "tea", "metric": "runs", "amount": 1 });
+        for (key, value) in definition.as_object().unwrap() {
+            base[key] = value.clone();
+        }
+        serde_json::from_value(base).unwrap()
+    }
+
+    fn config() -> Value {
+        json!({ "timerGroups": [{ "id": "drinks", "timerIds": ["tea", "coffee"] }] })
+    }
+
+    #[test]
+    fn periods_start_at_the_day_or_on_monday() {
+        assert_eq!(period_start(GoalPeriod::Day, date(14)), date(14));
+        assert_eq!(period_start(GoalPeriod::Week, date(14)), date(12));
+        assert_eq!(period_start(GoalPeriod::Week, date(12)), date(12));
+        // Domingo ainda é da semana que começou na segunda anterior
+        assert_eq!(period_start(GoalPeriod::Week, date(18)), date(12));
+        assert_eq!(period_start(GoalPeriod::Week, date(19)), date(19));
+    }
+
+    #[test]
+    fn scope_matches_timer_group_or_label() {
+        let config = config();
+        let cases = [
+            (json!({ "scope": "timer", "target": "tea" }), "tea", None, true),
+            (json!({ "scope": "timer", "target": "tea" }), "coffee", None, false),
+            (json!({ "scope": "group", "target": "drinks" }), "coffee", None, true),
+            (json!({ "scope": "group", "target": "drinks" }), "egg", Some("x"), false),
+            (json!({ "scope": "group", "target": "missing" }), "tea", None, false),
+            // Rótulo sem diferenciar maiúsculas, em qualquer timer
+            (json!({ "scope": "label", "target": " Invoices " }), "egg", Some("invoices"), true),
+            (json!({ "scope": "label", "target": "invoices" }), "egg", None, false),
+        ];
+        for (definition, timer_id, label, expected) in cases {
+            let goal = goal(definition.clone());
+            let timers = scope_timers(&goal, &config);
+            assert_eq!(matches_scope(&goal, &timers, timer_id, label), expected, "{} {}", definition, timer_id);
+        }
+    }
+
+    #[test]
+    fn run_value_counts_seconds_or_runs() {
+        let done = run("tea", 14, 300, Outcome::Completed, None);
+        let cancelled = run("tea", 14, 120, Outcome::Cancelled, None);
+        let seconds = goal(json!({ "metric": "seconds" }));
+        let runs_goal = goal(json!({}));
+        let runs_budget = goal(json!({ "kind": "budget" }));
+
+        assert_eq!(run_value(&seconds, &done), 300);
+        assert_eq!(run_value(&seconds, &cancelled), 120);
+        assert_eq!(run_value(&runs_goal, &done), 1);
+        // Metas só contam concluídas; limites contam todas
+        assert_eq!(run_value(&runs_goal, &cancelled), 0);
+        assert_eq!(run_value(&runs_budget, &cancelled), 1);
+    }
+
+    #[test]
+    fn goals_need_the_amount_and_budgets_must_not_pass_it() {
+        let target = goal(json!({ "amount": 3 }));
+        let budget = goal(json!({ "kind": "budget", "amount": 3 }));
+        assert_eq!([2, 3, 4].map(|v| is_met(&target, v)), [false, true, true]);
+        assert_eq!([2, 3, 4].map(|v| is_met(&budget, v)), [true, true, false]);
+    }
+
+    #[test]
+    fn streaks_skip_an_unfinished_today_and_stop_at_a_miss() {
+        let goal = goal(json!({}));
+        let mut runs = vec![
+            run("tea", 9, 60, Outcome::Completed, None),
+            run("tea", 11, 60, Outcome::Completed, None),
+            run("tea", 12, 60, Outcome::Completed, None),
+            run("tea", 13, 60, Outcome::Completed, None),
+            run("tea", 13, 60, Outcome::Cancelled, None),
+        ];
+        let today = date(14);
+        let progress = evaluate(&goal, &config(), &runs, &[], Some(date(9)), today);
+        assert!(!progress.met);
+        // 11, 12 e 13; o dia 10 sem execução quebra a sequência
+        assert_eq!(progress.streak, 3);
+
+        runs.push(run("tea", 14, 60, Outcome::Completed, None));
+        let progress = evaluate(&goal, &config(), &runs, &[], Some(date(9)), today);
+        assert_eq!((progress.met, progress.current, progress.streak), (true, 1, 4));
+        assert_eq!(progress.period_start, at(14, 0).timestamp_millis());
+        assert_eq!(progress.period_end, at(15, 0).timestamp_millis() - 1);
+    }
+
+    #[test]
+    fn budget_streaks_count_back_to_the_first_recorded_day() {
+        let budget = goal(json!({ "kind": "budget", "amount": 1 }));
+        let runs = vec![
+            run("tea", 10, 60, Outcome::Completed, None),
+            run("tea", 10, 60, Outcome::Completed, None),
+            run("tea", 12, 60, Outcome::Completed, None),
+        ];
+        // Dias 11 a 14 dentro do limite; o dia 10 passou
+        let progress = evaluate(&budget, &config(), &runs, &[], Some(date(10)), date(14));
+        assert_eq!((progress.met, progress.streak), (true, 4));
+        // Sem histórico antes, não conta dias que o app nem registrava
+        let progress = evaluate(&budget, &config(), &runs, &[], Some(date(13)), date(14));
+        assert_eq!(progress.streak, 2);
+    }
+
+    #[test]
+    fn weekly_goals_sum_the_whole_week() {
+        let weekly = goal(json!({ "period": "week", "metric": "seconds", "amount": 600 }));
+        let runs = vec![
+            run("tea", 11, 400, Outcome::Completed, None),
+            run("tea", 12, 300, Outcome::Completed, None),
+            run("tea", 18, 300, Outcome::Cancelled, None),
+        ];
+        let progress = evaluate(&weekly, &config(), &runs, &[], Some(date(11)), date(18));
+        assert_eq!((progress.current, progress.met), (600, true));
+        // Semana anterior (começou no dia 5) não chegou a 600
+        assert_eq!(progress.streak, 1);
+    }
+
+    #[test]
+    fn open_runs_count_toward_seconds_in_the_current_period() {
+        let seconds = goal(json!({ "metric": "seconds", "amount": 900 }));
+        let open: Vec<OpenRun> = vec![
+            ("tea".to_string(), at(14, 9).timestamp_millis(), None, 500.0),
+            // Começou ontem: fica de fora do período atual
+            ("tea".to_string(), at(13, 23).timestamp_millis(), None, 100.0),
+            ("egg".to_string(), at(14, 9).timestamp_millis(), None, 100.0),
+        ];
+        let runs = vec![run("tea", 14, 400, Outcome::Completed, None)];
+        let progress = evaluate(&seconds, &config(), &runs, &open, Some(date(14)), date(14));
+        assert_eq!((progress.current, progress.met), (900, true));
+    }
+
+    #[test]
+    fn budgets_warn_at_ninety_percent_by_default() {
+        let cases = [
+            (json!({ "kind": "budget", "amount": 10 }), 8, false),
+            (json!({ "kind": "budget", "amount": 10 }), 9, true),
+            (json!({ "kind": "budget", "amount": 10, "warnAt": 0.5 }), 5, true),
+            (json!({ "kind": "budget", "amount": 10, "warnAt": 0.5 }), 4, false),
+            // Metas nunca avisam
+            (json!({ "amount": 10 }), 10, false),
+        ];
+        for (definition, count, expected) in cases {
+            let goal = goal(definition.clone());
+            let runs: Vec<Run> = (0..count).map(|_| run("tea", 14, 60, Outcome::Completed, None)).collect();
+            let progress = evaluate(&goal, &config(), &runs, &[], Some(date(14)), date(14));
+            assert_eq!(progress.warning, expected, "{} with {}", definition, count);
+        }
+    }
+
+    fn progress_of(kind: GoalKind, met: bool, warning: bool, day: u32) -> GoalProgress {
+        GoalProgress {
+            id: "g".to_string(),
+            name: "Tea".to_string(),
+            kind,
+            period: GoalPeriod::Day,
+            metric: GoalMetric::Runs,
+            amount: 3,
+            current: 3,
+            fraction: 1.0,
+            met,
+            warning,
+            streak: 0,
+            period_start: at(day, 0).timestamp_millis(),
+            period_end: at(day + 1, 0).timestamp_millis() - 1,
+        }
+    }
+
+    #[test]
+    fn each_alert_is_sent_once_per_period() {
+        let mut notified = HashSet::new();
+        let titles = |list: &[GoalProgress], notified: &mut HashSet<NotifiedKey>| -> Vec<&'static str> {
+            new_alerts(list, notified).into_iter().map(|(title, _)| title).collect()
+        };
+
+        let reached = [progress_of(GoalKind::Goal, true, false, 14)];
+        assert_eq!(titles(&reached, &mut notified), vec!["Goal reached"]);
+        assert_eq!(titles(&reached, &mut notified), Vec::<&str>::new());
+        // Dia seguinte é outro período
+        assert_eq!(titles(&[progress_of(GoalKind::Goal, true, false, 15)], &mut notified), vec!["Goal reached"]);
+        assert_eq!(titles(&[progress_of(GoalKind::Goal, false, false, 16)], &mut notified), Vec::<&str>::new());
+
+        // O aviso e o estouro do limite são avisos diferentes no mesmo período
+        let warning = [progress_of(GoalKind::Budget, true, true, 14)];
+        assert_eq!(titles(&warning, &mut notified), vec!["Budget almost used"]);
+        assert_eq!(titles(&warning, &mut notified), Vec::<&str>::new());
+        let exceeded = [progress_of(GoalKind::Budget, false, true, 14)];
+        assert_eq!(titles(&exceeded, &mut notified), vec!["Budget exceeded"]);
+        assert_eq!(titles(&exceeded, &mut notified), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn validation_rejects_malformed_goals() {
+        let valid = json!({ "id": "g", "scope": "label", "target": "x", "metric": "seconds", "amount": 60 });
+        assert!(validate_goals(&json!({ "goals": [valid.clone()] })).is_ok());
+        let broken = [
+            json!({ "target": " " }),
+            json!({ "amount": 0 }),
+            json!({ "scope": "project" }),
+            json!({ "warnAt": 1.5 }),
+            json!({ "warnAt": 0 }),
+        ];
+        for patch in broken {
+            let mut goal = valid.clone();
+            for (key, value) in patch.as_object().unwrap() {
+                goal[key] = value.clone();
+            }
+            assert!(validate_goals(&json!({ "goals": [goal.clone()] })).is_err(), "{}", goal);
+        }
+    }
+}
@@ -0,0 +1,664 @@
+use crate::commands::load_config;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Listener, Manager};
+
+/// Quantas execuções `get_run_history` devolve quando o limite não é informado
+const DEFAULT_RUN_LIMIT: usize = 500;
+/// Log de eventos persistente (uma entrada JSON por linha), no diretório de dados do app
+const HISTORY_FILE: &str = "history.jsonl";
+/// Dias mantidos quando `historyRetentionDays` não está na configuração (0 = para sempre)
+const DEFAULT_RETENTION_DAYS: u64 = 90;
+const PRUNE_INTERVAL_SECS: u64 = 6 * 60 * 60;
+const DEFAULT_QUERY_LIMIT: usize = 1000;
+/// Por quanto tempo a origem anotada de uma ação espera o evento do overlay
+const TRIGGER_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunOutcome {
+    Finished,
+    Cancelled,
+}
+
+/// Uma execução concluída de um timer, reconstruída a partir do histórico persistente.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunRecord {
+    pub timer_id: String,
+    pub name: Option<String>,
+    /// Instância paralela (política `stack`); ausente = principal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<u32>,
+    /// Início e fim em milissegundos desde a epoch (UTC)
+    pub started_at: i64,
+    pub ended_at: i64,
+    /// Reset durante o overtime ainda conta como concluída
+    pub outcome: RunOutcome,
+    /// Quanto tempo o timer ficou em overtime antes de ser reconhecido ou resetado
+    pub overtime_seconds: Option<u64>,
+    /// Duração usada em cada repetição (contagens regressivas), na ordem
+    pub durations_seconds: Vec<u64>,
+    /// Tempo efetivamente contado (sem intervalos, com overtime)
+    pub actual_seconds: u64,
+    pub repeat_count: u32,
+    pub label: Option<String>,
+    pub note: Option<String>,
+    /// Semente e primeiro sorteio de timers com duração aleatória, para repetir a sessão
+    pub random_seed: Option<u64>,
+    pub random_draw: Option<u64>,
+}
+
+/// Tipo de evento registrado no histórico persistente.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryEventKind {
+    Started,
+    /// Nova repetição de uma contagem regressiva
+    Repeated,
+    Paused,
+    Resumed,
+    Finished,
+    /// Resetado antes de terminar
+    Cancelled,
+    /// Overtime reconhecido
+    Acknowledged,
+}
+
+/// Quem pediu a ação que gerou o evento.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TriggerSource {
+    Keybind,
+    Tray,
+    Api,
+    Schedule,
+    Chain,
+    LogWatch,
+    Process,
+    Cli,
+}
+
+/// Uma linha do histórico persistente.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub timer_id: String,
+    pub name: Option<String>,
+    pub event: HistoryEventKind,
+    /// Momento do evento em milissegundos desde a epoch (UTC)
+    pub at: i64,
+    /// Início da execução a que o evento pertence
+    pub run_started_at: Option<i64>,
+    /// Duração planejada da fase atual (contagens regressivas)
+    pub planned_seconds: Option<u64>,
+    /// Tempo efetivamente contado na execução até o evento (sem intervalos, com overtime)
+    pub actual_seconds: Option<u64>,
+    /// Repetições concluídas antes do evento
+    pub repeat_count: u32,
+    /// None para eventos automáticos (fim da contagem, repetição)
+    pub source: Option<TriggerSource>,
+    /// Instância paralela (política `stack`); ausente = principal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<u32>,
+    /// Em que se trabalhou na execução ("invoice review"), editável depois
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Semente e primeiro sorteio de timers com duração aleatória, para repetir a sessão
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_draw: Option<u64>,
+}
+
+/// Rótulo e anotação de uma execução.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLabel {
+    pub label: Option<String>,
+    pub note: Option<String>,
+}
+
+impl RunLabel {
+    /// Textos vazios viram None
+    pub fn new(label: Option<String>, note: Option<String>) -> Self {
+        let clean = |text: Option<String>| {
+            text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+        };
+        RunLabel {
+            label: clean(label),
+            note: clean(note),
+        }
+    }
+}
+
+/// Filtros de `query_history`; todos opcionais.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    /// Intervalo em milissegundos desde a epoch (inclusivo)
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub timer_id: Option<String>,
+    pub events: Option<Vec<HistoryEventKind>>,
+    pub limit: Option<usize>,
+}
+
+/// Serializa escritas e a limpeza do arquivo
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+/// Timer -> origem anotada e quando
+type PendingTriggers = HashMap<String, (TriggerSource, Instant)>;
+
+/// Origem de uma ação sobre todos os timers, com os timers que já a usaram
+struct GlobalTrigger {
+    source: TriggerSource,
+    at: Instant,
+    used: HashSet<String>,
+}
+
+/// Origem anotada antes de pedir uma ação ao overlay
+static PENDING_TRIGGERS: LazyLock<Mutex<PendingTriggers>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static PENDING_TRIGGER_ALL: Mutex<Option<GlobalTrigger>> = Mutex::new(None);
+
+/// Anota quem está pedindo a próxima ação de um timer; o overlay não sabe de onde ela veio.
+pub fn note_trigger(timer_id: &str, source: TriggerSource) {
+    PENDING_TRIGGERS
+        .lock()
+        .unwrap()
+        .insert(timer_id.to_string(), (source, Instant::now()));
+}
+
+/// Igual a `note_trigger`, sem substituir uma origem já anotada para o timer.
+pub fn note_trigger_default(timer_id: &str, source: TriggerSource) {
+    let mut pending = PENDING_TRIGGERS.lock().unwrap();
+    if pending.get(timer_id).is_none_or(|(_, at)| at.elapsed() >= TRIGGER_TTL) {
+        pending.insert(timer_id.to_string(), (source, Instant::now()));
+    }
+}
+
+/// Igual a `note_trigger`, para o reset geral, que vale para todos os timers.
+pub fn note_trigger_all(source: TriggerSource) {
+    *PENDING_TRIGGER_ALL.lock().unwrap() = Some(GlobalTrigger {
+        source,
+        at: Instant::now(),
+        used: HashSet::new(),
+    });
+}
+
+/// Origem anotada para o evento do timer, se ainda válida. A anotação geral só
+/// explica cancelamentos e vale uma vez para cada timer.
+pub fn take_trigger(timer_id: &str, event: HistoryEventKind) -> Option<TriggerSource> {
+    {
+        let mut pending = PENDING_TRIGGERS.lock().unwrap();
+        pending.retain(|_, (_, at)| at.elapsed() < TRIGGER_TTL);
+        if let Some((source, _)) = pending.remove(timer_id) {
+            return Some(source);
+        }
+    }
+    if event != HistoryEventKind::Cancelled {
+        return None;
+    }
+    let mut global = PENDING_TRIGGER_ALL.lock().unwrap();
+    let trigger = global.as_mut().filter(|trigger| trigger.at.elapsed() < TRIGGER_TTL)?;
+    trigger.used.insert(timer_id.to_string()).then_some(trigger.source)
+}
+
+/// Rótulos pedidos para a próxima execução de cada timer
+static PENDING_LABELS: LazyLock<Mutex<HashMap<String, RunLabel>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Guarda o rótulo a aplicar quando o timer iniciar.
+pub fn note_label(timer_id: &str, label: RunLabel) {
+    PENDING_LABELS.lock().unwrap().insert(timer_id.to_string(), label);
+}
+
+/// Rótulo pedido para a execução que está começando, se houver.
+pub fn take_label(timer_id: &str) -> Option<RunLabel> {
+    PENDING_LABELS.lock().unwrap().remove(timer_id)
+}
+
+fn history_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(HISTORY_FILE))
+}
+
+/// Acrescenta uma entrada ao histórico persistente.
+pub fn append(app: &AppHandle, entry: &HistoryEntry) {
+    println!(
+        "[HISTORY] {} {:?} (source {:?}, actual {:?}s)",
+        entry.timer_id, entry.event, entry.source, entry.actual_seconds
+    );
+    let Some(path) = history_path(app) else { return };
+    let Ok(line) = serde_json::to_string(entry) else { return };
+
+    let _guard = FILE_LOCK.lock().unwrap();
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = result {
+        println!("[HISTORY] WARNING: Failed to append to {:?}: {}", path, e);
+    }
+}
+
+/// Todas as entradas do arquivo, da mais antiga para a mais recente. Linhas inválidas
+/// (ex.: escrita interrompida) são ignoradas.
+fn read_entries(app: &AppHandle) -> Vec<HistoryEntry> {
+    let Some(path) = history_path(app) else { return Vec::new() };
+    let _guard = FILE_LOCK.lock().unwrap();
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Junta os eventos por execução (timer + instância + início). Execuções sem fim registrado
+/// (ainda rodando ou interrompidas pelo fechamento do app) ficam de fora; as que estão em
+/// overtime entram como concluídas, sem `overtime_seconds` até serem reconhecidas.
+pub fn collect_runs(entries: &[HistoryEntry]) -> Vec<RunRecord> {
+    // Execução montada até o evento atual; `finished_at` marca o início do overtime
+    struct Partial {
+        record: RunRecord,
+        outcome: Option<RunOutcome>,
+        finished_at: Option<i64>,
+    }
+
+    let mut runs: HashMap<(String, u32, i64), Partial> = HashMap::new();
+    // Eventos em ordem cronológica: o último terminal define o fim da execução
+    let mut sorted: Vec<&HistoryEntry> = entries.iter().collect();
+    sorted.sort_by_key(|entry| entry.at);
+
+    for entry in sorted {
+        let started_at = entry.run_started_at.unwrap_or(entry.at);
+        let key = (entry.timer_id.clone(), entry.instance.unwrap_or(0), started_at);
+        let run = runs.entry(key).or_insert_with(|| Partial {
+            record: RunRecord {
+                timer_id: entry.timer_id.clone(),
+                name: entry.name.clone(),
+                instance: entry.instance,
+                started_at,
+                ended_at: entry.at,
+                outcome: RunOutcome::Finished,
+                overtime_seconds: None,
+                durations_seconds: Vec::new(),
+                actual_seconds: 0,
+                repeat_count: 0,
+                label: None,
+                note: None,
+                random_seed: None,
+                random_draw: None,
+            },
+            outcome: None,
+            finished_at: None,
+        });
+
+        let record = &mut run.record;
+        // Mantém o nome mais recente do timer
+        if entry.name.is_some() {
+            record.name = entry.name.clone();
+        }
+        record.actual_seconds = record.actual_seconds.max(entry.actual_seconds.unwrap_or(0));
+        record.repeat_count = record.repeat_count.max(entry.repeat_count);
+        record.random_seed = record.random_seed.or(entry.random_seed);
+        record.random_draw = record.random_draw.or(entry.random_draw);
+        if entry.label.is_some() || entry.note.is_some() {
+            record.label = entry.label.clone();
+            record.note = entry.note.clone();
+        }
+
+        match entry.event {
+            HistoryEventKind::Started | HistoryEventKind::Repeated => {
+                record.durations_seconds.extend(entry.planned_seconds);
+                continue;
+            }
+            HistoryEventKind::Paused | HistoryEventKind::Resumed => continue,
+            HistoryEventKind::Finished => {
+                run.finished_at.get_or_insert(entry.at);
+                run.outcome = Some(RunOutcome::Finished);
+            }
+            HistoryEventKind::Acknowledged | HistoryEventKind::Cancelled => {
+                let overtime = run.finished_at.map(|finished| ((entry.at - finished) / 1000).max(0) as u64);
+                record.overtime_seconds = overtime.or(record.overtime_seconds);
+                run.outcome = Some(match (entry.event, overtime) {
+                    (HistoryEventKind::Cancelled, None) => RunOutcome::Cancelled,
+                    _ => RunOutcome::Finished,
+                });
+            }
+        }
+        record.ended_at = entry.at;
+        // Sem o início (ex.: Pomodoro, ou já removido pela retenção) fica só a fase final
+        if record.durations_seconds.is_empty() {
+            record.durations_seconds.extend(entry.planned_seconds);
+        }
+    }
+
+    let mut runs: Vec<RunRecord> = runs
+        .into_values()
+        .filter_map(|run| {
+            let outcome = run.outcome?;
+            Some(RunRecord { outcome, ..run.record })
+        })
+        .collect();
+    runs.sort_by_key(|run| run.started_at);
+    runs
+}
+
+/// Execuções concluídas, da que terminou por último para a mais antiga.
+pub fn recent_runs(app: &AppHandle, limit: Option<usize>) -> Vec<RunRecord> {
+    let mut runs = collect_runs(&read_entries(app));
+    runs.sort_by_key(|run| std::cmp::Reverse(run.ended_at));
+    runs.truncate(limit.unwrap_or(DEFAULT_RUN_LIMIT));
+    runs
+}
+
+/// Entradas que passam pelos filtros, da mais recente para a mais antiga.
+pub fn query(app: &AppHandle, query: &HistoryQuery) -> Vec<HistoryEntry> {
+    read_entries(app)
+        .into_iter()
+        .rev()
+        .filter(|entry| query.from.is_none_or(|from| entry.at >= from))
+        .filter(|entry| query.to.is_none_or(|to| entry.at <= to))
+        .filter(|entry| query.timer_id.as_ref().is_none_or(|id| &entry.timer_id == id))
+        .filter(|entry| query.events.as_ref().is_none_or(|events| events.contains(&entry.event)))
+        .take(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT))
+        .collect()
+}
+
+/// Rótulos usados recentemente, sem repetição, do mais recente para o mais antigo.
+pub fn recent_labels(app: &AppHandle, limit: usize) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for entry in read_entries(app).into_iter().rev() {
+        if let Some(label) = entry.label {
+            if !labels.contains(&label) {
+                labels.push(label);
+                if labels.len() >= limit {
+                    break;
+                }
+            }
+        }
+    }
+    labels
+}
+
+/// Troca rótulo e anotação de todas as entradas de uma execução já registrada.
+/// Retorna quantas entradas mudaram.
+pub fn label_run(app: &AppHandle, timer_id: &str, run_started_at: i64, label: &RunLabel) -> Result<usize, String> {
+    let path = history_path(app).ok_or_else(|| "App data directory not available".to_string())?;
+    let _guard = FILE_LOCK.lock().unwrap();
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let mut changed = 0;
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| match serde_json::from_str::<HistoryEntry>(line) {
+            Ok(mut entry) if entry.timer_id == timer_id && entry.run_started_at == Some(run_started_at) => {
+                entry.label = label.label.clone();
+                entry.note = label.note.clone();
+                changed += 1;
+                serde_json::to_string(&entry).unwrap_or_else(|_| line.to_string())
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if changed == 0 {
+        return Ok(0);
+    }
+
+    let temp = path.with_extension("jsonl.tmp");
+    let mut data = lines.join("\n");
+    data.push('\n');
+    fs::write(&temp, data).map_err(|e| format!("Failed to write history: {}", e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("Failed to replace history: {}", e))?;
+    println!("[HISTORY] Labeled {} entries of {} run {}", changed, timer_id, run_started_at);
+    Ok(changed)
+}
+
+fn retention_days(app: &AppHandle) -> u64 {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|config| config.get("historyRetentionDays").and_then(|d| d.as_u64()))
+        .unwrap_or(DEFAULT_RETENTION_DAYS)
+}
+
+/// Linhas que continuam no arquivo e quantas saíram. Só entradas lidas com `at` antes
+/// de `cutoff` saem; linhas que não dá para ler ficam como estão (podem ser de uma
+/// versão mais nova do app).
+fn prune_lines(content: &str, cutoff: i64) -> (Vec<&str>, usize) {
+    let mut removed = 0;
+    let kept = content
+        .lines()
+        .filter(|line| match serde_json::from_str::<HistoryEntry>(line) {
+            Ok(entry) if entry.at < cutoff => {
+                removed += 1;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    (kept, removed)
+}
+
+/// Remove entradas mais antigas que a retenção configurada. Retorna quantas saíram.
+pub fn prune(app: &AppHandle) -> Result<usize, String> {
+    let days = retention_days(app);
+    if days == 0 {
+        return Ok(0);
+    }
+    let Some(path) = history_path(app) else { return Ok(0) };
+    let cutoff = Utc::now().timestamp_millis() - (days as i64) * 24 * 60 * 60 * 1000;
+
+    let _guard = FILE_LOCK.lock().unwrap();
+    let Ok(content) = fs::read_to_string(&path) else { return Ok(0) };
+    let (kept, removed) = prune_lines(&content, cutoff);
+    if removed == 0 {
+        return Ok(0);
+    }
+
+    // Escreve em um arquivo temporário e troca, para não perder o histórico se o app fechar no meio
+    let temp = path.with_extension("jsonl.tmp");
+    let mut data = kept.join("\n");
+    if !data.is_empty() {
+        data.push('\n');
+    }
+    fs::write(&temp, data).map_err(|e| format!("Failed to write history: {}", e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("Failed to replace history: {}", e))?;
+    println!("[HISTORY] Pruned {} entries older than {} days", removed, days);
+    Ok(removed)
+}
+
+/// Aplica a retenção ao iniciar, quando a configuração muda e periodicamente.
+pub fn start_history_maintenance(app: AppHandle) {
+    std::thread::spawn(move || {
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let mut last_prune: Option<Instant> = None;
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || last_prune.is_none_or(|at| at.elapsed() >= Duration::from_secs(PRUNE_INTERVAL_SECS))
+            {
+                if let Err(e) = prune(&app) {
+                    println!("[HISTORY] WARNING: {}", e);
+                }
+                last_prune = Some(Instant::now());
+            }
+            std::thread::sleep(Duration::from_secs(30));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(timer_id: &str, at: i64) -> String {
+        serde_json::json!({
+            "timerId": timer_id,
+            "name": null,
+            "event": "finished",
+            "at": at,
+            "runStartedAt": null,
+            "plannedSeconds": null,
+            "actualSeconds": null,
+            "repeatCount": 0,
+            "source": null,
+        })
+        .to_string()
+    }
+
+    fn event(event: HistoryEventKind, at: i64, planned: Option<u64>, actual: Option<u64>) -> HistoryEntry {
+        HistoryEntry {
+            timer_id: "tea".to_string(),
+            name: Some("Tea".to_string()),
+            event,
+            at,
+            run_started_at: Some(1_000),
+            planned_seconds: planned,
+            actual_seconds: actual,
+            repeat_count: 0,
+            source: None,
+            instance: None,
+            label: None,
+            note: None,
+            random_seed: None,
+            random_draw: None,
+        }
+    }
+
+    #[test]
+    fn acknowledged_overtime_is_a_finished_run_with_overtime() {
+        use HistoryEventKind::*;
+        let entries = [
+            event(Started, 1_000, Some(60), None),
+            event(Finished, 61_000, Some(60), Some(60)),
+            event(Acknowledged, 91_000, Some(60), Some(90)),
+        ];
+        let runs = collect_runs(&entries);
+        assert_eq!(runs.len(), 1);
+        let run = &runs[0];
+        assert_eq!(run.outcome, RunOutcome::Finished);
+        assert_eq!((run.started_at, run.ended_at), (1_000, 91_000));
+        assert_eq!(run.overtime_seconds, Some(30));
+        assert_eq!(run.durations_seconds, [60]);
+        assert_eq!(run.actual_seconds, 90);
+    }
+
+    #[test]
+    fn reset_counts_as_cancelled_unless_the_run_was_in_overtime() {
+        use HistoryEventKind::*;
+        let cases = [
+            (vec![event(Started, 1_000, Some(60), None), event(Cancelled, 20_000, Some(60), Some(19))], RunOutcome::Cancelled, None),
+            (
+                vec![
+                    event(Started, 1_000, Some(60), None),
+                    event(Finished, 61_000, Some(60), Some(60)),
+                    event(Cancelled, 66_000, Some(60), Some(65)),
+                ],
+                RunOutcome::Finished,
+                Some(5),
+            ),
+        ];
+        for (entries, outcome, overtime) in cases {
+            let runs = collect_runs(&entries);
+            assert_eq!(runs.len(), 1, "{:?}", entries);
+            assert_eq!(runs[0].outcome, outcome, "{:?}", entries);
+            assert_eq!(runs[0].overtime_seconds, overtime, "{:?}", entries);
+        }
+    }
+
+    #[test]
+    fn repeats_keep_each_duration_and_the_random_seed() {
+        use HistoryEventKind::*;
+        let mut started = event(Started, 1_000, Some(60), None);
+        started.random_seed = Some(42);
+        started.random_draw = Some(60);
+        let mut repeated = event(Repeated, 61_000, Some(45), Some(60));
+        repeated.repeat_count = 1;
+        let mut finished = event(Finished, 106_000, Some(45), Some(105));
+        finished.repeat_count = 1;
+        finished.label = Some("drill".to_string());
+
+        let runs = collect_runs(&[finished, started, repeated]);
+        assert_eq!(runs[0].durations_seconds, [60, 45]);
+        assert_eq!(runs[0].repeat_count, 1);
+        assert_eq!((runs[0].random_seed, runs[0].random_draw), (Some(42), Some(60)));
+        assert_eq!(runs[0].label.as_deref(), Some("drill"));
+    }
+
+    #[test]
+    fn open_runs_are_left_out_and_instances_kept_apart() {
+        use HistoryEventKind::*;
+        let mut stacked = event(Started, 1_000, Some(30), None);
+        stacked.instance = Some(1);
+        let mut stacked_end = event(Finished, 31_000, Some(30), Some(30));
+        stacked_end.instance = Some(1);
+        let entries = [
+            event(Started, 1_000, Some(60), None),
+            event(Paused, 10_000, Some(60), Some(9)),
+            stacked,
+            stacked_end,
+        ];
+        let runs = collect_runs(&entries);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].instance, Some(1));
+    }
+
+    #[test]
+    fn run_without_a_start_entry_uses_the_final_phase_duration() {
+        // Focos do Pomodoro só registram o fim
+        let runs = collect_runs(&[event(HistoryEventKind::Finished, 1_501_000, Some(1500), Some(1500))]);
+        assert_eq!(runs[0].durations_seconds, [1500]);
+        assert_eq!(runs[0].started_at, 1_000);
+    }
+
+    #[test]
+    fn global_trigger_applies_once_per_cancelled_timer() {
+        note_trigger_all(TriggerSource::Keybind);
+        assert_eq!(take_trigger("global-a", HistoryEventKind::Started), None);
+        assert_eq!(take_trigger("global-a", HistoryEventKind::Cancelled), Some(TriggerSource::Keybind));
+        assert_eq!(take_trigger("global-a", HistoryEventKind::Cancelled), None);
+        assert_eq!(take_trigger("global-b", HistoryEventKind::Cancelled), Some(TriggerSource::Keybind));
+
+        // Anotação do próprio timer vale para qualquer evento
+        note_trigger("global-a", TriggerSource::Tray);
+        assert_eq!(take_trigger("global-a", HistoryEventKind::Started), Some(TriggerSource::Tray));
+    }
+
+    #[test]
+    fn prune_drops_only_entries_before_cutoff() {
+        let content = [line("old", 999), line("edge", 1000), line("new", 2000)].join("\n");
+        let (kept, removed) = prune_lines(&content, 1000);
+        assert_eq!(removed, 1);
+        assert_eq!(kept, [line("edge", 1000), line("new", 2000)]);
+    }
+
+    #[test]
+    fn prune_keeps_lines_it_cannot_parse() {
+        let content = [
+            line("old", 10).as_str(),
+            "{\"timerId\":\"cut",
+            "{\"unknown\":true}",
+            line("new", 2000).as_str(),
+        ]
+        .join("\n");
+        let (kept, removed) = prune_lines(&content, 1000);
+        assert_eq!(removed, 1);
+        let new = line("new", 2000);
+        assert_eq!(kept, ["{\"timerId\":\"cut", "{\"unknown\":true}", new.as_str()]);
+    }
+}
@@ -0,0 +1,598 @@
+use crate::engine::{TimerEvent, TimerEventKind};
+use crate::history::HistoryEntry;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Condvar, LazyLock, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Tempo máximo de um hook quando `timeoutSeconds` não está configurado
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+/// Hooks rodando ao mesmo tempo quando `eventHookConcurrency` não está configurado
+const DEFAULT_CONCURRENCY: usize = 4;
+/// Quanto um hook espera por uma vaga antes de ser descartado
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Linhas de saída guardadas no estado de cada hook
+const MAX_OUTPUT_LINES: usize = 20;
+/// Quanto esperar pelos pipes depois que o processo termina: um filho em segundo plano
+/// que herdou o stdout pode mantê-los abertos e não deve prender a vaga do hook
+const OUTPUT_GRACE: Duration = Duration::from_secs(2);
+
+/// Comando executado quando um timer gera um dos eventos escolhidos.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventHook {
+    id: String,
+    name: Option<String>,
+    /// Eventos que disparam o hook (`started`, `phase`, `finished`...); vazio = todos
+    #[serde(default)]
+    events: Vec<TimerEventKind>,
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+    working_dir: Option<String>,
+    timeout_seconds: Option<u64>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Resultado da última execução de um hook.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventHookStatus {
+    pub id: String,
+    pub runs: u32,
+    pub running: u32,
+    pub last_run_at: Option<i64>,
+    pub last_duration_ms: Option<u64>,
+    pub last_exit_code: Option<i32>,
+    pub last_error: Option<String>,
+    /// Últimas linhas de stdout/stderr
+    pub last_output: Vec<String>,
+}
+
+static STATUS: LazyLock<Mutex<HashMap<String, EventHookStatus>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+/// Vagas em uso pelos hooks em execução
+static SLOTS: Slots = Slots::new();
+
+/// Contador de hooks em execução, com espera por uma vaga livre.
+struct Slots {
+    running: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl Slots {
+    const fn new() -> Self {
+        Slots {
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Ocupa uma vaga, esperando no máximo `timeout`; false se nenhuma abriu a tempo.
+    fn acquire(&self, limit: usize, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut running = self.running.lock().unwrap();
+        while *running >= limit {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            running = self.freed.wait_timeout(running, deadline - now).unwrap().0;
+        }
+        *running += 1;
+        true
+    }
+
+    fn release(&self) {
+        *self.running.lock().unwrap() -= 1;
+        self.freed.notify_one();
+    }
+}
+
+fn parse_hooks(list: Option<&Value>) -> Vec<EventHook> {
+    list.and_then(|h| h.as_array())
+        .map(|hooks| {
+            hooks
+                .iter()
+                .filter_map(|hook| match serde_json::from_value::<EventHook>(hook.clone()) {
+                    Ok(hook) => Some(hook),
+                    Err(e) => {
+                        println!("[HOOK] WARNING: Ignoring invalid hook: {}", e);
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Valida `eventHooks` (globais e de cada timer) antes de salvar a configuração.
+pub fn validate_event_hooks(config: &Value) -> Result<(), String> {
+    let icons = config.get("icons").and_then(|i| i.as_array());
+    let lists = std::iter::once(config.get("eventHooks"))
+        .chain(icons.into_iter().flatten().map(|icon| icon.get("eventHooks")))
+        .flatten();
+    for list in lists {
+        let Some(hooks) = list.as_array() else {
+            return Err("eventHooks must be a list".to_string());
+        };
+        for hook in hooks {
+            let hook: EventHook = serde_json::from_value(hook.clone()).map_err(|e| format!("Invalid hook: {}", e))?;
+            if hook.command.trim().is_empty() {
+                return Err(format!("Hook {} has no command", hook.id));
+            }
+        }
+    }
+    if config.get("eventHookConcurrency").is_some_and(|c| !c.is_null() && c.as_u64().is_none_or(|c| c == 0)) {
+        return Err("eventHookConcurrency must be a positive number".to_string());
+    }
+    Ok(())
+}
+
+/// Hooks globais (`eventHooks`) seguidos dos hooks do próprio timer (`icons[].eventHooks`).
+fn hooks_for(config: &Value, timer_id: &str) -> Vec<EventHook> {
+    let icon = config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .and_then(|icons| {
+            icons
+                .iter()
+                .find(|icon| icon.get("id").and_then(|id| id.as_str()) == Some(timer_id))
+        });
+    let mut hooks = parse_hooks(config.get("eventHooks"));
+    hooks.extend(parse_hooks(icon.and_then(|i| i.get("eventHooks"))));
+    hooks
+}
+
+fn event_name(kind: TimerEventKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_default()
+}
+
+/// Dados do evento enviados como JSON no stdin do hook.
+pub(crate) fn event_payload(config: &Value, event: &TimerEvent, entry: Option<&HistoryEntry>) -> Value {
+    let name = entry.and_then(|e| e.name.clone()).or_else(|| {
+        config
+            .get("icons")
+            .and_then(|i| i.as_array())
+            .and_then(|icons| {
+                icons
+                    .iter()
+                    .find(|icon| icon.get("id").and_then(|id| id.as_str()) == Some(event.timer_id.as_str()))
+            })
+            .and_then(|icon| icon.get("name"))
+            .and_then(|n| n.as_str())
+            .map(|n| n.to_string())
+    });
+    json!({
+        "timerId": event.timer_id,
+        "name": name,
+        "event": event_name(event.event),
+        "at": entry.map(|e| e.at).unwrap_or_else(|| Utc::now().timestamp_millis()),
+        "instance": event.instance.unwrap_or(0),
+        "phase": event.phase,
+        "remaining": event.remaining,
+        "runStartedAt": entry.and_then(|e| e.run_started_at),
+        "plannedSeconds": entry.and_then(|e| e.planned_seconds),
+        "actualSeconds": entry.and_then(|e| e.actual_seconds),
+        "label": entry.and_then(|e| e.label.clone()),
+        "note": entry.and_then(|e| e.note.clone()),
+        "source": entry.and_then(|e| e.source),
+        "detail": event.detail,
+    })
+}
+
+/// Variáveis `TIMER_*` com os campos simples do evento
+fn event_env(payload: &Value) -> Vec<(String, String)> {
+    let fields = [
+        ("TIMER_ID", "timerId"),
+        ("TIMER_NAME", "name"),
+        ("TIMER_EVENT", "event"),
+        ("TIMER_EVENT_AT", "at"),
+        ("TIMER_INSTANCE", "instance"),
+        ("TIMER_PHASE", "phase"),
+        ("TIMER_REMAINING", "remaining"),
+        ("TIMER_RUN_STARTED_AT", "runStartedAt"),
+        ("TIMER_ACTUAL_SECONDS", "actualSeconds"),
+        ("TIMER_LABEL", "label"),
+        ("TIMER_SOURCE", "source"),
+    ];
+    fields
+        .iter()
+        .filter_map(|(var, field)| {
+            let value = match payload.get(*field)? {
+                Value::Null => return None,
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            Some((var.to_string(), value))
+        })
+        .collect()
+}
+
+fn update_status(id: &str, update: impl FnOnce(&mut EventHookStatus)) {
+    let mut status = STATUS.lock().unwrap();
+    let entry = status.entry(id.to_string()).or_insert_with(|| EventHookStatus {
+        id: id.to_string(),
+        ..Default::default()
+    });
+    update(entry);
+}
+
+/// Leitura de um pipe em andamento, com as últimas linhas lidas até agora.
+struct Capture {
+    lines: Arc<Mutex<Vec<String>>>,
+    reader: JoinHandle<()>,
+}
+
+/// Lê um pipe linha a linha para o log numa thread própria.
+fn capture<R: Read + Send + 'static>(pipe: Option<R>, label: String) -> Capture {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let shared = lines.clone();
+    let reader = std::thread::spawn(move || {
+        if let Some(pipe) = pipe {
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                println!("[HOOK] {}: {}", label, line);
+                let mut lines = shared.lock().unwrap();
+                lines.push(line);
+                if lines.len() > MAX_OUTPUT_LINES {
+                    lines.remove(0);
+                }
+            }
+        }
+    });
+    Capture { lines, reader }
+}
+
+/// Espera as leituras terminarem por no máximo `OUTPUT_GRACE` e devolve o que foi lido;
+/// leituras ainda presas seguem sozinhas até o pipe fechar.
+fn collect_output(name: &str, captures: &[Capture]) -> Vec<String> {
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    while captures.iter().any(|c| !c.reader.is_finished()) {
+        if Instant::now() >= deadline {
+            println!("[HOOK] WARNING: {} left its output open, not waiting for it", name);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    captures
+        .iter()
+        .flat_map(|c| c.lines.lock().unwrap().clone())
+        .collect()
+}
+
+/// Executa o hook e espera o fim ou o timeout. Erros só vão para o log e o estado.
+fn run_hook(hook: &EventHook, payload: &Value) {
+    let name = hook.name.clone().unwrap_or_else(|| hook.id.clone());
+    let mut command = Command::new(&hook.command);
+    command
+        .args(&hook.args)
+        .envs(event_env(payload))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(dir) = hook.working_dir.as_deref().filter(|d| !d.is_empty()) {
+        command.current_dir(dir);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW: sem janela de console piscando a cada evento
+        command.creation_flags(0x0800_0000);
+    }
+
+    let started = Instant::now();
+    update_status(&hook.id, |s| {
+        s.running += 1;
+        s.last_run_at = Some(Utc::now().timestamp_millis());
+    });
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            println!("[HOOK] WARNING: Failed to start {} ({}): {}", name, hook.command, e);
+            update_status(&hook.id, |s| {
+                s.running -= 1;
+                s.runs += 1;
+                s.last_exit_code = None;
+                s.last_error = Some(format!("Failed to start: {}", e));
+                s.last_output = Vec::new();
+            });
+            return;
+        }
+    };
+
+    // Leitura e escrita em threads próprias: um hook que enche o stdout antes de ler o
+    // stdin (ou que nunca o lê) não pode travar esta thread fora do alcance do timeout
+    let stdout = capture(child.stdout.take(), format!("{} stdout", name));
+    let stderr = capture(child.stderr.take(), format!("{} stderr", name));
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = payload.to_string();
+        std::thread::spawn(move || {
+            // Hooks que não leem o stdin fecham o pipe; isso não é erro
+            let _ = writeln!(stdin, "{}", input);
+        });
+    }
+
+    let timeout = Duration::from_secs(hook.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECS).max(1));
+    let (exit_code, error) = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let error = (!status.success()).then(|| format!("Exited with {}", status));
+                break (status.code(), error);
+            }
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (None, Some(format!("Timed out after {}s", timeout.as_secs())));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => break (None, Some(format!("Failed to wait: {}", e))),
+        }
+    };
+
+    let output = collect_output(&name, &[stdout, stderr]);
+    let elapsed = started.elapsed().as_millis() as u64;
+    match &error {
+        Some(e) => println!("[HOOK] WARNING: {} failed after {}ms: {}", name, elapsed, e),
+        None => println!("[HOOK] {} finished in {}ms", name, elapsed),
+    }
+    update_status(&hook.id, |s| {
+        s.running -= 1;
+        s.runs += 1;
+        s.last_duration_ms = Some(elapsed);
+        s.last_exit_code = exit_code;
+        s.last_error = error;
+        s.last_output = output;
+    });
+}
+
+/// Dispara, em threads próprias, os hooks que escutam este evento. Nunca bloqueia nem
+/// falha: o timer segue igual mesmo que o comando não exista, trave ou retorne erro.
+pub fn on_timer_event(config: &Value, event: &TimerEvent, entry: Option<&HistoryEntry>) {
+    let hooks = listening_hooks(config, event);
+    if hooks.is_empty() {
+        return;
+    }
+
+    let limit = concurrency_limit(config);
+    let payload = event_payload(config, event, entry);
+    for hook in hooks {
+        spawn_hook(hook, payload.clone(), limit);
+    }
+}
+
+/// Hooks ativos do timer que escutam o evento.
+fn listening_hooks(config: &Value, event: &TimerEvent) -> Vec<EventHook> {
+    hooks_for(config, &event.timer_id)
+        .into_iter()
+        .filter(|hook| hook.enabled && !hook.command.trim().is_empty())
+        .filter(|hook| hook.events.is_empty() || hook.events.contains(&event.event))
+        .collect()
+}
+
+fn concurrency_limit(config: &Value) -> usize {
+    config
+        .get("eventHookConcurrency")
+        .and_then(|c| c.as_u64())
+        .map(|c| c.max(1) as usize)
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
+
+/// Roda o hook numa thread própria assim que houver vaga no limite de hooks simultâneos.
+fn spawn_hook(hook: EventHook, payload: Value, limit: usize) {
+    std::thread::spawn(move || {
+        if !SLOTS.acquire(limit, QUEUE_TIMEOUT) {
+            println!("[HOOK] WARNING: Dropping {} for {}: too many hooks running", hook.id, payload["event"]);
+            update_status(&hook.id, |s| s.last_error = Some("Dropped: too many hooks running".to_string()));
+            return;
+        }
+        run_hook(&hook, &payload);
+        SLOTS.release();
+    });
+}
+
+/// Estado de cada hook já executado nesta sessão.
+pub fn hook_status() -> Vec<EventHookStatus> {
+    STATUS.lock().unwrap().values().cloned().collect()
+}
+
+/// Roda um hook uma vez com um evento de exemplo, para testar a configuração.
+pub fn test_hook(config: &Value, hook: &Value) -> Result<(), String> {
+    let hook: EventHook = serde_json::from_value(hook.clone()).map_err(|e| format!("Invalid hook: {}", e))?;
+    let event = TimerEvent {
+        timer_id: "test".to_string(),
+        event: hook.events.first().copied().unwrap_or(TimerEventKind::Finished),
+        phase: Some(1),
+        remaining: Some(0),
+        instance: None,
+        seed: None,
+        draw: None,
+        detail: None,
+    };
+    let payload = event_payload(config, &event, None);
+    // O teste ocupa uma vaga como qualquer execução
+    spawn_hook(hook, payload, concurrency_limit(config));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{HistoryEventKind, TriggerSource};
+
+    fn event(timer_id: &str, kind: TimerEventKind) -> TimerEvent {
+        TimerEvent {
+            timer_id: timer_id.to_string(),
+            event: kind,
+            phase: None,
+            remaining: Some(0),
+            instance: None,
+            seed: None,
+            draw: None,
+            detail: None,
+        }
+    }
+
+    fn config() -> Value {
+        json!({
+            "eventHooks": [
+                { "id": "all", "command": "notify" },
+                { "id": "broken", "events": ["exploded"], "command": "x" },
+                { "id": "done", "events": ["finished"], "command": "log" },
+                { "id": "off", "command": "log", "enabled": false }
+            ],
+            "icons": [
+                { "id": "tea", "name": "Tea", "eventHooks": [
+                    { "id": "tea-start", "events": ["started", "phase"], "command": "lamp" },
+                    { "command": "no id" },
+                    { "id": "blank", "command": "  " }
+                ] },
+                { "id": "egg", "eventHooks": [{ "id": "egg-hook", "command": "egg" }] }
+            ]
+        })
+    }
+
+    fn ids(hooks: &[EventHook]) -> Vec<&str> {
+        hooks.iter().map(|h| h.id.as_str()).collect()
+    }
+
+    #[test]
+    fn global_hooks_come_before_the_timer_hooks_and_invalid_ones_are_skipped() {
+        let config = config();
+        assert_eq!(ids(&hooks_for(&config, "tea")), vec!["all", "done", "off", "tea-start", "blank"]);
+        assert_eq!(ids(&hooks_for(&config, "missing")), vec!["all", "done", "off"]);
+        assert_eq!(ids(&hooks_for(&json!({}), "tea")), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn hooks_listen_to_their_events_and_empty_means_all() {
+        let config = config();
+        let cases = [
+            ("tea", TimerEventKind::Started, vec!["all", "tea-start"]),
+            ("tea", TimerEventKind::Phase, vec!["all", "tea-start"]),
+            ("tea", TimerEventKind::Finished, vec!["all", "done"]),
+            ("tea", TimerEventKind::Paused, vec!["all"]),
+            ("egg", TimerEventKind::Cancelled, vec!["all", "egg-hook"]),
+        ];
+        for (timer_id, kind, expected) in cases {
+            assert_eq!(ids(&listening_hooks(&config, &event(timer_id, kind))), expected, "{} {:?}", timer_id, kind);
+        }
+    }
+
+    #[test]
+    fn env_skips_null_fields_and_stringifies_numbers() {
+        let entry = HistoryEntry {
+            timer_id: "tea".to_string(),
+            name: None,
+            event: HistoryEventKind::Finished,
+            at: 1_700_000_000_000,
+            run_started_at: Some(1_699_999_700_000),
+            planned_seconds: Some(300),
+            actual_seconds: Some(300),
+            repeat_count: 0,
+            source: Some(TriggerSource::LogWatch),
+            instance: None,
+            label: None,
+            note: None,
+            random_seed: None,
+            random_draw: None,
+        };
+        let payload = event_payload(&config(), &event("tea", TimerEventKind::Finished), Some(&entry));
+        let env: HashMap<String, String> = event_env(&payload).into_iter().collect();
+
+        assert_eq!(env["TIMER_ID"], "tea");
+        // Sem nome no histórico, usa o da configuração
+        assert_eq!(env["TIMER_NAME"], "Tea");
+        assert_eq!(env["TIMER_EVENT"], "finished");
+        assert_eq!(env["TIMER_EVENT_AT"], "1700000000000");
+        assert_eq!(env["TIMER_INSTANCE"], "0");
+        assert_eq!(env["TIMER_REMAINING"], "0");
+        assert_eq!(env["TIMER_ACTUAL_SECONDS"], "300");
+        assert_eq!(env["TIMER_SOURCE"], "logWatch");
+        assert!(!env.contains_key("TIMER_PHASE"));
+        assert!(!env.contains_key("TIMER_LABEL"));
+    }
+
+    #[test]
+    fn slots_wait_for_a_free_one_and_give_up_after_the_timeout() {
+        let slots = Arc::new(Slots::new());
+        assert!(slots.acquire(2, Duration::ZERO));
+        assert!(slots.acquire(2, Duration::ZERO));
+        let started = Instant::now();
+        assert!(!slots.acquire(2, Duration::from_millis(50)));
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        // Uma vaga liberada por outra thread destrava quem espera
+        let releaser = {
+            let slots = slots.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                slots.release();
+            })
+        };
+        assert!(slots.acquire(2, Duration::from_secs(5)));
+        releaser.join().unwrap();
+        assert_eq!(*slots.running.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn a_missing_command_records_the_error_without_exit_code() {
+        let hook: EventHook = serde_json::from_value(json!({
+            "id": "missing-command-test",
+            "command": "overlay-timer-no-such-command-xyz"
+        }))
+        .unwrap();
+        run_hook(&hook, &json!({ "event": "finished" }));
+
+        let status = STATUS.lock().unwrap()["missing-command-test"].clone();
+        assert_eq!((status.runs, status.running), (1, 0));
+        assert_eq!(status.last_exit_code, None);
+        assert!(status.last_error.unwrap().starts_with("Failed to start"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_hook_gets_the_payload_and_its_output_is_kept() {
+        let hook: EventHook = serde_json::from_value(json!({
+            "id": "echo-test",
+            "command": "sh",
+            "args": ["-c", "read payload; echo \"$TIMER_EVENT $payload\"; exit 3"]
+        }))
+        .unwrap();
+        run_hook(&hook, &json!({ "event": "started" }));
+
+        let status = STATUS.lock().unwrap()["echo-test"].clone();
+        assert_eq!(status.last_exit_code, Some(3));
+        assert_eq!(status.last_output, vec![r#"started {"event":"started"}"#]);
+        assert!(status.last_error.is_some());
+    }
+
+    #[test]
+    fn validation_rejects_malformed_hooks() {
+        assert!(validate_event_hooks(&json!({})).is_ok());
+        assert!(validate_event_hooks(&json!({ "eventHooks": [{ "id": "a", "command": "x" }] })).is_ok());
+
+        let broken = [
+            json!({ "eventHooks": [{ "id": "a", "command": "" }] }),
+            json!({ "eventHooks": [{ "id": "a", "command": "x", "events": ["exploded"] }] }),
+            json!({ "eventHooks": { "id": "a" } }),
+            json!({ "icons": [{ "id": "t", "eventHooks": [{ "command": "x" }] }] }),
+            json!({ "eventHookConcurrency": 0 }),
+        ];
+        for config in broken {
+            assert!(validate_event_hooks(&config).is_err(), "{}", config);
+        }
+    }
+}
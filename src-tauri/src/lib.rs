@@ -1,4 +1,26 @@
+mod alarms;
+mod chains;
+mod charges;
+mod chessclock;
+mod cli;
+mod clock;
 mod commands;
+mod engine;
+mod export;
+mod goals;
+mod history;
+mod hooks;
+mod logwatch;
+mod pomodoro;
+mod processes;
+mod profiles;
+mod quick;
+mod random;
+mod recurrence;
+mod resets;
+mod schedules;
+mod stats;
+mod webhooks;
 #[cfg(windows)]
 mod keyboard_hook;
 
@@ -12,10 +34,53 @@ use commands::{
     set_overlay_click_through,
     emit_position_changed,
     quick_create_timer,
+    get_quick_timers,
+    keep_quick_timer,
     create_timer_from_preset,
     start_timer,
+    start_timer_group,
+    pause_timer_group,
+    reset_timer_group,
+    run_timer_group_action,
+    report_timer_event,
+    snooze_alarm,
+    dismiss_alarm,
+    get_upcoming_alarms,
+    get_timer_states,
+    preview_schedule,
+    lap_timer,
+    get_laps,
+    acknowledge_timer,
+    adjust_timer,
+    adjust_active_timer,
+    get_run_history,
+    query_history,
+    get_stats,
+    get_weekly_summary,
+    export_history,
+    label_run,
+    get_recent_labels,
+    get_goal_progress,
+    get_event_hook_status,
+    test_event_hook,
+    get_webhook_status,
+    test_webhook,
+    use_charge,
+    reset_charges,
+    toggle_pomodoro,
+    skip_pomodoro_phase,
+    restart_pomodoro_cycle,
+    reset_pomodoro_day,
+    pass_turn,
+    toggle_chess_clock,
+    reset_chess_clock,
+    roll_random_duration,
+    reseed_random_timer,
+    get_log_watch_status,
+    get_process_rule_status,
     open_overlay_devtools,
 };
+use history::TriggerSource;
 
 #[cfg(windows)]
 use commands::{
@@ -35,6 +100,23 @@ use serde_json;
 use serde_json::json;
 use std::fs;
 
+/// Durações do menu "Quick Create Timer" do tray: (sufixo do id, rótulo, segundos).
+const QUICK_DURATIONS: [(&str, &str, u64); 7] = [
+    ("30s", "30 seconds", 30),
+    ("1min", "1 minute", 60),
+    ("2min", "2 minutes", 120),
+    ("5min", "5 minutes", 300),
+    ("10min", "10 minutes", 600),
+    ("15min", "15 minutes", 900),
+    ("30min", "30 minutes", 1800),
+];
+
+/// Subcomandos de linha de comando (`start`, `quick`, `reset-all`...) repassados à instância
+/// em execução. Retorna o código de saída, ou `None` para abrir o app normalmente.
+pub fn run_cli() -> Option<i32> {
+    cli::run_from_args(std::env::args().skip(1).collect())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -80,6 +162,11 @@ pub fn run() {
                 .and_then(|p| p.as_array())
                 .cloned()
                 .unwrap_or_else(Vec::new);
+            let groups_array = config_json
+                .get("timerGroups")
+                .and_then(|g| g.as_array())
+                .cloned()
+                .unwrap_or_else(Vec::new);
 
             let reset_specific_items: Vec<_> = icons_array
                 .iter()
@@ -115,6 +202,40 @@ pub fn run() {
                 })
                 .collect();
             
+            let group_menus: Vec<_> = groups_array
+                .iter()
+                .filter_map(|group| {
+                    let id = group.get("id")?.as_str()?;
+                    let name = group.get("name").and_then(|n| n.as_str()).unwrap_or("Group");
+                    let start_item = MenuItemBuilder::with_id(format!("group_start_{}", id), "Start All").build(app).ok()?;
+                    let pause_item = MenuItemBuilder::with_id(format!("group_pause_{}", id), "Pause All").build(app).ok()?;
+                    let reset_item = MenuItemBuilder::with_id(format!("group_reset_{}", id), "Reset All").build(app).ok()?;
+                    SubmenuBuilder::with_id(app, format!("group_{}", id), name)
+                        .items(&[&start_item, &pause_item, &reset_item])
+                        .build()
+                        .ok()
+                })
+                .collect();
+            let pomodoro_menus: Vec<_> = icons_array
+                .iter()
+                .filter(|icon| icon.get("timerType").and_then(|t| t.as_str()) == Some("pomodoro"))
+                .filter_map(|icon| {
+                    let id = icon.get("id")?.as_str()?;
+                    let name = icon.get("name").and_then(|n| n.as_str()).unwrap_or("Pomodoro");
+                    let toggle_item = MenuItemBuilder::with_id(format!("pomodoro_toggle_{}", id), "Start/Pause").build(app).ok()?;
+                    let skip_item = MenuItemBuilder::with_id(format!("pomodoro_skip_{}", id), "Skip Phase").build(app).ok()?;
+                    let restart_item = MenuItemBuilder::with_id(format!("pomodoro_restart_{}", id), "Restart Cycle").build(app).ok()?;
+                    let reset_day_item = MenuItemBuilder::with_id(format!("pomodoro_reset_day_{}", id), "Reset Day Counter").build(app).ok()?;
+                    SubmenuBuilder::with_id(app, format!("pomodoro_{}", id), format!("🍅 {}", name))
+                        .items(&[&toggle_item, &skip_item, &restart_item, &reset_day_item])
+                        .build()
+                        .ok()
+                })
+                .collect();
+            let pomodoro_menus_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = pomodoro_menus.iter().map(|item| item as &dyn tauri::menu::IsMenuItem<_>).collect();
+
+            let group_menus_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = group_menus.iter().map(|item| item as &dyn tauri::menu::IsMenuItem<_>).collect();
+
             let reset_specific_items_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = reset_specific_items.iter().map(|item| item as &dyn tauri::menu::IsMenuItem<_>).collect();
             let reset_specific_item: Box<dyn tauri::menu::IsMenuItem<_>> = if !reset_specific_items.is_empty() {
                 Box::new(SubmenuBuilder::with_id(app, "reset_specific", "Reset Specific Timer")
@@ -142,21 +263,36 @@ pub fn run() {
                 Box::new(MenuItemBuilder::with_id("create_from_preset_disabled", "Create Timer from Preset (No presets)").build(app)?)
             };
             
-            let quick_create_items = vec![
-                MenuItemBuilder::with_id("quick_create_30s", "30 seconds").build(app)?,
-                MenuItemBuilder::with_id("quick_create_1min", "1 minute").build(app)?,
-                MenuItemBuilder::with_id("quick_create_2min", "2 minutes").build(app)?,
-                MenuItemBuilder::with_id("quick_create_5min", "5 minutes").build(app)?,
-                MenuItemBuilder::with_id("quick_create_10min", "10 minutes").build(app)?,
-                MenuItemBuilder::with_id("quick_create_15min", "15 minutes").build(app)?,
-                MenuItemBuilder::with_id("quick_create_30min", "30 minutes").build(app)?,
-            ];
+            let quick_create_items = QUICK_DURATIONS
+                .iter()
+                .map(|(key, label, _)| MenuItemBuilder::with_id(format!("quick_create_{}", key), *label).build(app))
+                .collect::<Result<Vec<_>, _>>()?;
+            // Mesmas durações, mas só na memória: somem do overlay ao terminar
+            let quick_ephemeral_items = QUICK_DURATIONS
+                .iter()
+                .map(|(key, label, _)| MenuItemBuilder::with_id(format!("quick_ephemeral_{}", key), *label).build(app))
+                .collect::<Result<Vec<_>, _>>()?;
+            let quick_ephemeral_items_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = quick_ephemeral_items.iter().map(|item| item as &dyn tauri::menu::IsMenuItem<_>).collect();
+            let quick_ephemeral_menu = SubmenuBuilder::with_id(app, "quick_ephemeral", "Run Without Saving")
+                .items(&quick_ephemeral_items_refs)
+                .build()?;
             
+            // Preenchido com os timers rápidos em execução, que podem ser mantidos na configuração
+            let quick_keep_menu = SubmenuBuilder::with_id(app, "quick_keep", "Keep Running Quick Timer").build()?;
+            quick::set_tray_menu(app.handle(), quick_keep_menu.clone());
+
             let quick_create_items_refs: Vec<&dyn tauri::menu::IsMenuItem<_>> = quick_create_items.iter().map(|item| item as &dyn tauri::menu::IsMenuItem<_>).collect();
             let quick_create_item = SubmenuBuilder::with_id(app, "quick_create", "Quick Create Timer")
                 .items(&quick_create_items_refs)
+                .separator()
+                .items(&[&quick_ephemeral_menu])
+                .items(&[&quick_keep_menu])
                 .build()?;
             
+            // Preenchido e atualizado pelo scheduler de alarmes
+            let upcoming_alarms_menu = SubmenuBuilder::with_id(app, "upcoming_alarms", "Upcoming Alarms").build()?;
+            alarms::set_tray_menu(upcoming_alarms_menu.clone());
+
             let quit_item = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
             
             let menu = MenuBuilder::new(app)
@@ -164,6 +300,9 @@ pub fn run() {
                 .items(&[&reset_timers_item])
                 .items(&[start_timer_menu.as_ref()])
                 .items(&[reset_specific_item.as_ref()])
+                .items(&group_menus_refs)
+                .items(&pomodoro_menus_refs)
+                .items(&[&upcoming_alarms_menu])
                 .separator()
                 .items(&[presets_menu.as_ref()])
                 .items(&[&quick_create_item])
@@ -265,6 +404,7 @@ pub fn run() {
                     if event_id.starts_with("start_timer_") {
                         let timer_id = event_id.strip_prefix("start_timer_").unwrap_or("");
                         println!("[TRAY] Start timer clicked: {}", timer_id);
+                        history::note_trigger(timer_id, TriggerSource::Tray);
                         if let Some(overlay_window) = app.get_webview_window("overlay") {
                             let _ = overlay_window.emit("start-specific-timer", timer_id);
                         }
@@ -275,12 +415,78 @@ pub fn run() {
                     if event_id.starts_with("reset_timer_") {
                         let timer_id = event_id.strip_prefix("reset_timer_").unwrap_or("");
                         println!("[TRAY] Reset specific timer clicked: {}", timer_id);
+                        history::note_trigger(timer_id, TriggerSource::Tray);
                         if let Some(overlay_window) = app.get_webview_window("overlay") {
                             let _ = overlay_window.emit("reset-specific-timer", timer_id);
                         }
                         return;
                     }
 
+                    // Handle timer group actions
+                    for (prefix, action) in [("group_start_", "start"), ("group_pause_", "pause"), ("group_reset_", "reset")] {
+                        if let Some(group_id) = event_id.strip_prefix(prefix) {
+                            println!("[TRAY] Group {} clicked: {}", action, group_id);
+                            let app_handle_group = app.clone();
+                            let group_id = group_id.to_string();
+                            std::thread::spawn(move || {
+                                if let Err(e) = run_timer_group_action(&app_handle_group, &group_id, action, TriggerSource::Tray) {
+                                    println!("[TRAY] ERROR: Failed to {} group {}: {}", action, group_id, e);
+                                }
+                            });
+                            return;
+                        }
+                    }
+
+                    // Handle Pomodoro actions
+                    type PomodoroAction = fn(&tauri::AppHandle, &str) -> Result<pomodoro::PomodoroSnapshot, String>;
+                    let pomodoro_actions: [(&str, PomodoroAction); 4] = [
+                        ("pomodoro_toggle_", pomodoro::toggle_pomodoro),
+                        ("pomodoro_skip_", pomodoro::skip_phase),
+                        ("pomodoro_restart_", pomodoro::restart_cycle),
+                        ("pomodoro_reset_day_", pomodoro::reset_day),
+                    ];
+                    for (prefix, action) in pomodoro_actions {
+                        if let Some(timer_id) = event_id.strip_prefix(prefix) {
+                            println!("[TRAY] Pomodoro {} clicked: {}", prefix.trim_end_matches('_'), timer_id);
+                            if let Err(e) = action(app, timer_id) {
+                                println!("[TRAY] ERROR: Pomodoro action failed for {}: {}", timer_id, e);
+                            }
+                            return;
+                        }
+                    }
+
+                    if let Some(timer_id) = event_id.strip_prefix("quick_keep_") {
+                        println!("[TRAY] Keep quick timer clicked: {}", timer_id);
+                        let app_handle_keep = app.clone();
+                        let timer_id = timer_id.to_string();
+                        std::thread::spawn(move || {
+                            if let Err(e) = keep_quick_timer(app_handle_keep, timer_id.clone()) {
+                                println!("[TRAY] ERROR: Failed to keep quick timer {}: {}", timer_id, e);
+                            }
+                        });
+                        return;
+                    }
+
+                    // Handle alarm snooze/dismiss
+                    if let Some(timer_id) = event_id.strip_prefix("alarm_snooze_") {
+                        println!("[TRAY] Snooze alarm clicked: {}", timer_id);
+                        if let Err(e) = alarms::snooze_alarm(app, timer_id, None) {
+                            println!("[TRAY] ERROR: Failed to snooze alarm {}: {}", timer_id, e);
+                        }
+                        return;
+                    }
+                    if let Some(timer_id) = event_id.strip_prefix("alarm_dismiss_") {
+                        println!("[TRAY] Dismiss alarm clicked: {}", timer_id);
+                        let app_handle_alarm = app.clone();
+                        let timer_id = timer_id.to_string();
+                        std::thread::spawn(move || {
+                            if let Err(e) = alarms::dismiss_alarm(&app_handle_alarm, &timer_id) {
+                                println!("[TRAY] ERROR: Failed to dismiss alarm {}: {}", timer_id, e);
+                            }
+                        });
+                        return;
+                    }
+
                     // Handle create timer from preset
                     if event_id.starts_with("create_preset_") {
                         let preset_id = event_id.strip_prefix("create_preset_").unwrap_or("").to_string();
@@ -295,21 +501,31 @@ pub fn run() {
                     }
                     
                     // Handle quick create timer
-                    let duration_map: std::collections::HashMap<&str, u64> = [
-                        ("quick_create_30s", 30),
-                        ("quick_create_1min", 60),
-                        ("quick_create_2min", 120),
-                        ("quick_create_5min", 300),
-                        ("quick_create_10min", 600),
-                        ("quick_create_15min", 900),
-                        ("quick_create_30min", 1800),
-                    ].iter().cloned().collect();
+                    let quick_duration = |prefix: &str| {
+                        event_id.strip_prefix(prefix).and_then(|key| {
+                            QUICK_DURATIONS
+                                .iter()
+                                .find(|(k, _, _)| *k == key)
+                                .map(|(_, _, seconds)| *seconds)
+                        })
+                    };
                     
-                    if let Some(&duration) = duration_map.get(event_id) {
+                    if let Some(duration) = quick_duration("quick_create_") {
                         println!("[TRAY] Quick create timer clicked: {} seconds", duration);
                         let app_handle_quick = app.clone();
                         std::thread::spawn(move || {
-                            if let Err(e) = quick_create_timer(app_handle_quick, duration) {
+                            if let Err(e) = quick_create_timer(app_handle_quick, duration, None) {
+                                println!("[TRAY] ERROR: Failed to quick create timer: {}", e);
+                            }
+                        });
+                        return;
+                    }
+                    
+                    if let Some(duration) = quick_duration("quick_ephemeral_") {
+                        println!("[TRAY] Quick ephemeral timer clicked: {} seconds", duration);
+                        let app_handle_quick = app.clone();
+                        std::thread::spawn(move || {
+                            if let Err(e) = quick::create_ephemeral(&app_handle_quick, duration, TriggerSource::Tray) {
                                 println!("[TRAY] ERROR: Failed to quick create timer: {}", e);
                             }
                         });
@@ -354,6 +570,7 @@ pub fn run() {
                         }
                         "reset_timers" => {
                             println!("[TRAY] Reset all timers clicked");
+                            history::note_trigger_all(TriggerSource::Tray);
                             if let Some(overlay_window) = app.get_webview_window("overlay") {
                                 let _ = overlay_window.emit("reset-all-timers", ());
                             }
@@ -370,6 +587,19 @@ pub fn run() {
                 .build(app)
                 .expect("Failed to build tray icon");
 
+            alarms::start_alarm_scheduler(app.handle().clone());
+            schedules::start_schedule_runner(app.handle().clone());
+            charges::start_charge_runner(app.handle().clone());
+            pomodoro::start_pomodoro_runner(app.handle().clone());
+            chessclock::start_chess_clock_runner(app.handle().clone());
+            logwatch::start_log_watch_runner(app.handle().clone());
+            processes::start_process_watcher(app.handle().clone());
+            history::start_history_maintenance(app.handle().clone());
+            clock::start_clock_monitor(app.handle().clone());
+            goals::start_goal_monitor(app.handle().clone());
+            webhooks::start_webhook_monitor(app.handle().clone());
+            cli::start_control_server(app.handle().clone());
+
             #[cfg(windows)]
             {
                 // Registrar hook de baixo nível no Windows
@@ -394,8 +624,49 @@ pub fn run() {
             set_overlay_click_through,
             emit_position_changed,
             quick_create_timer,
+            get_quick_timers,
+            keep_quick_timer,
             create_timer_from_preset,
             start_timer,
+            start_timer_group,
+            pause_timer_group,
+            reset_timer_group,
+            report_timer_event,
+            snooze_alarm,
+            dismiss_alarm,
+            get_upcoming_alarms,
+            get_timer_states,
+            preview_schedule,
+            lap_timer,
+            get_laps,
+            acknowledge_timer,
+            adjust_timer,
+            adjust_active_timer,
+            get_run_history,
+            query_history,
+            get_stats,
+            get_weekly_summary,
+            export_history,
+            label_run,
+            get_recent_labels,
+            get_goal_progress,
+            get_event_hook_status,
+            test_event_hook,
+            get_webhook_status,
+            test_webhook,
+            use_charge,
+            reset_charges,
+            toggle_pomodoro,
+            skip_pomodoro_phase,
+            restart_pomodoro_cycle,
+            reset_pomodoro_day,
+            pass_turn,
+            toggle_chess_clock,
+            reset_chess_clock,
+            roll_random_duration,
+            reseed_random_timer,
+            get_log_watch_status,
+            get_process_rule_status,
             #[cfg(windows)]
             register_low_level_shortcut,
             #[cfg(windows)]
@@ -0,0 +1,524 @@
+use crate::commands::load_config;
+use crate::history::{self, TriggerSource};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Listener, Manager};
+
+const CONFIG_RELOAD_SECS: u64 = 30;
+/// Eventos de jogo (ex.: "You have been stunned") precisam virar timer quase na hora
+const TICK_MILLIS: u64 = 250;
+/// Máximo lido por arquivo a cada ciclo, para um log enorme não travar a thread
+const MAX_READ_BYTES: u64 = 1024 * 1024;
+/// Linha sem quebra maior que isso é processada assim mesmo
+const MAX_PARTIAL_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogWatchAction {
+    Start,
+    Reset,
+    /// Só pausa; um timer já pausado continua pausado
+    Pause,
+    /// Alterna entre pausado e rodando a cada linha
+    Toggle,
+}
+
+/// Gatilho de log (`logWatchers[]`): cada linha nova de `path` que casar com `pattern`
+/// executa `action` no timer `timer_id`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogWatcherConfig {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    path: String,
+    pattern: String,
+    action: LogWatchAction,
+    timer_id: String,
+    /// Grupo de captura (nome ou índice) com a duração a usar ao iniciar o timer
+    #[serde(default)]
+    duration_group: Option<String>,
+    #[serde(default = "default_true")]
+    enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+struct Watcher {
+    config: LogWatcherConfig,
+    regex: Result<Regex, String>,
+}
+
+/// Estado de um gatilho de log, como exposto para o frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogWatchStatus {
+    pub id: String,
+    pub name: Option<String>,
+    pub path: String,
+    pub timer_id: String,
+    pub action: LogWatchAction,
+    pub enabled: bool,
+    /// Arquivo encontrado e sendo acompanhado
+    pub watching: bool,
+    pub match_count: u64,
+    /// Último disparo em milissegundos desde a epoch
+    pub last_match_at: Option<i64>,
+    pub last_line: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MatchStats {
+    count: u64,
+    last_at: Option<i64>,
+    last_line: Option<String>,
+}
+
+/// Posição de leitura em um arquivo acompanhado.
+#[derive(Debug, Default)]
+struct FileTail {
+    offset: u64,
+    identity: Option<u64>,
+    /// Final do arquivo ainda sem `\n`
+    partial: Vec<u8>,
+}
+
+static STATUS: LazyLock<Mutex<Vec<LogWatchStatus>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn watcher_configs(config: &Value) -> Vec<Result<LogWatcherConfig, String>> {
+    config
+        .get("logWatchers")
+        .and_then(|w| w.as_array())
+        .map(|watchers| {
+            watchers
+                .iter()
+                .map(|w| serde_json::from_value(w.clone()).map_err(|e| format!("Invalid log watcher: {}", e)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn compile(config: &LogWatcherConfig) -> Result<Regex, String> {
+    if config.path.trim().is_empty() {
+        return Err("No log file selected".to_string());
+    }
+    let regex = Regex::new(&config.pattern).map_err(|e| format!("Invalid pattern: {}", e))?;
+    if let Some(group) = config.duration_group.as_deref().filter(|g| !g.is_empty()) {
+        let exists = match group.parse::<usize>() {
+            Ok(index) => index < regex.captures_len(),
+            Err(_) => regex.capture_names().flatten().any(|name| name == group),
+        };
+        if !exists {
+            return Err(format!("Pattern has no capture group \"{}\"", group));
+        }
+    }
+    Ok(regex)
+}
+
+/// Valida os gatilhos de log antes de salvar a configuração.
+pub fn validate_log_watchers(config: &Value) -> Result<(), String> {
+    for watcher in watcher_configs(config) {
+        let watcher = watcher?;
+        compile(&watcher).map_err(|e| {
+            format!("{} on log watcher \"{}\"", e, watcher.name.as_deref().unwrap_or(&watcher.id))
+        })?;
+    }
+    Ok(())
+}
+
+fn load_watchers(app: &AppHandle) -> Vec<Watcher> {
+    let config = load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .unwrap_or(Value::Null);
+    watcher_configs(&config)
+        .into_iter()
+        .filter_map(|watcher| match watcher {
+            Ok(config) => Some(Watcher {
+                regex: compile(&config),
+                config,
+            }),
+            Err(e) => {
+                println!("[LOGWATCH] WARNING: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Duração capturada do log: `90`, `1:30`, `1:02:03`, `90s`, `2m`, `1.5h`, `1h30m`.
+pub(crate) fn parse_duration(text: &str) -> Option<u64> {
+    let text = text.trim().to_ascii_lowercase();
+    if text.contains(':') {
+        return text
+            .split(':')
+            .map(|part| part.trim().parse::<u64>().ok())
+            .try_fold(0u64, |total, part| total.checked_mul(60)?.checked_add(part?))
+            .filter(|secs| *secs > 0);
+    }
+
+    // Um número sozinho (segundos) ou partes com unidade: `2m`, `1h30m`, `2m 30s`
+    let mut seconds = 0.0;
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        let unit_start = rest.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        let (number, after) = rest.split_at(unit_start);
+        let unit_end = after.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(after.len());
+        let (unit, next) = after.split_at(unit_end);
+        let multiplier = match unit {
+            "" if seconds == 0.0 && next.is_empty() => 1.0,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+            "h" | "hr" | "hour" | "hours" => 3600.0,
+            _ => return None,
+        };
+        let number = number.trim().parse::<f64>().ok().filter(|n| *n >= 0.0)?;
+        seconds += number * multiplier;
+        rest = next.trim_start();
+    }
+    let seconds = seconds.round();
+    (seconds >= 1.0 && seconds < u64::MAX as f64).then_some(seconds as u64)
+}
+
+/// Identifica o arquivo por trás do caminho, para perceber rotação (renomear e recriar).
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+/// No Windows não há inode estável; a data de criação muda quando o log é recriado.
+/// Se não mudar (tunneling do NTFS), a checagem de truncamento cobre o caso.
+#[cfg(not(unix))]
+fn file_identity(metadata: &fs::Metadata) -> Option<u64> {
+    metadata
+        .created()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_nanos() as u64)
+}
+
+/// Lê as linhas completas escritas desde a última leitura. Na primeira vez que o arquivo
+/// é visto começa do fim (o histórico antigo não dispara nada); se ele sumir, for
+/// truncado ou trocado por outro, a leitura recomeça do início do arquivo novo.
+fn read_new_lines(path: &Path, tail: &mut Option<FileTail>) -> Result<Vec<String>, String> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            *tail = Some(FileTail::default());
+            return Err(format!("Cannot read file: {}", e));
+        }
+    };
+    let len = metadata.len();
+    let identity = file_identity(&metadata);
+
+    let tail = tail.get_or_insert_with(|| FileTail {
+        offset: len,
+        identity,
+        partial: Vec::new(),
+    });
+    let rotated = matches!((tail.identity, identity), (Some(old), Some(new)) if old != new);
+    if rotated || len < tail.offset {
+        println!(
+            "[LOGWATCH] {} was {}, reading from the start",
+            path.display(),
+            if rotated { "rotated" } else { "truncated" }
+        );
+        tail.offset = 0;
+        tail.partial.clear();
+    }
+    tail.identity = identity;
+
+    if len == tail.offset {
+        return Ok(Vec::new());
+    }
+
+    let mut file = File::open(path).map_err(|e| format!("Cannot open file: {}", e))?;
+    file.seek(SeekFrom::Start(tail.offset))
+        .map_err(|e| format!("Cannot seek file: {}", e))?;
+    let mut buffer = Vec::new();
+    let read = file
+        .take(MAX_READ_BYTES)
+        .read_to_end(&mut buffer)
+        .map_err(|e| format!("Cannot read file: {}", e))?;
+    tail.offset += read as u64;
+
+    tail.partial.extend_from_slice(&buffer);
+    let mut lines: Vec<String> = Vec::new();
+    let mut start = 0;
+    for (index, byte) in tail.partial.iter().enumerate() {
+        if *byte == b'\n' {
+            lines.push(decode_line(&tail.partial[start..index]));
+            start = index + 1;
+        }
+    }
+    tail.partial.drain(..start);
+    if tail.partial.len() > MAX_PARTIAL_BYTES {
+        lines.push(decode_line(&tail.partial));
+        tail.partial.clear();
+    }
+    Ok(lines)
+}
+
+fn decode_line(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim_end_matches('\r').to_string()
+}
+
+fn fire(app: &AppHandle, watcher: &LogWatcherConfig, captures: &Captures) {
+    let duration = watcher
+        .duration_group
+        .as_deref()
+        .filter(|g| !g.is_empty())
+        .and_then(|group| match group.parse::<usize>() {
+            Ok(index) => captures.get(index),
+            Err(_) => captures.name(group),
+        })
+        .and_then(|m| parse_duration(m.as_str()));
+
+    println!(
+        "[LOGWATCH] Watcher {} matched: {:?} timer {} (duration {:?})",
+        watcher.id, watcher.action, watcher.timer_id, duration
+    );
+
+    let Some(overlay_window) = app.get_webview_window("overlay") else {
+        println!("[LOGWATCH] WARNING: Overlay window not found");
+        return;
+    };
+    history::note_trigger(&watcher.timer_id, TriggerSource::LogWatch);
+    let result = match (watcher.action, duration) {
+        (LogWatchAction::Start, Some(seconds)) => overlay_window.emit(
+            "start-timer-with-duration",
+            json!({ "timerId": watcher.timer_id, "durationSeconds": seconds }),
+        ),
+        (LogWatchAction::Start, None) => overlay_window.emit("start-specific-timer", &watcher.timer_id),
+        (LogWatchAction::Reset, _) => overlay_window.emit("reset-specific-timer", &watcher.timer_id),
+        (LogWatchAction::Pause, _) => overlay_window.emit("pause-only-timer", &watcher.timer_id),
+        (LogWatchAction::Toggle, _) => overlay_window.emit("pause-specific-timer", &watcher.timer_id),
+    };
+    if let Err(e) = result {
+        println!("[LOGWATCH] ERROR: Failed to emit action for {}: {}", watcher.id, e);
+    }
+}
+
+/// Estado atual de cada gatilho de log, na ordem da configuração.
+pub fn log_watch_status() -> Vec<LogWatchStatus> {
+    STATUS.lock().unwrap().clone()
+}
+
+/// Thread que acompanha os arquivos de log configurados e dispara as ações.
+pub fn start_log_watch_runner(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[LOGWATCH] Runner started");
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let mut watchers = load_watchers(&app);
+        let mut last_reload = Instant::now();
+        let mut tails: HashMap<String, Option<FileTail>> = HashMap::new();
+        let mut stats: HashMap<String, MatchStats> = HashMap::new();
+
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || last_reload.elapsed() >= Duration::from_secs(CONFIG_RELOAD_SECS)
+            {
+                watchers = load_watchers(&app);
+                last_reload = Instant::now();
+            }
+
+            // Cada arquivo é lido uma vez por ciclo, mesmo com vários gatilhos nele
+            let paths: BTreeSet<String> = watchers
+                .iter()
+                .filter(|w| w.config.enabled && w.regex.is_ok())
+                .map(|w| w.config.path.clone())
+                .collect();
+            tails.retain(|path, _| paths.contains(path));
+
+            let mut file_errors: HashMap<String, String> = HashMap::new();
+            for path in &paths {
+                let tail = tails.entry(path.clone()).or_default();
+                let lines = match read_new_lines(Path::new(path), tail) {
+                    Ok(lines) => lines,
+                    Err(e) => {
+                        file_errors.insert(path.clone(), e);
+                        continue;
+                    }
+                };
+
+                for line in &lines {
+                    for watcher in watchers.iter().filter(|w| w.config.enabled && w.config.path == *path) {
+                        let Ok(regex) = &watcher.regex else { continue };
+                        if let Some(captures) = regex.captures(line) {
+                            fire(&app, &watcher.config, &captures);
+                            let entry = stats.entry(watcher.config.id.clone()).or_default();
+                            entry.count += 1;
+                            entry.last_at = Some(chrono::Utc::now().timestamp_millis());
+                            entry.last_line = Some(line.clone());
+                        }
+                    }
+                }
+            }
+
+            let status = watchers
+                .iter()
+                .map(|watcher| {
+                    let config = &watcher.config;
+                    let watcher_stats = stats.get(&config.id).cloned().unwrap_or_default();
+                    let error = match &watcher.regex {
+                        Err(e) => Some(e.clone()),
+                        Ok(_) if config.enabled => file_errors.get(&config.path).cloned(),
+                        Ok(_) => None,
+                    };
+                    LogWatchStatus {
+                        id: config.id.clone(),
+                        name: config.name.clone(),
+                        path: config.path.clone(),
+                        timer_id: config.timer_id.clone(),
+                        action: config.action,
+                        enabled: config.enabled,
+                        watching: config.enabled && error.is_none(),
+                        match_count: watcher_stats.count,
+                        last_match_at: watcher_stats.last_at,
+                        last_line: watcher_stats.last_line,
+                        error,
+                    }
+                })
+                .collect();
+            *STATUS.lock().unwrap() = status;
+
+            std::thread::sleep(Duration::from_millis(TICK_MILLIS));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_durations() {
+        let cases = [
+            ("90", Some(90)),
+            (" 90s ", Some(90)),
+            ("5m", Some(300)),
+            ("5 min", Some(300)),
+            ("1.5h", Some(5400)),
+            ("1h30m", Some(5400)),
+            ("2m 30s", Some(150)),
+            ("1:30", Some(90)),
+            ("1:02:03", Some(3723)),
+            ("0", None),
+            ("0:00", None),
+            ("", None),
+            ("soon", None),
+            ("5 parsecs", None),
+            ("1h 30", None),
+            ("-5m", None),
+            ("1:xx", None),
+            ("nan", None),
+            // Estouro do u64
+            ("99999999999999999999", None),
+            ("99999999999999999999h", None),
+            ("307445734561825861:00", None),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_duration(text), expected, "{:?}", text);
+        }
+    }
+
+    /// Arquivo de log numa pasta temporária só deste teste
+    fn log_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("overlay-timer-logwatch-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("game.log");
+        fs::write(&path, "old line\n").unwrap();
+        path
+    }
+
+    fn append(path: &Path, text: &str) {
+        fs::OpenOptions::new().append(true).open(path).unwrap().write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn reads_only_lines_appended_after_the_first_look() {
+        let path = log_file("append");
+        let mut tail = None;
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), Vec::<String>::new());
+
+        append(&path, "stunned\r\nhealed\n");
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), vec!["stunned", "healed"]);
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), Vec::<String>::new());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn a_line_without_newline_waits_for_the_rest() {
+        let path = log_file("partial");
+        let mut tail = None;
+        read_new_lines(&path, &mut tail).unwrap();
+
+        append(&path, "boss pull");
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), Vec::<String>::new());
+        append(&path, "ed in 10\nnext");
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), vec!["boss pulled in 10"]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn truncation_restarts_from_the_beginning() {
+        let path = log_file("truncate");
+        let mut tail = None;
+        append(&path, "a much longer line than what comes next\n");
+        read_new_lines(&path, &mut tail).unwrap();
+
+        fs::write(&path, "fresh\n").unwrap();
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), vec!["fresh"]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    // No Windows o tunneling do NTFS pode manter a data de criação do arquivo recriado
+    #[cfg(unix)]
+    #[test]
+    fn rotation_reads_the_new_file_from_the_start() {
+        let path = log_file("rotate");
+        let mut tail = None;
+        read_new_lines(&path, &mut tail).unwrap();
+        append(&path, "partial without newline");
+        read_new_lines(&path, &mut tail).unwrap();
+
+        // Renomeia e recria entre duas leituras; maior que o antigo para não parecer truncamento
+        fs::rename(&path, path.with_extension("log.1")).unwrap();
+        fs::write(&path, "first line of the new log, longer than before\nsecond\n").unwrap();
+        assert_eq!(
+            read_new_lines(&path, &mut tail).unwrap(),
+            vec!["first line of the new log, longer than before", "second"]
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn a_missing_file_is_read_from_the_start_when_it_returns() {
+        let path = log_file("missing");
+        let mut tail = None;
+        read_new_lines(&path, &mut tail).unwrap();
+
+        fs::remove_file(&path).unwrap();
+        assert!(read_new_lines(&path, &mut tail).is_err());
+        fs::write(&path, "back\n").unwrap();
+        assert_eq!(read_new_lines(&path, &mut tail).unwrap(), vec!["back"]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,711 @@
+use crate::commands::load_config;
+use crate::engine::{self, RunStatus, TimerEvent, TimerEventKind};
+use crate::history::{self, HistoryEntry, HistoryEventKind};
+use chrono::{Local, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Listener, Manager};
+
+const CONFIG_RELOAD_SECS: u64 = 30;
+const TICK_MILLIS: u64 = 200;
+/// Contador de ciclos do dia, por timer (sobrevive a reinícios do app)
+const COUNTER_FILE: &str = "pomodoro.json";
+
+/// Ciclo Pomodoro (`timerType: "pomodoro"`): foco de `timerDuration` segundos,
+/// pausa curta após cada foco e pausa longa a cada `longBreakEvery` focos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PomodoroConfig {
+    focus: Duration,
+    short_break: Duration,
+    long_break: Duration,
+    long_break_every: u32,
+    auto_advance: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PomodoroPhase {
+    Focus,
+    ShortBreak,
+    LongBreak,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PomodoroRuntime {
+    phase: PomodoroPhase,
+    /// Focos concluídos desde a última pausa longa
+    cycle: u32,
+    /// Restante da fase em `running_since` (ou agora, se parada)
+    remaining: Duration,
+    /// None = fase parada (ainda não iniciada ou pausada)
+    running_since: Option<Instant>,
+    /// A fase já começou a contar em algum momento (distingue pausado de ocioso)
+    started: bool,
+    /// Quando a fase atual começou a contar, em milissegundos desde a epoch (UTC)
+    phase_started_at: Option<i64>,
+    /// Tempo já contado na fase atual antes de `running_since` (pausas não entram)
+    counted: Duration,
+    completed_today: u32,
+    day: NaiveDate,
+}
+
+/// Estado do ciclo Pomodoro de um timer, como exposto para o frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PomodoroSnapshot {
+    pub timer_id: String,
+    pub phase: PomodoroPhase,
+    pub status: RunStatus,
+    pub remaining_ms: u64,
+    pub phase_seconds: u64,
+    /// Posição do foco atual (ou do que acabou de terminar) no conjunto, começando em 1
+    pub cycle: u32,
+    pub long_break_every: u32,
+    pub completed_today: u32,
+    pub auto_advance: bool,
+}
+
+/// Fase concluída por `settle`, com os horários reais de início e fim.
+#[derive(Debug, Clone, Copy)]
+struct FinishedPhase {
+    phase: PomodoroPhase,
+    next: PomodoroPhase,
+    /// A fase seguinte começou sozinha (avanço automático)
+    next_started: bool,
+    started_at: i64,
+    ended_at: i64,
+    seconds: u64,
+    /// Tempo que a fase realmente contou, sem as pausas
+    actual_seconds: u64,
+}
+
+static POMODOROS: LazyLock<Mutex<HashMap<String, PomodoroRuntime>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn pomodoro_config(icon: &Value) -> Option<PomodoroConfig> {
+    if icon.get("timerType").and_then(|t| t.as_str()) != Some("pomodoro") {
+        return None;
+    }
+    let pomodoro = icon.get("pomodoro");
+    let seconds = |key: &str, default: u64| {
+        pomodoro
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(default)
+    };
+    Some(PomodoroConfig {
+        focus: Duration::from_secs(icon.get("timerDuration").and_then(|d| d.as_u64()).unwrap_or(1500)),
+        short_break: Duration::from_secs(seconds("shortBreak", 300)),
+        long_break: Duration::from_secs(seconds("longBreak", 900)),
+        long_break_every: seconds("longBreakEvery", 4).clamp(1, u32::MAX as u64) as u32,
+        auto_advance: pomodoro
+            .and_then(|p| p.get("autoAdvance"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    })
+}
+
+fn pomodoro_configs(config: &Value) -> HashMap<String, PomodoroConfig> {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .map(|icons| {
+            icons
+                .iter()
+                .filter_map(|icon| {
+                    let id = icon.get("id").and_then(|id| id.as_str())?;
+                    Some((id.to_string(), pomodoro_config(icon)?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn load_configs(app: &AppHandle) -> HashMap<String, PomodoroConfig> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .map(|config| pomodoro_configs(&config))
+        .unwrap_or_default()
+}
+
+fn load_pomodoro_config(app: &AppHandle, timer_id: &str) -> Result<PomodoroConfig, String> {
+    load_configs(app)
+        .remove(timer_id)
+        .ok_or_else(|| format!("Timer {} is not a Pomodoro timer", timer_id))
+}
+
+fn counter_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(COUNTER_FILE))
+}
+
+/// Ciclos concluídos hoje, lidos do arquivo do contador (0 se for de outro dia).
+fn load_counter(app: &AppHandle, timer_id: &str, today: NaiveDate) -> u32 {
+    let Some(path) = counter_path(app) else { return 0 };
+    let Ok(content) = fs::read_to_string(path) else { return 0 };
+    let Ok(counters) = serde_json::from_str::<Value>(&content) else { return 0 };
+    let entry = &counters[timer_id];
+    if entry.get("date").and_then(|d| d.as_str()) != Some(today.to_string().as_str()) {
+        return 0;
+    }
+    entry.get("completed").and_then(|c| c.as_u64()).unwrap_or(0) as u32
+}
+
+fn save_counters(app: &AppHandle) {
+    let Some(path) = counter_path(app) else { return };
+    let counters: serde_json::Map<String, Value> = POMODOROS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(timer_id, runtime)| {
+            (
+                timer_id.clone(),
+                json!({ "date": runtime.day.to_string(), "completed": runtime.completed_today }),
+            )
+        })
+        .collect();
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Err(e) = fs::write(&path, Value::Object(counters).to_string()) {
+        println!("[POMODORO] WARNING: Failed to save cycle counter: {}", e);
+    }
+}
+
+impl PomodoroRuntime {
+    fn new(config: &PomodoroConfig, completed_today: u32, today: NaiveDate) -> Self {
+        PomodoroRuntime {
+            phase: PomodoroPhase::Focus,
+            cycle: 0,
+            remaining: config.focus,
+            running_since: None,
+            started: false,
+            phase_started_at: None,
+            counted: Duration::ZERO,
+            completed_today,
+            day: today,
+        }
+    }
+
+    fn phase_duration(config: &PomodoroConfig, phase: PomodoroPhase) -> Duration {
+        match phase {
+            PomodoroPhase::Focus => config.focus,
+            PomodoroPhase::ShortBreak => config.short_break,
+            PomodoroPhase::LongBreak => config.long_break,
+        }
+    }
+
+    fn remaining_at(&self, now: Instant) -> Duration {
+        match self.running_since {
+            Some(since) => self.remaining.saturating_sub(now.saturating_duration_since(since)),
+            None => self.remaining,
+        }
+    }
+
+    fn status(&self) -> RunStatus {
+        match (self.running_since, self.started) {
+            (Some(_), _) => RunStatus::Running,
+            (None, true) => RunStatus::Paused,
+            (None, false) => RunStatus::Idle,
+        }
+    }
+
+    fn roll_day(&mut self, today: NaiveDate) {
+        if self.day != today {
+            self.day = today;
+            self.completed_today = 0;
+        }
+    }
+
+    /// Passa para a fase seguinte. `completed` indica se a fase atual terminou
+    /// (e não foi pulada); só focos concluídos contam no dia. A próxima fase começa em
+    /// `start_at` se houver avanço automático, senão fica parada esperando o atalho.
+    fn advance(&mut self, config: &PomodoroConfig, completed: bool, start_at: Option<Instant>) {
+        self.phase = match self.phase {
+            PomodoroPhase::Focus => {
+                if completed {
+                    self.completed_today += 1;
+                }
+                self.cycle += 1;
+                if self.cycle >= config.long_break_every {
+                    PomodoroPhase::LongBreak
+                } else {
+                    PomodoroPhase::ShortBreak
+                }
+            }
+            PomodoroPhase::LongBreak => {
+                self.cycle = 0;
+                PomodoroPhase::Focus
+            }
+            PomodoroPhase::ShortBreak => PomodoroPhase::Focus,
+        };
+        self.remaining = Self::phase_duration(config, self.phase);
+        self.running_since = start_at;
+        self.started = start_at.is_some();
+        self.phase_started_at = start_at.map(epoch_millis);
+        self.counted = Duration::ZERO;
+    }
+
+    /// Conclui as fases vencidas até `now`; com avanço automático a fase seguinte começa
+    /// exatamente no fim da anterior. Retorna as fases concluídas, em ordem.
+    fn settle(&mut self, config: &PomodoroConfig, now: Instant) -> Vec<FinishedPhase> {
+        let mut finished = Vec::new();
+        while let Some(since) = self.running_since {
+            let end = since + self.remaining;
+            if end > now {
+                break;
+            }
+            let phase = self.phase;
+            let seconds = Self::phase_duration(config, phase).as_secs();
+            let ended_at = epoch_millis(end);
+            let started_at = self.phase_started_at.unwrap_or(ended_at - seconds as i64 * 1000);
+            let actual_seconds = (self.counted + self.remaining).as_secs();
+            self.advance(config, true, config.auto_advance.then_some(end));
+            finished.push(FinishedPhase {
+                phase,
+                next: self.phase,
+                next_started: self.running_since.is_some(),
+                started_at,
+                ended_at,
+                seconds,
+                actual_seconds,
+            });
+            // Fases de duração zero não podem travar o loop
+            if self.remaining.is_zero() {
+                self.running_since = None;
+                self.started = false;
+            }
+        }
+        finished
+    }
+
+    /// Como `snapshot`, mas sem contar os ciclos de um dia que já passou.
+    fn snapshot_on(&self, timer_id: &str, config: &PomodoroConfig, now: Instant, today: NaiveDate) -> PomodoroSnapshot {
+        let mut snapshot = self.snapshot(timer_id, config, now);
+        if self.day != today {
+            snapshot.completed_today = 0;
+        }
+        snapshot
+    }
+
+    fn snapshot(&self, timer_id: &str, config: &PomodoroConfig, now: Instant) -> PomodoroSnapshot {
+        let cycle = match self.phase {
+            PomodoroPhase::Focus => self.cycle + 1,
+            _ => self.cycle,
+        };
+        PomodoroSnapshot {
+            timer_id: timer_id.to_string(),
+            phase: self.phase,
+            status: self.status(),
+            remaining_ms: self.remaining_at(now).as_millis() as u64,
+            phase_seconds: Self::phase_duration(config, self.phase).as_secs(),
+            cycle,
+            long_break_every: config.long_break_every,
+            completed_today: self.completed_today,
+            auto_advance: config.auto_advance,
+        }
+    }
+}
+
+/// Converte um `Instant` (passado ou presente) em milissegundos desde a epoch.
+fn epoch_millis(at: Instant) -> i64 {
+    Utc::now().timestamp_millis() - Instant::now().saturating_duration_since(at).as_millis() as i64
+}
+
+/// Evento do ciclo para hooks e webhooks, com a fase do Pomodoro em `detail`.
+fn pomodoro_event(timer_id: &str, event: TimerEventKind, phase: PomodoroPhase, remaining: Option<u64>) -> TimerEvent {
+    TimerEvent {
+        timer_id: timer_id.to_string(),
+        event,
+        phase: None,
+        remaining,
+        instance: None,
+        seed: None,
+        draw: None,
+        detail: Some(json!({ "pomodoroPhase": phase })),
+    }
+}
+
+/// Avisa hooks e webhooks de uma ação do usuário no ciclo (iniciar, pausar, pular).
+fn dispatch_change(app: &AppHandle, snapshot: &PomodoroSnapshot, kind: TimerEventKind) {
+    let Ok(config) = engine::load_config_value(app) else { return };
+    let remaining = Some(snapshot.remaining_ms / 1000);
+    let event = pomodoro_event(&snapshot.timer_id, kind, snapshot.phase, remaining);
+    engine::dispatch_event(&config, &event, None);
+}
+
+/// Registra no histórico os focos concluídos e avisa overlay, hooks e webhooks de cada fase terminada.
+///
+/// As fases não passam por `engine::handle_timer_event`: o ciclo vive aqui, não nas
+/// execuções do engine. Por isso o histórico só recebe o `finished` de cada foco, já com
+/// `run_started_at` (sem entrada `started` separada), e as fases não disparam regras de
+/// encadeamento — o editor do ícone não oferece regras para timers Pomodoro.
+fn report_finished(
+    app: &AppHandle,
+    snapshot: &PomodoroSnapshot,
+    name: Option<String>,
+    finished: &[FinishedPhase],
+) {
+    let config = engine::load_config_value(app).unwrap_or(Value::Null);
+    for done in finished {
+        println!(
+            "[POMODORO] {} finished {:?} after {}s, next {:?} ({} today)",
+            snapshot.timer_id, done.phase, done.actual_seconds, done.next, snapshot.completed_today
+        );
+        let mut entry = None;
+        if done.phase == PomodoroPhase::Focus {
+            // Rótulo pedido durante o foco vale para a sessão que terminou
+            let focus_label = history::take_label(&snapshot.timer_id).unwrap_or_default();
+            entry = Some(HistoryEntry {
+                timer_id: snapshot.timer_id.clone(),
+                name: name.clone(),
+                event: HistoryEventKind::Finished,
+                at: done.ended_at,
+                run_started_at: Some(done.started_at),
+                planned_seconds: Some(done.seconds),
+                actual_seconds: Some(done.actual_seconds),
+                repeat_count: 0,
+                source: None,
+                instance: None,
+                label: focus_label.label,
+                note: focus_label.note,
+                random_seed: None,
+                random_draw: None,
+            });
+        }
+        if let Some(entry) = &entry {
+            history::append(app, entry);
+        }
+        let _ = app.emit(
+            "pomodoro-phase-finished",
+            json!({
+                "timerId": snapshot.timer_id,
+                "finished": done.phase,
+                "next": done.next,
+                "completedToday": snapshot.completed_today,
+                "autoAdvance": snapshot.auto_advance,
+            }),
+        );
+
+        let mut event = pomodoro_event(&snapshot.timer_id, TimerEventKind::Finished, done.phase, Some(0));
+        event.detail = Some(json!({
+            "pomodoroPhase": done.phase,
+            "next": done.next,
+            "completedToday": snapshot.completed_today,
+        }));
+        engine::dispatch_event(&config, &event, entry.as_ref());
+        if done.next_started {
+            let event = pomodoro_event(&snapshot.timer_id, TimerEventKind::Phase, done.next, None);
+            engine::dispatch_event(&config, &event, None);
+        }
+    }
+}
+
+/// Aplica `update` ao ciclo de um timer, criando-o se preciso, e avisa o overlay.
+fn update_pomodoro(
+    app: &AppHandle,
+    timer_id: &str,
+    update: impl FnOnce(&mut PomodoroRuntime, &PomodoroConfig, Instant),
+) -> Result<PomodoroSnapshot, String> {
+    let config = load_pomodoro_config(app, timer_id)?;
+    let now = Instant::now();
+    let today = Local::now().date_naive();
+    let existing = POMODOROS.lock().unwrap().contains_key(timer_id);
+    let completed = if existing { 0 } else { load_counter(app, timer_id, today) };
+
+    let (snapshot, settled, finished) = {
+        let mut pomodoros = POMODOROS.lock().unwrap();
+        let runtime = pomodoros
+            .entry(timer_id.to_string())
+            .or_insert_with(|| PomodoroRuntime::new(&config, completed, today));
+        runtime.roll_day(today);
+        // Fases que venceram entre um tick do runner e este pedido
+        let finished = runtime.settle(&config, now);
+        let settled = runtime.snapshot(timer_id, &config, now);
+        update(runtime, &config, now);
+        (runtime.snapshot(timer_id, &config, now), settled, finished)
+    };
+
+    save_counters(app);
+    if !finished.is_empty() {
+        report_finished(app, &settled, timer_names(app).remove(timer_id), &finished);
+    }
+    let _ = app.emit("pomodoro-changed", &snapshot);
+    Ok(snapshot)
+}
+
+/// Estado atual do ciclo de um ícone; None se não for um timer Pomodoro. Só lê: um
+/// ciclo que ainda não foi usado aparece no início, com o contador salvo do dia.
+pub fn pomodoro_state(app: &AppHandle, icon: &Value) -> Option<PomodoroSnapshot> {
+    let config = pomodoro_config(icon)?;
+    let timer_id = icon.get("id").and_then(|id| id.as_str())?;
+    let now = Instant::now();
+    let today = Local::now().date_naive();
+    let snapshot = POMODOROS
+        .lock()
+        .unwrap()
+        .get(timer_id)
+        .map(|runtime| runtime.snapshot_on(timer_id, &config, now, today));
+    Some(snapshot.unwrap_or_else(|| {
+        PomodoroRuntime::new(&config, load_counter(app, timer_id, today), today).snapshot(timer_id, &config, now)
+    }))
+}
+
+/// Status da fase atual, para quem só conhece timers comuns (regras de processo).
+pub fn run_status(timer_id: &str) -> Option<RunStatus> {
+    POMODOROS.lock().unwrap().get(timer_id).map(|runtime| runtime.status())
+}
+
+/// Inicia, pausa ou retoma a fase atual (atalho do timer).
+pub fn toggle_pomodoro(app: &AppHandle, timer_id: &str) -> Result<PomodoroSnapshot, String> {
+    let mut kind = TimerEventKind::Paused;
+    let snapshot = update_pomodoro(app, timer_id, |runtime, _, now| match runtime.running_since {
+        Some(since) => {
+            runtime.counted += now.saturating_duration_since(since);
+            runtime.remaining = runtime.remaining_at(now);
+            runtime.running_since = None;
+        }
+        None => {
+            if runtime.started {
+                kind = TimerEventKind::Resumed;
+            } else {
+                kind = TimerEventKind::Started;
+                runtime.phase_started_at = Some(epoch_millis(now));
+            }
+            runtime.running_since = Some(now);
+            runtime.started = true;
+        }
+    })?;
+    println!("[POMODORO] {} {:?} ({:?})", timer_id, snapshot.status, snapshot.phase);
+    dispatch_change(app, &snapshot, kind);
+    Ok(snapshot)
+}
+
+/// Pula para a próxima fase sem contar o foco atual como concluído.
+pub fn skip_phase(app: &AppHandle, timer_id: &str) -> Result<PomodoroSnapshot, String> {
+    let snapshot = update_pomodoro(app, timer_id, |runtime, config, now| {
+        let start_at = (config.auto_advance || runtime.running_since.is_some()).then_some(now);
+        runtime.advance(config, false, start_at);
+    })?;
+    println!("[POMODORO] {} skipped to {:?}", timer_id, snapshot.phase);
+    dispatch_change(app, &snapshot, TimerEventKind::Phase);
+    Ok(snapshot)
+}
+
+/// Volta ao primeiro foco do conjunto, parado; o contador do dia continua.
+pub fn restart_cycle(app: &AppHandle, timer_id: &str) -> Result<PomodoroSnapshot, String> {
+    let snapshot = update_pomodoro(app, timer_id, |runtime, config, _| {
+        *runtime = PomodoroRuntime::new(config, runtime.completed_today, runtime.day);
+    })?;
+    println!("[POMODORO] {} cycle restarted", timer_id);
+    Ok(snapshot)
+}
+
+/// Zera o contador de ciclos do dia e recomeça o conjunto.
+pub fn reset_day(app: &AppHandle, timer_id: &str) -> Result<PomodoroSnapshot, String> {
+    let snapshot = update_pomodoro(app, timer_id, |runtime, config, _| {
+        *runtime = PomodoroRuntime::new(config, 0, runtime.day);
+    })?;
+    println!("[POMODORO] {} day counter reset", timer_id);
+    Ok(snapshot)
+}
+
+fn timer_names(app: &AppHandle) -> HashMap<String, String> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|config| config.get("icons").and_then(|i| i.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|icon| {
+            let id = icon.get("id")?.as_str()?;
+            let name = icon.get("name")?.as_str()?;
+            Some((id.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+/// Thread que conclui as fases, avança o ciclo e avisa o overlay a cada troca.
+pub fn start_pomodoro_runner(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[POMODORO] Runner started");
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let mut configs = load_configs(&app);
+        let mut last_reload = Instant::now();
+
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || last_reload.elapsed() >= Duration::from_secs(CONFIG_RELOAD_SECS)
+            {
+                configs = load_configs(&app);
+                last_reload = Instant::now();
+            }
+
+            let now = Instant::now();
+            let today = Local::now().date_naive();
+            let mut changed = Vec::new();
+            {
+                let mut pomodoros = POMODOROS.lock().unwrap();
+                // Timers removidos ou que deixaram de ser Pomodoro
+                pomodoros.retain(|timer_id, _| configs.contains_key(timer_id));
+                for (timer_id, runtime) in pomodoros.iter_mut() {
+                    let config = &configs[timer_id];
+                    let day_before = runtime.day;
+                    runtime.roll_day(today);
+                    let finished = runtime.settle(config, now);
+                    if !finished.is_empty() || day_before != runtime.day {
+                        changed.push((finished, runtime.snapshot(timer_id, config, now)));
+                    }
+                }
+            }
+            if changed.is_empty() {
+                std::thread::sleep(Duration::from_millis(TICK_MILLIS));
+                continue;
+            }
+
+            save_counters(&app);
+            let names = timer_names(&app);
+            for (finished, snapshot) in changed {
+                let _ = app.emit("pomodoro-changed", &snapshot);
+                report_finished(&app, &snapshot, names.get(&snapshot.timer_id).cloned(), &finished);
+            }
+
+            std::thread::sleep(Duration::from_millis(TICK_MILLIS));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(every: u32, auto_advance: bool) -> PomodoroConfig {
+        PomodoroConfig {
+            focus: Duration::from_secs(10),
+            short_break: Duration::from_secs(5),
+            long_break: Duration::from_secs(20),
+            long_break_every: every,
+            auto_advance,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+    }
+
+    #[test]
+    fn long_break_every_n_focuses() {
+        let config = config(3, false);
+        let mut runtime = PomodoroRuntime::new(&config, 0, today());
+        let mut phases = Vec::new();
+        for _ in 0..8 {
+            runtime.advance(&config, true, None);
+            phases.push(runtime.phase);
+        }
+        use PomodoroPhase::*;
+        assert_eq!(
+            phases,
+            [ShortBreak, Focus, ShortBreak, Focus, LongBreak, Focus, ShortBreak, Focus]
+        );
+        assert_eq!(runtime.completed_today, 4);
+        // Depois da pausa longa o conjunto recomeça
+        assert_eq!(runtime.cycle, 1);
+    }
+
+    #[test]
+    fn auto_advance_chains_phases_without_drift() {
+        let config = config(4, true);
+        let t0 = Instant::now();
+        let mut runtime = PomodoroRuntime::new(&config, 0, today());
+        runtime.running_since = Some(t0);
+        runtime.started = true;
+
+        // Foco (10 s) e pausa curta (5 s) vencem; o foco seguinte começou em t0 + 15 s
+        let finished = runtime.settle(&config, t0 + Duration::from_secs(16));
+        let phases: Vec<_> = finished.iter().map(|f| (f.phase, f.next, f.next_started)).collect();
+        assert_eq!(
+            phases,
+            [
+                (PomodoroPhase::Focus, PomodoroPhase::ShortBreak, true),
+                (PomodoroPhase::ShortBreak, PomodoroPhase::Focus, true),
+            ]
+        );
+        assert_eq!(finished[0].actual_seconds, 10);
+        assert_eq!(finished[1].actual_seconds, 5);
+        assert_eq!(runtime.phase, PomodoroPhase::Focus);
+        assert_eq!(runtime.running_since, Some(t0 + Duration::from_secs(15)));
+        assert_eq!(runtime.remaining_at(t0 + Duration::from_secs(16)), Duration::from_secs(9));
+        assert_eq!(runtime.completed_today, 1);
+    }
+
+    #[test]
+    fn without_auto_advance_next_phase_waits() {
+        let config = config(4, false);
+        let t0 = Instant::now();
+        let mut runtime = PomodoroRuntime::new(&config, 0, today());
+        runtime.running_since = Some(t0);
+        runtime.started = true;
+
+        let finished = runtime.settle(&config, t0 + Duration::from_secs(60));
+        assert_eq!(finished.len(), 1);
+        assert!(!finished[0].next_started);
+        assert_eq!(runtime.phase, PomodoroPhase::ShortBreak);
+        assert_eq!(runtime.status(), RunStatus::Idle);
+        assert_eq!(runtime.remaining, config.short_break);
+    }
+
+    #[test]
+    fn skipped_focus_does_not_count_toward_the_day() {
+        let config = config(2, false);
+        let mut runtime = PomodoroRuntime::new(&config, 3, today());
+        runtime.advance(&config, false, None);
+        assert_eq!(runtime.phase, PomodoroPhase::ShortBreak);
+        assert_eq!(runtime.completed_today, 3);
+        // Pular ainda avança o conjunto rumo à pausa longa
+        runtime.advance(&config, false, None);
+        runtime.advance(&config, false, None);
+        assert_eq!(runtime.phase, PomodoroPhase::LongBreak);
+        assert_eq!(runtime.completed_today, 3);
+    }
+
+    #[test]
+    fn actual_seconds_leave_out_pauses() {
+        let config = config(4, false);
+        let t0 = Instant::now();
+        let mut runtime = PomodoroRuntime::new(&config, 0, today());
+        // Contou 4 s, ficou pausado e retomou em t0 com os 6 s restantes
+        runtime.phase_started_at = Some(epoch_millis(t0) - 30_000);
+        runtime.counted = Duration::from_secs(4);
+        runtime.remaining = Duration::from_secs(6);
+        runtime.running_since = Some(t0);
+        runtime.started = true;
+
+        let finished = runtime.settle(&config, t0 + Duration::from_secs(6));
+        assert_eq!(finished.len(), 1);
+        assert!(finished[0].ended_at - finished[0].started_at >= 30_000);
+        assert_eq!(finished[0].seconds, 10);
+        assert_eq!(finished[0].actual_seconds, 10);
+        assert_eq!(runtime.counted, Duration::ZERO);
+    }
+
+    #[test]
+    fn snapshot_of_a_past_day_reads_zero_without_rolling_the_runtime() {
+        let config = config(4, false);
+        let mut runtime = PomodoroRuntime::new(&config, 0, today());
+        runtime.advance(&config, true, None);
+        let now = Instant::now();
+        let tomorrow = today().succ_opt().unwrap();
+
+        assert_eq!(runtime.snapshot_on("focus", &config, now, today()).completed_today, 1);
+        assert_eq!(runtime.snapshot_on("focus", &config, now, tomorrow).completed_today, 0);
+        assert_eq!(runtime.day, today());
+        assert_eq!(runtime.completed_today, 1);
+    }
+}
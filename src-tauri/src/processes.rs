@@ -0,0 +1,517 @@
+use crate::commands::load_config;
+use crate::engine::{self, RunStatus};
+use crate::history::{self, TriggerSource};
+use crate::profiles;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Listener, Manager};
+
+const CONFIG_RELOAD_SECS: u64 = 30;
+const POLL_SECS: u64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessEvent {
+    Start,
+    Exit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessAction {
+    Start,
+    Stop,
+    Reset,
+    /// Troca para o perfil do alvo (`target.type: "profile"`)
+    Profile,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ProcessTarget {
+    Timer {
+        #[serde(rename = "timerId")]
+        timer_id: String,
+    },
+    Group {
+        #[serde(rename = "groupId")]
+        group_id: String,
+    },
+    Profile {
+        #[serde(rename = "profileId")]
+        profile_id: String,
+    },
+}
+
+/// Regra de processo (`processRules[]`): quando um programa que casa com `pattern`
+/// abre ou fecha, executa `action` num timer, em todos os timers de um grupo ou troca de perfil.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProcessRuleConfig {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    /// Nome do executável (`Wow.exe`) ou caminho completo; aceita `*` e `?`
+    pattern: String,
+    event: ProcessEvent,
+    action: ProcessAction,
+    target: ProcessTarget,
+    #[serde(default = "default_true")]
+    enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+struct ProcessRule {
+    config: ProcessRuleConfig,
+    matcher: Regex,
+    /// Padrões com separador de diretório comparam o caminho completo
+    match_path: bool,
+}
+
+/// Estado de uma regra de processo, como exposto para o frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessRuleStatus {
+    pub id: String,
+    pub process_running: bool,
+    /// Último disparo em milissegundos desde a epoch
+    pub last_fired_at: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+struct ProcessInfo {
+    name: String,
+    path: Option<String>,
+}
+
+static STATUS: LazyLock<Mutex<Vec<ProcessRuleStatus>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+/// Converte um padrão com `*`/`?` em regex, sem diferenciar maiúsculas.
+fn wildcard_regex(pattern: &str) -> Result<Regex, String> {
+    let escaped = regex::escape(pattern.trim())
+        .replace(r"\*", ".*")
+        .replace(r"\?", ".");
+    Regex::new(&format!("(?i)^{}$", escaped)).map_err(|e| format!("Invalid pattern: {}", e))
+}
+
+fn parse_rule(rule: &Value) -> Result<ProcessRule, String> {
+    let config: ProcessRuleConfig = serde_json::from_value(rule.clone()).map_err(|e| e.to_string())?;
+    if config.pattern.trim().is_empty() {
+        return Err(format!("Rule {}: pattern is empty", config.id));
+    }
+    let target_matches = match config.target {
+        ProcessTarget::Profile { .. } => config.action == ProcessAction::Profile,
+        _ => config.action != ProcessAction::Profile,
+    };
+    if !target_matches {
+        return Err(format!(
+            "Rule {}: profile switching needs a profile target, and only it",
+            config.id
+        ));
+    }
+    let matcher = wildcard_regex(&config.pattern).map_err(|e| format!("Rule {}: {}", config.id, e))?;
+    let match_path = config.pattern.contains(['/', '\\']);
+    Ok(ProcessRule {
+        config,
+        matcher,
+        match_path,
+    })
+}
+
+/// Valida `processRules` antes de salvar a configuração.
+pub fn validate_process_rules(config: &Value) -> Result<(), String> {
+    let Some(rules) = config.get("processRules").and_then(|r| r.as_array()) else {
+        return Ok(());
+    };
+    rules.iter().try_for_each(|rule| parse_rule(rule).map(|_| ()))
+}
+
+fn load_rules(app: &AppHandle) -> (Vec<ProcessRule>, Value) {
+    let config = load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .unwrap_or(Value::Null);
+    let rules = config
+        .get("processRules")
+        .and_then(|r| r.as_array())
+        .map(|rules| {
+            rules
+                .iter()
+                .filter_map(|rule| match parse_rule(rule) {
+                    Ok(rule) => Some(rule),
+                    Err(e) => {
+                        println!("[PROCESS] WARNING: Invalid process rule: {}", e);
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (rules, config)
+}
+
+fn file_name(path: &str) -> Option<String> {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+}
+
+#[cfg(target_os = "linux")]
+fn list_processes(_with_paths: bool) -> Vec<ProcessInfo> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().bytes().all(|b| b.is_ascii_digit()))
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let path = std::fs::read_link(dir.join("exe"))
+                .ok()
+                .map(|p| p.to_string_lossy().into_owned());
+            // `comm` é truncado em 15 caracteres; usa o executável quando dá para ler
+            let name = path.as_deref().and_then(file_name).or_else(|| {
+                std::fs::read_to_string(dir.join("comm"))
+                    .ok()
+                    .map(|comm| comm.trim().to_string())
+            })?;
+            Some(ProcessInfo { name, path })
+        })
+        .collect()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn list_processes(_with_paths: bool) -> Vec<ProcessInfo> {
+    let Ok(output) = std::process::Command::new("ps").args(["-A", "-o", "comm="]).output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| ProcessInfo {
+            name: file_name(line).unwrap_or_else(|| line.to_string()),
+            path: Some(line.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn list_processes(with_paths: bool) -> Vec<ProcessInfo> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+
+    let mut processes = Vec::new();
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return processes;
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                let len = entry
+                    .szExeFile
+                    .iter()
+                    .position(|c| *c == 0)
+                    .unwrap_or(entry.szExeFile.len());
+                processes.push(ProcessInfo {
+                    name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+                    // Abrir cada processo é caro; só quando alguma regra usa caminho
+                    path: if with_paths { process_path(entry.th32ProcessID) } else { None },
+                });
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+    }
+    processes
+}
+
+#[cfg(windows)]
+unsafe fn process_path(pid: u32) -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+    let mut buffer = [0u16; 1024];
+    let mut size = buffer.len() as u32;
+    let result = QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut size);
+    let _ = CloseHandle(handle);
+    result.ok()?;
+    Some(String::from_utf16_lossy(&buffer[..size as usize]))
+}
+
+fn rule_matches(rule: &ProcessRule, processes: &[ProcessInfo]) -> bool {
+    processes.iter().any(|process| {
+        if rule.match_path {
+            process.path.as_deref().is_some_and(|path| rule.matcher.is_match(path))
+        } else {
+            rule.matcher.is_match(&process.name)
+        }
+    })
+}
+
+fn target_timers(target: &ProcessTarget, config: &Value) -> Vec<String> {
+    match target {
+        ProcessTarget::Timer { timer_id } => vec![timer_id.clone()],
+        ProcessTarget::Group { group_id } => config
+            .get("timerGroups")
+            .and_then(|g| g.as_array())
+            .and_then(|groups| {
+                groups
+                    .iter()
+                    .find(|g| g.get("id").and_then(|id| id.as_str()) == Some(group_id))
+            })
+            .and_then(|group| group.get("timerIds"))
+            .and_then(|ids| ids.as_array())
+            .map(|ids| ids.iter().filter_map(|id| id.as_str()).map(String::from).collect())
+            .unwrap_or_default(),
+        ProcessTarget::Profile { .. } => Vec::new(),
+    }
+}
+
+/// Evento do overlay que leva o timer ao estado pedido, ou None se ele já está nele.
+fn overlay_event(action: ProcessAction, status: RunStatus) -> Option<&'static str> {
+    match (action, status) {
+        (ProcessAction::Start, RunStatus::Idle) => Some("start-specific-timer"),
+        (ProcessAction::Start, RunStatus::Paused) => Some("pause-specific-timer"),
+        (ProcessAction::Stop, RunStatus::Running) => Some("pause-specific-timer"),
+        (ProcessAction::Reset, _) => Some("reset-specific-timer"),
+        _ => None,
+    }
+}
+
+/// Se a regra dispara nesta varredura: na transição de fechado para aberto (ou o contrário).
+fn fires(event: ProcessEvent, running: bool, previous: bool) -> bool {
+    match event {
+        ProcessEvent::Start => running && !previous,
+        ProcessEvent::Exit => !running && previous,
+    }
+}
+
+/// Aplica a ação olhando o estado de cada timer: o overlay só tem eventos que alternam
+/// (start reinicia, pause pausa/retoma), então "start" não reinicia um timer que já roda
+/// e "stop" não retoma um que já está pausado.
+fn apply(app: &AppHandle, rule: &ProcessRuleConfig, config: &Value) {
+    if rule.action == ProcessAction::Profile {
+        match &rule.target {
+            ProcessTarget::Profile { profile_id } => {
+                println!("[PROCESS] Rule {} -> profile {}", rule.id, profile_id);
+                if let Err(e) = profiles::switch_profile(app, profile_id, TriggerSource::Process) {
+                    println!("[PROCESS] ERROR: Failed to switch profile for rule {}: {}", rule.id, e);
+                }
+            }
+            _ => println!("[PROCESS] WARNING: Rule {} switches profiles but has no profile target", rule.id),
+        }
+        return;
+    }
+
+    let Some(overlay_window) = app.get_webview_window("overlay") else {
+        println!("[PROCESS] WARNING: Overlay window not found");
+        return;
+    };
+
+    for timer_id in target_timers(&rule.target, config) {
+        if let Some(event) = overlay_event(rule.action, engine::run_status(&timer_id)) {
+            println!("[PROCESS] Rule {} -> {} {}", rule.id, event, timer_id);
+            history::note_trigger(&timer_id, TriggerSource::Process);
+            if let Err(e) = overlay_window.emit(event, &timer_id) {
+                println!("[PROCESS] ERROR: Failed to emit {} for {}: {}", event, timer_id, e);
+            }
+        }
+    }
+}
+
+/// Estado atual de cada regra de processo, na ordem da configuração.
+pub fn process_rule_status() -> Vec<ProcessRuleStatus> {
+    STATUS.lock().unwrap().clone()
+}
+
+/// Thread que varre a tabela de processos e dispara as regras ao abrir/fechar programas.
+/// Na primeira varredura, programas já abertos contam como "abriu".
+pub fn start_process_watcher(app: AppHandle) {
+    std::thread::spawn(move || {
+        println!("[PROCESS] Watcher started");
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let (mut rules, mut config) = load_rules(&app);
+        let mut last_reload = Instant::now();
+        let mut was_running: HashMap<String, bool> = HashMap::new();
+        let mut last_fired: HashMap<String, i64> = HashMap::new();
+
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || last_reload.elapsed() >= Duration::from_secs(CONFIG_RELOAD_SECS)
+            {
+                (rules, config) = load_rules(&app);
+                last_reload = Instant::now();
+            }
+
+            let active: Vec<&ProcessRule> = rules.iter().filter(|r| r.config.enabled).collect();
+            let processes = if active.is_empty() {
+                Vec::new()
+            } else {
+                list_processes(active.iter().any(|r| r.match_path))
+            };
+
+            let mut status = Vec::new();
+            for rule in &active {
+                let running = rule_matches(rule, &processes);
+                let previous = was_running.insert(rule.config.id.clone(), running).unwrap_or(false);
+                if fires(rule.config.event, running, previous) {
+                    println!(
+                        "[PROCESS] {} {} (rule {})",
+                        rule.config.pattern,
+                        if running { "started" } else { "exited" },
+                        rule.config.name.as_deref().unwrap_or(&rule.config.id)
+                    );
+                    apply(&app, &rule.config, &config);
+                    last_fired.insert(rule.config.id.clone(), chrono::Utc::now().timestamp_millis());
+                }
+                status.push(ProcessRuleStatus {
+                    id: rule.config.id.clone(),
+                    process_running: running,
+                    last_fired_at: last_fired.get(&rule.config.id).copied(),
+                });
+            }
+            was_running.retain(|id, _| active.iter().any(|r| r.config.id == *id));
+            *STATUS.lock().unwrap() = status;
+
+            std::thread::sleep(Duration::from_secs(POLL_SECS));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn process(name: &str, path: Option<&str>) -> ProcessInfo {
+        ProcessInfo {
+            name: name.to_string(),
+            path: path.map(String::from),
+        }
+    }
+
+    fn rule(pattern: &str) -> ProcessRule {
+        parse_rule(&json!({
+            "id": "r1",
+            "pattern": pattern,
+            "event": "start",
+            "action": "start",
+            "target": { "type": "timer", "timerId": "t1" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn wildcards_escape_regex_characters() {
+        let cases = [
+            ("game.exe", "game.exe", true),
+            // `.` é literal, não "qualquer caractere"
+            ("game.exe", "gameXexe", false),
+            ("c++ tool", "c++ tool", true),
+            ("c++ tool", "cc tool", false),
+            ("app (x86)", "app (x86)", true),
+            ("app (x86)", "app x86", false),
+            ("wow*.exe", "Wow-64.exe", true),
+            ("wow?.exe", "wow64.exe", false),
+            ("wow?.exe", "wow6.exe", true),
+        ];
+        for (pattern, name, expected) in cases {
+            let matcher = wildcard_regex(pattern).unwrap();
+            assert_eq!(matcher.is_match(name), expected, "{} vs {}", pattern, name);
+        }
+    }
+
+    #[test]
+    fn executable_names_match_ignoring_case() {
+        let rule = rule("Wow.exe");
+        assert!(rule_matches(&rule, &[process("WOW.EXE", None)]));
+        assert!(rule_matches(&rule, &[process("wow.exe", None)]));
+        assert!(!rule_matches(&rule, &[process("wow.exe.bak", None)]));
+    }
+
+    #[test]
+    fn patterns_with_a_directory_match_the_full_path() {
+        let rule = rule("C:\\Games\\*\\game.exe");
+        assert!(rule.match_path);
+        assert!(rule_matches(&rule, &[process("game.exe", Some("c:\\games\\wow\\GAME.exe"))]));
+        // Sem caminho conhecido, o nome sozinho não basta
+        assert!(!rule_matches(&rule, &[process("game.exe", None)]));
+    }
+
+    #[test]
+    fn rules_fire_on_the_matching_transition_only() {
+        let cases = [
+            (ProcessEvent::Start, true, false, true),
+            (ProcessEvent::Start, true, true, false),
+            (ProcessEvent::Start, false, true, false),
+            (ProcessEvent::Exit, false, true, true),
+            (ProcessEvent::Exit, false, false, false),
+            (ProcessEvent::Exit, true, false, false),
+        ];
+        for (event, running, previous, expected) in cases {
+            assert_eq!(fires(event, running, previous), expected, "{:?} {} {}", event, running, previous);
+        }
+    }
+
+    #[test]
+    fn start_and_stop_leave_timers_already_in_that_state() {
+        assert_eq!(overlay_event(ProcessAction::Start, RunStatus::Idle), Some("start-specific-timer"));
+        assert_eq!(overlay_event(ProcessAction::Start, RunStatus::Paused), Some("pause-specific-timer"));
+        assert_eq!(overlay_event(ProcessAction::Start, RunStatus::Running), None);
+        assert_eq!(overlay_event(ProcessAction::Stop, RunStatus::Running), Some("pause-specific-timer"));
+        assert_eq!(overlay_event(ProcessAction::Stop, RunStatus::Paused), None);
+        assert_eq!(overlay_event(ProcessAction::Reset, RunStatus::Paused), Some("reset-specific-timer"));
+    }
+
+    #[test]
+    fn validation_rejects_malformed_rules() {
+        let valid = json!({
+            "id": "r1",
+            "pattern": "wow.exe",
+            "event": "exit",
+            "action": "profile",
+            "target": { "type": "profile", "profileId": "raid" }
+        });
+        assert!(validate_process_rules(&json!({ "processRules": [valid.clone()] })).is_ok());
+
+        let broken = [
+            json!({ "pattern": "   " }),
+            json!({ "event": "crash" }),
+            json!({ "action": "start" }),
+            json!({ "target": { "type": "timer", "timerId": "t1" } }),
+        ];
+        for patch in broken {
+            let mut rule = valid.clone();
+            for (key, value) in patch.as_object().unwrap() {
+                rule[key] = value.clone();
+            }
+            assert!(
+                validate_process_rules(&json!({ "processRules": [rule.clone()] })).is_err(),
+                "{}",
+                rule
+            );
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::commands::{load_config, save_config};
+use crate::engine::{self, TimerEvent, TimerEventKind};
+use crate::history::{self, TriggerSource};
+use crate::quick;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+/// Campos do layout do overlay que um perfil pode definir.
+const LAYOUT_KEYS: [&str; 3] = ["overlayCorner", "overlayOrientation", "compactMode"];
+
+/// Ativa um perfil (`profiles[]`): o overlay passa a mostrar só os timers do perfil e
+/// os campos de layout definidos nele substituem os atuais.
+pub fn switch_profile(app: &AppHandle, profile_id: &str, source: TriggerSource) -> Result<(), String> {
+    let config_str = load_config(app.clone())?;
+    let mut config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    let profile = config
+        .get("profiles")
+        .and_then(|p| p.as_array())
+        .and_then(|profiles| {
+            profiles
+                .iter()
+                .find(|p| p.get("id").and_then(|id| id.as_str()) == Some(profile_id))
+        })
+        .cloned()
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+
+    for key in LAYOUT_KEYS {
+        if let Some(value) = profile.get(key).filter(|v| !v.is_null()) {
+            config[key] = value.clone();
+        }
+    }
+    config["activeProfileId"] = json!(profile_id);
+
+    println!(
+        "[PROFILE] Switching to profile {}",
+        profile.get("name").and_then(|n| n.as_str()).unwrap_or(profile_id)
+    );
+    // `save_config` encerra as execuções que saem do overlay; anota quem pediu a troca
+    for (timer_id, _) in engine::open_runs() {
+        if !shown_on_overlay(&config, &timer_id) {
+            history::note_trigger(&timer_id, source);
+        }
+    }
+    let updated_config = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    save_config(app.clone(), updated_config, Some(true))
+}
+
+/// Se o overlay mostra o timer com esta configuração: timers rápidos efêmeros sempre
+/// aparecem; os da configuração, se o perfil ativo os lista (ou não lista nenhum).
+fn shown_on_overlay(config: &Value, timer_id: &str) -> bool {
+    if quick::is_ephemeral(timer_id) {
+        return true;
+    }
+    let in_config = config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .is_some_and(|icons| icons.iter().any(|icon| icon.get("id").and_then(|id| id.as_str()) == Some(timer_id)));
+    if !in_config {
+        return false;
+    }
+
+    let active_id = config.get("activeProfileId").and_then(|id| id.as_str());
+    let profile_timers = config
+        .get("profiles")
+        .and_then(|p| p.as_array())
+        .and_then(|profiles| {
+            profiles
+                .iter()
+                .find(|p| active_id.is_some() && p.get("id").and_then(|id| id.as_str()) == active_id)
+        })
+        .and_then(|profile| profile.get("timerIds"))
+        .and_then(|ids| ids.as_array())
+        .filter(|ids| !ids.is_empty());
+    profile_timers.is_none_or(|ids| ids.iter().any(|id| id.as_str() == Some(timer_id)))
+}
+
+/// O overlay desmonta os timers que saem dele sem reportar o fim; encerra as execuções
+/// abertas desses timers como canceladas, senão ficariam rodando para sempre no engine.
+pub fn settle_hidden_runs(app: &AppHandle, config: &Value, source: TriggerSource) {
+    for (timer_id, instance) in engine::open_runs() {
+        if shown_on_overlay(config, &timer_id) {
+            continue;
+        }
+        println!(
+            "[PROFILE] Cancelling {} (instance {}): no longer on the overlay",
+            timer_id, instance
+        );
+        history::note_trigger_default(&timer_id, source);
+        engine::handle_timer_event(
+            app,
+            TimerEvent {
+                timer_id,
+                event: TimerEventKind::Cancelled,
+                phase: None,
+                remaining: None,
+                instance: Some(instance),
+                seed: None,
+                draw: None,
+                detail: None,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(active: Option<&str>) -> Value {
+        json!({
+            "icons": [{ "id": "boss" }, { "id": "buff" }, { "id": "tea" }],
+            "activeProfileId": active,
+            "profiles": [
+                { "id": "raid", "name": "Raid", "timerIds": ["boss", "buff"] },
+                { "id": "all", "name": "All", "timerIds": [] }
+            ]
+        })
+    }
+
+    #[test]
+    fn profile_timer_ids_decide_what_the_overlay_shows() {
+        let raid = config(Some("raid"));
+        assert!(shown_on_overlay(&raid, "boss"));
+        assert!(!shown_on_overlay(&raid, "tea"));
+    }
+
+    #[test]
+    fn no_profile_or_an_empty_list_shows_every_timer() {
+        for active in [None, Some("all"), Some("missing")] {
+            assert!(shown_on_overlay(&config(active), "tea"), "{:?}", active);
+        }
+    }
+
+    #[test]
+    fn timers_removed_from_the_config_are_not_shown() {
+        assert!(!shown_on_overlay(&config(None), "deleted"));
+    }
+}
@@ -0,0 +1,328 @@
+use crate::commands::{free_quick_keybind, load_config, save_config};
+use crate::engine::{self, RunStatus, TimerEvent, TimerEventKind};
+use crate::history::{self, TriggerSource};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::menu::{MenuItemBuilder, Submenu};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+/// Tempo que um timer efêmero concluído continua no overlay antes de sumir
+const LINGER_SECS: u64 = 5;
+const KEEP_HANDOFF_MILLIS: u64 = 1500;
+
+/// Timers rápidos que só existem na memória, no mesmo formato dos ícones da configuração
+static EPHEMERAL: LazyLock<Mutex<Vec<Value>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static TRAY_MENU: Mutex<Option<Submenu<Wry>>> = Mutex::new(None);
+/// Sufixo dos ids, para timers criados no mesmo milissegundo não colidirem
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+pub fn ephemeral_timers() -> Vec<Value> {
+    EPHEMERAL.lock().unwrap().clone()
+}
+
+fn icon_id(icon: &Value) -> Option<&str> {
+    icon.get("id").and_then(|id| id.as_str())
+}
+
+fn config_has_icon(config: &Value, timer_id: &str) -> bool {
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .is_some_and(|icons| icons.iter().any(|icon| icon_id(icon) == Some(timer_id)))
+}
+
+pub fn is_ephemeral(timer_id: &str) -> bool {
+    EPHEMERAL
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|icon| icon_id(icon) == Some(timer_id))
+}
+
+/// Acrescenta os timers efêmeros aos ícones de uma configuração carregada do disco,
+/// para o engine tratá-los como qualquer outro timer. Timers já guardados na
+/// configuração (durante a troca de `keep`) não entram de novo.
+pub fn with_ephemeral(config: &mut Value) {
+    merge_ephemeral(config, ephemeral_timers());
+}
+
+fn merge_ephemeral(config: &mut Value, ephemeral: Vec<Value>) {
+    let ephemeral: Vec<Value> = ephemeral
+        .into_iter()
+        .filter(|icon| !icon_id(icon).is_some_and(|id| config_has_icon(config, id)))
+        .collect();
+    if ephemeral.is_empty() {
+        return;
+    }
+    match config.get_mut("icons").and_then(|i| i.as_array_mut()) {
+        Some(icons) => icons.extend(ephemeral),
+        None => config["icons"] = json!(ephemeral),
+    }
+}
+
+fn duration_label(seconds: u64) -> String {
+    if seconds >= 60 && seconds.is_multiple_of(60) {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+fn new_timer_id() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    format!("quick-{}-{}", millis, NEXT_SEQ.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Cria um timer efêmero e já o inicia. Retorna o id.
+pub fn create_ephemeral(app: &AppHandle, duration: u64, source: TriggerSource) -> Result<String, String> {
+    if duration == 0 {
+        return Err("Quick timer duration must be greater than zero".to_string());
+    }
+    let timer_id = new_timer_id();
+    let icon = json!({
+        "id": timer_id,
+        "name": format!("Quick {}", duration_label(duration)),
+        "iconName": "Timer",
+        "timerDuration": duration,
+        "notificationType": "notification",
+        "timerType": "countdown",
+        "ephemeral": true
+    });
+    EPHEMERAL.lock().unwrap().push(icon);
+    println!("[QUICK] Ephemeral timer {} created ({}s)", timer_id, duration);
+    notify(app);
+
+    // O overlay enfileira o início se o ícone ainda não tiver aparecido
+    history::note_trigger(&timer_id, source);
+    if let Some(overlay) = app.get_webview_window("overlay") {
+        overlay
+            .emit("start-specific-timer", &timer_id)
+            .map_err(|e| format!("Failed to emit start event: {}", e))?;
+    }
+    Ok(timer_id)
+}
+
+/// Remove timers efêmeros que terminaram ou foram resetados.
+pub fn on_timer_event(app: &AppHandle, event: &TimerEvent) {
+    if !is_ephemeral(&event.timer_id) {
+        return;
+    }
+    match removal_delay(&event.event) {
+        Some(delay) if delay.is_zero() => remove(app, &event.timer_id),
+        Some(delay) => {
+            let app = app.clone();
+            let timer_id = event.timer_id.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                if still_done(engine::run_status(&timer_id)) {
+                    remove(&app, &timer_id);
+                }
+            });
+        }
+        None => {}
+    }
+}
+
+/// Quanto esperar para tirar um timer efêmero do overlay depois deste evento:
+/// cancelados saem na hora; concluídos deixam o zero visível um instante.
+fn removal_delay(event: &TimerEventKind) -> Option<Duration> {
+    match event {
+        TimerEventKind::Cancelled => Some(Duration::ZERO),
+        TimerEventKind::Finished => Some(Duration::from_secs(LINGER_SECS)),
+        _ => None,
+    }
+}
+
+/// Se foi reiniciado durante a espera, o timer fica.
+fn still_done(status: RunStatus) -> bool {
+    status != RunStatus::Running
+}
+
+fn remove(app: &AppHandle, timer_id: &str) {
+    let removed = {
+        let mut ephemeral = EPHEMERAL.lock().unwrap();
+        let before = ephemeral.len();
+        ephemeral.retain(|icon| icon_id(icon) != Some(timer_id));
+        ephemeral.len() != before
+    };
+    if removed {
+        println!("[QUICK] Ephemeral timer {} removed", timer_id);
+        notify(app);
+    }
+}
+
+/// Promove um timer efêmero para a configuração, com um atalho `Alt+F*` se houver livre.
+pub fn keep(app: &AppHandle, timer_id: &str) -> Result<(), String> {
+    let icon = ephemeral_timers()
+        .into_iter()
+        .find(|icon| icon_id(icon) == Some(timer_id))
+        .ok_or_else(|| format!("Quick timer {} not found", timer_id))?;
+
+    let config_str = load_config(app.clone())?;
+    let mut config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+    add_to_config(&mut config, icon)?;
+
+    let config_str = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    save_config(app.clone(), config_str, Some(true))?;
+    println!("[QUICK] Ephemeral timer {} kept in config", timer_id);
+
+    // Só sai da lista depois que o overlay recarregar a configuração (ele espera 500ms),
+    // senão o timer em execução seria desmontado antes de reaparecer como ícone normal
+    let app = app.clone();
+    let timer_id = timer_id.to_string();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(KEEP_HANDOFF_MILLIS));
+        remove(&app, &timer_id);
+    });
+    Ok(())
+}
+
+/// Acrescenta o timer efêmero aos ícones da configuração, sem a marca `ephemeral`.
+fn add_to_config(config: &mut Value, mut icon: Value) -> Result<(), String> {
+    let timer_id = icon_id(&icon).unwrap_or_default().to_string();
+    if config_has_icon(config, &timer_id) {
+        return Err(format!("Quick timer {} is already in the config", timer_id));
+    }
+
+    if let Some(fields) = icon.as_object_mut() {
+        fields.remove("ephemeral");
+        if let Some(keybind) = free_quick_keybind(config) {
+            fields.insert("keybind".to_string(), json!(keybind));
+        }
+    }
+    match config.get_mut("icons").and_then(|i| i.as_array_mut()) {
+        Some(icons) => icons.push(icon),
+        None => config["icons"] = json!([icon]),
+    }
+    Ok(())
+}
+
+pub fn set_tray_menu(app: &AppHandle, menu: Submenu<Wry>) {
+    *TRAY_MENU.lock().unwrap() = Some(menu);
+    refresh_tray_menu(app);
+}
+
+fn notify(app: &AppHandle) {
+    let _ = app.emit("quick-timers-changed", ephemeral_timers());
+    refresh_tray_menu(app);
+}
+
+fn refresh_tray_menu(app: &AppHandle) {
+    let guard = TRAY_MENU.lock().unwrap();
+    let Some(menu) = guard.as_ref() else {
+        return;
+    };
+
+    if let Ok(items) = menu.items() {
+        for item in items {
+            let _ = menu.remove(&item);
+        }
+    }
+
+    let ephemeral = ephemeral_timers();
+    if ephemeral.is_empty() {
+        if let Ok(item) = MenuItemBuilder::with_id("quick_keep_none", "No running quick timers")
+            .enabled(false)
+            .build(app)
+        {
+            let _ = menu.append(&item);
+        }
+        return;
+    }
+
+    for icon in ephemeral {
+        let (Some(id), Some(name)) = (
+            icon.get("id").and_then(|i| i.as_str()),
+            icon.get("name").and_then(|n| n.as_str()),
+        ) else {
+            continue;
+        };
+        if let Ok(item) = MenuItemBuilder::with_id(format!("quick_keep_{}", id), format!("Keep {}", name)).build(app) {
+            let _ = menu.append(&item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn quick_icon(timer_id: &str) -> Value {
+        json!({
+            "id": timer_id,
+            "name": "Quick 5m",
+            "timerDuration": 300,
+            "timerType": "countdown",
+            "ephemeral": true
+        })
+    }
+
+    #[test]
+    fn ephemeral_ids_are_unique() {
+        // Criados no mesmo milissegundo, o sufixo sequencial os diferencia
+        let ids: Vec<String> = (0..1000).map(|_| new_timer_id()).collect();
+        let unique: HashSet<&String> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+        assert!(ids.iter().all(|id| id.starts_with("quick-")));
+    }
+
+    #[test]
+    fn finished_ephemerals_linger_five_seconds() {
+        let cases = [
+            (TimerEventKind::Finished, Some(Duration::from_secs(5))),
+            (TimerEventKind::Cancelled, Some(Duration::ZERO)),
+            (TimerEventKind::Started, None),
+            (TimerEventKind::Paused, None),
+        ];
+        for (event, expected) in cases {
+            assert_eq!(removal_delay(&event), expected, "{:?}", event);
+        }
+    }
+
+    #[test]
+    fn ephemeral_restarted_while_lingering_stays() {
+        assert!(!still_done(RunStatus::Running));
+        for status in [RunStatus::Idle, RunStatus::Overtime] {
+            assert!(still_done(status), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn keep_moves_an_ephemeral_into_the_config_exactly_once() {
+        let mut config = json!({ "icons": [{ "id": "boss", "keybind": "Alt+F1" }] });
+        add_to_config(&mut config, quick_icon("quick-1-1")).unwrap();
+        assert!(add_to_config(&mut config, quick_icon("quick-1-1")).is_err());
+
+        let icons = config["icons"].as_array().unwrap();
+        assert_eq!(icons.len(), 2);
+        let kept = &icons[1];
+        assert_eq!(kept["id"], "quick-1-1");
+        assert!(kept.get("ephemeral").is_none());
+        assert_eq!(kept["keybind"], "Alt+F2");
+
+        // Até sair da lista efêmera, o timer não aparece em dobro
+        merge_ephemeral(&mut config, vec![quick_icon("quick-1-1"), quick_icon("quick-2-2")]);
+        let ids: Vec<&str> = config["icons"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(icon_id)
+            .collect();
+        assert_eq!(ids, ["boss", "quick-1-1", "quick-2-2"]);
+    }
+
+    #[test]
+    fn keep_works_without_an_icons_list() {
+        let mut config = json!({});
+        add_to_config(&mut config, quick_icon("quick-1-1")).unwrap();
+        assert_eq!(config["icons"].as_array().map(|i| i.len()), Some(1));
+    }
+}
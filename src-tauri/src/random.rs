@@ -0,0 +1,275 @@
+use crate::commands::load_config;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+/// Faixa de duração aleatória de um timer (`randomDuration`), em segundos.
+/// `gapMin`/`gapMax` sorteiam o intervalo entre repetições.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RandomRange {
+    min: u64,
+    max: u64,
+    gap: Option<(u64, u64)>,
+    seed: Option<u64>,
+}
+
+/// SplitMix64: pequeno e com a mesma sequência em qualquer versão do app,
+/// o que o `StdRng` do crate rand não garante.
+#[derive(Debug, Clone, Copy)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Inteiro uniforme em `min..=max` (rejeição para não favorecer os menores).
+    /// Faixas de um valor só também consomem um sorteio, para a sequência não depender delas.
+    fn range(&mut self, min: u64, max: u64) -> u64 {
+        if max <= min {
+            self.next_u64();
+            return min;
+        }
+        // Faixa com todos os u64: qualquer valor serve
+        let Some(span) = (max - min).checked_add(1) else {
+            return self.next_u64();
+        };
+        let zone = u64::MAX - (u64::MAX % span);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return min + value % span;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RandomStream {
+    range: RandomRange,
+    seed: u64,
+    rng: SplitMix64,
+    /// Sorteios feitos desde a semente (cada sorteio gera duração + intervalo)
+    draws: u64,
+}
+
+/// Resultado de um sorteio; `seed` + `draw` reproduzem a mesma sequência.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomRoll {
+    pub duration_seconds: u64,
+    /// Intervalo antes da próxima repetição (None = o da configuração de repetição)
+    pub gap_seconds: Option<u64>,
+    pub seed: u64,
+    /// Índice do sorteio na sequência da semente, começando em 0
+    pub draw: u64,
+}
+
+static STREAMS: LazyLock<Mutex<HashMap<String, RandomStream>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn random_range(icon: &Value) -> Option<RandomRange> {
+    let random = icon.get("randomDuration")?;
+    let value = |key: &str| random.get(key).and_then(|v| v.as_u64());
+    let min = value("min")?;
+    let max = value("max").unwrap_or(min).max(min);
+    let gap = value("gapMin").map(|gap_min| (gap_min, value("gapMax").unwrap_or(gap_min).max(gap_min)));
+    Some(RandomRange {
+        min,
+        max,
+        gap,
+        seed: value("seed"),
+    })
+}
+
+fn load_range(app: &AppHandle, timer_id: &str) -> Result<RandomRange, String> {
+    let config_str = load_config(app.clone())?;
+    let config: Value =
+        serde_json::from_str(&config_str).map_err(|e| format!("Failed to parse config: {}", e))?;
+    config
+        .get("icons")
+        .and_then(|i| i.as_array())
+        .and_then(|icons| {
+            icons
+                .iter()
+                .find(|icon| icon.get("id").and_then(|id| id.as_str()) == Some(timer_id))
+        })
+        .and_then(random_range)
+        .ok_or_else(|| format!("Timer {} has no random duration", timer_id))
+}
+
+/// Semente sem configuração: do gerador do sistema, registrada no histórico
+fn fresh_seed() -> u64 {
+    let mut bytes = [0u8; 8];
+    if let Err(e) = getrandom::getrandom(&mut bytes) {
+        println!("[RANDOM] WARNING: System RNG unavailable ({}), seeding from the clock", e);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        return SplitMix64::new(nanos).next_u64();
+    }
+    u64::from_le_bytes(bytes)
+}
+
+fn new_stream(range: RandomRange, seed: Option<u64>) -> RandomStream {
+    let seed = seed.or(range.seed).unwrap_or_else(fresh_seed);
+    RandomStream {
+        range,
+        seed,
+        rng: SplitMix64::new(seed),
+        draws: 0,
+    }
+}
+
+fn next_roll(streams: &mut HashMap<String, RandomStream>, timer_id: &str, range: RandomRange) -> RandomRoll {
+    let stream = streams
+        .entry(timer_id.to_string())
+        .or_insert_with(|| new_stream(range, None));
+    if stream.range != range {
+        *stream = new_stream(range, None);
+    }
+
+    let draw = stream.draws;
+    let duration_seconds = stream.rng.range(range.min, range.max);
+    // Sorteia o intervalo mesmo sem ele, para as durações não mudarem ao ligá-lo
+    let (gap_min, gap_max) = range.gap.unwrap_or((0, 0));
+    let gap = stream.rng.range(gap_min, gap_max);
+    stream.draws += 1;
+
+    RandomRoll {
+        duration_seconds,
+        gap_seconds: range.gap.map(|_| gap),
+        seed: stream.seed,
+        draw,
+    }
+}
+
+/// Sorteia a duração da próxima execução (e o intervalo seguinte) de um timer aleatório.
+/// Mudar a faixa ou a semente na configuração recomeça a sequência.
+pub fn roll_duration(app: &AppHandle, timer_id: &str) -> Result<RandomRoll, String> {
+    let range = load_range(app, timer_id)?;
+    let roll = next_roll(&mut STREAMS.lock().unwrap(), timer_id, range);
+    println!(
+        "[RANDOM] {} rolled {}s (gap {:?}, seed {}, draw {})",
+        timer_id, roll.duration_seconds, roll.gap_seconds, roll.seed, roll.draw
+    );
+    Ok(roll)
+}
+
+/// Recomeça a sequência de um timer a partir de `seed` (ou da semente configurada,
+/// ou de uma nova) para repetir uma sessão. Retorna a semente em uso.
+pub fn reseed(app: &AppHandle, timer_id: &str, seed: Option<u64>) -> Result<u64, String> {
+    let range = load_range(app, timer_id)?;
+    let stream = new_stream(range, seed);
+    STREAMS.lock().unwrap().insert(timer_id.to_string(), stream);
+    println!("[RANDOM] {} reseeded with {}", timer_id, stream.seed);
+    Ok(stream.seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn range_of(random: Value) -> RandomRange {
+        random_range(&json!({ "id": "t", "randomDuration": random })).unwrap()
+    }
+
+    /// Sequência de (duração, intervalo) de uma semente
+    fn rolls(range: RandomRange, count: usize) -> Vec<(u64, Option<u64>)> {
+        let mut streams = HashMap::new();
+        (0..count)
+            .map(|_| next_roll(&mut streams, "t", range))
+            .map(|roll| (roll.duration_seconds, roll.gap_seconds))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_durations_and_gaps() {
+        let range = range_of(json!({ "min": 30, "max": 90, "gapMin": 5, "gapMax": 15, "seed": 42 }));
+        let first = rolls(range, 20);
+        assert_eq!(first, rolls(range, 20));
+        // Não é constante
+        assert!(first.iter().any(|roll| *roll != first[0]));
+
+        let other = range_of(json!({ "min": 30, "max": 90, "gapMin": 5, "gapMax": 15, "seed": 43 }));
+        assert_ne!(first, rolls(other, 20));
+    }
+
+    #[test]
+    fn a_single_value_range_still_consumes_a_draw() {
+        let mut fixed = SplitMix64::new(7);
+        let mut plain = SplitMix64::new(7);
+        assert_eq!(fixed.range(10, 10), 10);
+        plain.next_u64();
+        assert_eq!(fixed.next_u64(), plain.next_u64());
+
+        // Ligar o intervalo não muda as durações sorteadas
+        let without_gap = range_of(json!({ "min": 1, "max": 100, "seed": 9 }));
+        let with_gap = range_of(json!({ "min": 1, "max": 100, "gapMin": 3, "gapMax": 8, "seed": 9 }));
+        let durations = |range| rolls(range, 10).into_iter().map(|(d, _)| d).collect::<Vec<_>>();
+        assert_eq!(durations(without_gap), durations(with_gap));
+    }
+
+    #[test]
+    fn rolls_stay_inside_the_configured_range() {
+        let cases = [
+            (json!({ "min": 30, "max": 90, "gapMin": 5, "gapMax": 15 }), (30, 90), Some((5, 15))),
+            // `max` menor que `min` vira faixa de um valor
+            (json!({ "min": 60, "max": 10 }), (60, 60), None),
+            (json!({ "min": 45 }), (45, 45), None),
+            // `gapMin` sem `gapMax` é um intervalo fixo
+            (json!({ "min": 1, "max": 2, "gapMin": 20 }), (1, 2), Some((20, 20))),
+            (json!({ "min": 1, "max": 2, "gapMin": 20, "gapMax": 5 }), (1, 2), Some((20, 20))),
+            (json!({ "min": 0, "max": u64::MAX }), (0, u64::MAX), None),
+        ];
+        for (random, (min, max), gap) in cases {
+            for (duration, gap_seconds) in rolls(range_of(random.clone()), 200) {
+                assert!((min..=max).contains(&duration), "{} rolled {}", random, duration);
+                match (gap, gap_seconds) {
+                    (Some((lo, hi)), Some(value)) => assert!((lo..=hi).contains(&value), "{} gap {}", random, value),
+                    (None, None) => {}
+                    other => panic!("{}: gap {:?}", random, other),
+                }
+            }
+        }
+        assert!(random_range(&json!({ "id": "t" })).is_none());
+    }
+
+    #[test]
+    fn changing_the_range_restarts_the_stream() {
+        let mut streams = HashMap::new();
+        let range = range_of(json!({ "min": 30, "max": 90, "seed": 5 }));
+        let first = next_roll(&mut streams, "t", range);
+        assert_eq!(next_roll(&mut streams, "t", range).draw, 1);
+
+        let wider = range_of(json!({ "min": 30, "max": 120, "seed": 5 }));
+        let restarted = next_roll(&mut streams, "t", wider);
+        assert_eq!((restarted.seed, restarted.draw), (5, 0));
+
+        // De volta à faixa original, a sequência recomeça igual
+        let again = next_roll(&mut streams, "t", range);
+        assert_eq!((again.draw, again.duration_seconds), (0, first.duration_seconds));
+    }
+
+    #[test]
+    fn unseeded_streams_get_different_seeds() {
+        let range = range_of(json!({ "min": 1, "max": 10 }));
+        assert_ne!(new_stream(range, None).seed, new_stream(range, None).seed);
+        assert_eq!(new_stream(range, Some(3)).seed, 3);
+    }
+}
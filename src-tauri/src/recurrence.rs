@@ -0,0 +1,249 @@
+use chrono::{DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// Fuso horário de uma regra: o fuso local do sistema ou um nome IANA (ex.: `Europe/Berlin`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    Local,
+    Named(Tz),
+}
+
+impl Zone {
+    /// Aceita `None`, vazio ou `"local"` como fuso local.
+    pub fn parse(name: Option<&str>) -> Result<Zone, String> {
+        match name.map(|n| n.trim()).filter(|n| !n.is_empty()) {
+            None => Ok(Zone::Local),
+            Some(n) if n.eq_ignore_ascii_case("local") => Ok(Zone::Local),
+            Some(n) => n
+                .parse::<Tz>()
+                .map(Zone::Named)
+                .map_err(|_| format!("Unknown time zone: {}", n)),
+        }
+    }
+
+    fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).date_naive(),
+            Zone::Named(tz) => instant.with_timezone(tz).date_naive(),
+        }
+    }
+
+    /// Data e hora locais de um instante neste fuso.
+    pub fn local_datetime(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => instant.with_timezone(tz).naive_local(),
+        }
+    }
+
+    pub fn resolve(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Zone::Local => resolve_local(&Local, local),
+            Zone::Named(tz) => resolve_local(tz, local),
+        }
+    }
+}
+
+/// Converte um horário local em instante UTC tratando as transições de horário de verão:
+/// horários repetidos usam a primeira ocorrência e horários que não existem (pulados)
+/// vão para o primeiro quarto de hora válido depois do salto.
+fn resolve_local<T: TimeZone>(tz: &T, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => Some(dt.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest.with_timezone(&Utc)),
+        LocalResult::None => (1..=8)
+            .map(|step| local + Duration::minutes(15 * step))
+            .find_map(|shifted| tz.from_local_datetime(&shifted).earliest())
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// Lê `HH:MM` ou `HH:MM:SS`.
+pub fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value.trim(), "%H:%M:%S"))
+        .map_err(|_| format!("Invalid time (expected HH:MM): {}", value))
+}
+
+/// Converte dias no formato do JavaScript (0 = domingo ... 6 = sábado).
+pub fn weekday_from_index(index: u64) -> Option<Weekday> {
+    match index {
+        0 => Some(Weekday::Sun),
+        1 => Some(Weekday::Mon),
+        2 => Some(Weekday::Tue),
+        3 => Some(Weekday::Wed),
+        4 => Some(Weekday::Thu),
+        5 => Some(Weekday::Fri),
+        6 => Some(Weekday::Sat),
+        _ => None,
+    }
+}
+
+/// Próxima ocorrência de `time` no fuso `zone` estritamente depois de `after`.
+/// `weekdays` vazio significa todos os dias.
+pub fn next_occurrence(
+    zone: &Zone,
+    time: NaiveTime,
+    weekdays: &[Weekday],
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let start = zone.date_of(after);
+    (0..=8)
+        .filter_map(|offset| start.checked_add_signed(Duration::days(offset)))
+        .filter(|date| weekdays.is_empty() || weekdays.contains(&date.weekday()))
+        .filter_map(|date| zone.resolve(date.and_time(time)))
+        .find(|candidate| *candidate > after)
+}
+
+/// Próxima ocorrência de `time` no dia `day` do mês (1-31) estritamente depois de `after`.
+/// Meses sem esse dia usam o último dia do mês (dia 31 cai em 30/04, 28 ou 29/02).
+pub fn next_monthly_occurrence(
+    zone: &Zone,
+    time: NaiveTime,
+    day: u32,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+    let start = zone.date_of(after);
+    (0..=2)
+        .filter_map(|offset| {
+            let month0 = start.month0() + offset;
+            let year = start.year() + (month0 / 12) as i32;
+            let month = month0 % 12 + 1;
+            (1..=day)
+                .rev()
+                .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        })
+        .filter_map(|date| zone.resolve(date.and_time(time)))
+        .find(|candidate| *candidate > after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_rolls_to_next_day_once_time_has_passed() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(
+            next_occurrence(&zone, at(9, 0), &[], utc(2025, 6, 10, 8, 0)),
+            Some(utc(2025, 6, 10, 9, 0))
+        );
+        assert_eq!(
+            next_occurrence(&zone, at(9, 0), &[], utc(2025, 6, 10, 9, 0)),
+            Some(utc(2025, 6, 11, 9, 0))
+        );
+    }
+
+    #[test]
+    fn weekly_picks_next_listed_weekday() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        // 2025-06-10 é uma terça
+        let tuesday_reset =
+            next_occurrence(&zone, at(15, 0), &[Weekday::Tue], utc(2025, 6, 10, 15, 0));
+        assert_eq!(tuesday_reset, Some(utc(2025, 6, 17, 15, 0)));
+        let days = [Weekday::Mon, Weekday::Fri];
+        assert_eq!(
+            next_occurrence(&zone, at(8, 30), &days, utc(2025, 6, 10, 12, 0)),
+            Some(utc(2025, 6, 13, 8, 30))
+        );
+        assert_eq!(
+            next_occurrence(&zone, at(8, 30), &days, utc(2025, 6, 13, 9, 0)),
+            Some(utc(2025, 6, 16, 8, 30))
+        );
+    }
+
+    #[test]
+    fn weekly_uses_the_weekday_in_the_rule_zone() {
+        // 23:30 de segunda em UTC já é terça em Berlim
+        let zone = Zone::Named(Berlin);
+        let next = next_occurrence(&zone, at(1, 0), &[Weekday::Tue], utc(2025, 6, 9, 22, 30));
+        assert_eq!(next, Some(utc(2025, 6, 9, 23, 0)));
+    }
+
+    #[test]
+    fn monthly_picks_day_in_current_or_next_month() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(
+            next_monthly_occurrence(&zone, at(12, 0), 15, utc(2025, 6, 10, 0, 0)),
+            Some(utc(2025, 6, 15, 12, 0))
+        );
+        assert_eq!(
+            next_monthly_occurrence(&zone, at(12, 0), 15, utc(2025, 6, 15, 12, 0)),
+            Some(utc(2025, 7, 15, 12, 0))
+        );
+        assert_eq!(
+            next_monthly_occurrence(&zone, at(0, 0), 1, utc(2025, 12, 20, 0, 0)),
+            Some(utc(2026, 1, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn monthly_31st_clamps_to_last_day_of_short_months() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        let mut after = utc(2024, 1, 31, 12, 0);
+        let mut days = Vec::new();
+        for _ in 0..4 {
+            after = next_monthly_occurrence(&zone, at(12, 0), 31, after).unwrap();
+            days.push((after.month(), after.day()));
+        }
+        assert_eq!(days, vec![(2, 29), (3, 31), (4, 30), (5, 31)]);
+        assert_eq!(
+            next_monthly_occurrence(&zone, at(12, 0), 30, utc(2025, 2, 1, 0, 0)),
+            Some(utc(2025, 2, 28, 12, 0))
+        );
+    }
+
+    #[test]
+    fn monthly_rejects_invalid_days() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(
+            next_monthly_occurrence(&zone, at(12, 0), 0, utc(2025, 6, 1, 0, 0)),
+            None
+        );
+        assert_eq!(
+            next_monthly_occurrence(&zone, at(12, 0), 32, utc(2025, 6, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn skipped_local_time_moves_past_the_gap() {
+        // Em Berlim, 2025-03-30 pula de 02:00 para 03:00 (CET -> CEST)
+        let zone = Zone::Named(Berlin);
+        let next = next_occurrence(&zone, at(2, 30), &[], utc(2025, 3, 29, 12, 0));
+        assert_eq!(next, Some(utc(2025, 3, 30, 1, 0)));
+        assert_eq!(next.unwrap().with_timezone(&Berlin).time(), at(3, 0));
+        // No dia seguinte o horário volta a existir
+        assert_eq!(
+            next_occurrence(&zone, at(2, 30), &[], next.unwrap()),
+            Some(utc(2025, 3, 31, 0, 30))
+        );
+    }
+
+    #[test]
+    fn repeated_local_time_fires_once_on_first_occurrence() {
+        // Em Berlim, 2025-10-26 repete 02:00-03:00 (CEST -> CET)
+        let zone = Zone::Named(Berlin);
+        let first = next_occurrence(&zone, at(2, 30), &[], utc(2025, 10, 25, 12, 0));
+        assert_eq!(first, Some(utc(2025, 10, 26, 0, 30)));
+        // A segunda passagem por 02:30 (01:30 UTC) não dispara de novo
+        assert_eq!(
+            next_occurrence(&zone, at(2, 30), &[], first.unwrap()),
+            Some(utc(2025, 10, 27, 1, 30))
+        );
+        let monthly = next_monthly_occurrence(&zone, at(2, 30), 26, utc(2025, 10, 1, 0, 0));
+        assert_eq!(monthly, Some(utc(2025, 10, 26, 0, 30)));
+    }
+}
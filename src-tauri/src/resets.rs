@@ -0,0 +1,73 @@
+use crate::recurrence::{self, Zone};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Configuração de uma contagem até um reset recorrente (`icons[].reset`),
+/// ex.: reset semanal de raid às terças 15:00 UTC.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetConfig {
+    /// Horário do reset no formato `HH:MM`.
+    pub time: String,
+    /// Dias da semana do reset (0 = domingo ... 6 = sábado). Vazio = todo dia.
+    #[serde(default)]
+    pub weekdays: Vec<u64>,
+    /// Reset mensal nesse dia (1-31; meses mais curtos usam o último dia). Ignora `weekdays`.
+    #[serde(default)]
+    pub month_day: Option<u32>,
+    /// Nome IANA do fuso do servidor (vazio = fuso local do sistema).
+    #[serde(default)]
+    pub time_zone: Option<String>,
+}
+
+/// Próximo reset e a duração do ciclo que termina nele.
+#[derive(Debug, Clone, Copy)]
+pub struct ResetWindow {
+    pub next: DateTime<Utc>,
+    pub period_secs: i64,
+}
+
+pub fn reset_config(icon: &Value) -> Result<ResetConfig, String> {
+    let reset = icon
+        .get("reset")
+        .ok_or_else(|| "Reset timer has no reset rule".to_string())?;
+    serde_json::from_value(reset.clone()).map_err(|e| format!("Invalid reset rule: {}", e))
+}
+
+/// Calcula o próximo reset estritamente depois de `now`. Como sempre olha para
+/// frente, a contagem vira para a próxima ocorrência sozinha ao chegar em zero.
+pub fn next_reset(reset: &ResetConfig, now: DateTime<Utc>) -> Result<ResetWindow, String> {
+    let zone = Zone::parse(reset.time_zone.as_deref())?;
+    let time = recurrence::parse_time(&reset.time)?;
+    if let Some(day) = reset.month_day {
+        if !(1..=31).contains(&day) {
+            return Err(format!("Invalid day of month: {}", day));
+        }
+        let next = recurrence::next_monthly_occurrence(&zone, time, day, now)
+            .ok_or_else(|| "Reset rule has no upcoming occurrence".to_string())?;
+        let following = recurrence::next_monthly_occurrence(&zone, time, day, next)
+            .ok_or_else(|| "Reset rule has no upcoming occurrence".to_string())?;
+        return Ok(ResetWindow {
+            next,
+            period_secs: (following - next).num_seconds(),
+        });
+    }
+    let weekdays = reset
+        .weekdays
+        .iter()
+        .map(|day| {
+            recurrence::weekday_from_index(*day).ok_or_else(|| format!("Invalid weekday: {}", day))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let next = recurrence::next_occurrence(&zone, time, &weekdays, now)
+        .ok_or_else(|| "Reset rule has no upcoming occurrence".to_string())?;
+    let following = recurrence::next_occurrence(&zone, time, &weekdays, next)
+        .ok_or_else(|| "Reset rule has no upcoming occurrence".to_string())?;
+
+    Ok(ResetWindow {
+        next,
+        period_secs: (following - next).num_seconds(),
+    })
+}
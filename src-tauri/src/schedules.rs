@@ -0,0 +1,528 @@
+use crate::commands::{load_config, run_timer_group_action, start_timer_from};
+use crate::history::TriggerSource;
+use crate::recurrence::Zone;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Listener, Manager};
+
+const CONFIG_RELOAD_SECS: i64 = 30;
+const LAST_SEEN_SAVE_SECS: i64 = 30;
+/// Espera o overlay registrar os listeners antes de recuperar disparos perdidos
+const STARTUP_DELAY_SECS: u64 = 5;
+/// Limite de passos ao procurar o próximo disparo (cobre alguns anos)
+const MAX_SEARCH_STEPS: u32 = 200_000;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+
+/// Agendamento estilo cron de um timer ou grupo (`icons[].schedule`, `timerGroups[].schedule`).
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleConfig {
+    /// Expressão cron de 5 campos: minuto hora dia-do-mês mês dia-da-semana.
+    pub cron: String,
+    /// Nome IANA do fuso (vazio = fuso local do sistema).
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    /// Ignora os disparos perdidos enquanto o app estava fechado.
+    #[serde(default = "default_true")]
+    pub skip_missed: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Expressão cron já interpretada, com um bit por valor aceito em cada campo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Campos iniciados com `*` não restringem o dia (regra do cron clássico)
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<CronExpr, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Cron expression needs 5 fields (minute hour day month weekday): {}",
+                expr
+            ));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7, &WEEKDAY_NAMES, 0)?;
+        // 7 também é domingo
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(CronExpr {
+            minutes: parse_field(fields[0], 0, 59, &[], 0)?,
+            hours: parse_field(fields[1], 0, 23, &[], 0)?,
+            days_of_month: parse_field(fields[2], 1, 31, &[], 0)?,
+            months: parse_field(fields[3], 1, 12, &MONTH_NAMES, 1)?,
+            days_of_week,
+            any_day_of_month: fields[2].starts_with('*'),
+            any_day_of_week: fields[4].starts_with('*'),
+        })
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let dom = has_bit(self.days_of_month, date.day());
+        let dow = has_bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => dow,
+            (false, true) => dom,
+            // Com os dois restritos, o cron dispara se qualquer um bater
+            (false, false) => dom || dow,
+        }
+    }
+
+    /// Próximo disparo no fuso `zone` estritamente depois de `after`.
+    pub fn next_after(&self, zone: &Zone, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = zone.local_datetime(after);
+        let mut local = start.date().and_hms_opt(start.hour(), start.minute(), 0)? + Duration::minutes(1);
+
+        for _ in 0..MAX_SEARCH_STEPS {
+            let date = local.date();
+            if !has_bit(self.months, date.month()) {
+                local = first_of_next_month(date)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.day_matches(date) {
+                local = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !has_bit(self.hours, local.hour()) {
+                local = date.and_hms_opt(local.hour(), 0, 0)? + Duration::hours(1);
+                continue;
+            }
+            if !has_bit(self.minutes, local.minute()) {
+                local += Duration::minutes(1);
+                continue;
+            }
+            if let Some(instant) = zone.resolve(local).filter(|instant| *instant > after) {
+                return Some(instant);
+            }
+            local += Duration::minutes(1);
+        }
+        None
+    }
+}
+
+fn has_bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+/// Interpreta um campo cron (`*`, `5`, `1-5`, `*/15`, `1-30/2`, listas com vírgula e
+/// nomes como `mon` ou `jan`). `first_name` é o valor do primeiro nome da lista.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str], first_name: u32) -> Result<u64, String> {
+    let parse_value = |value: &str| -> Result<u32, String> {
+        if let Some(index) = names.iter().position(|n| n.eq_ignore_ascii_case(value)) {
+            return Ok(index as u32 + first_name);
+        }
+        value
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid cron value: {}", value))
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("Invalid cron step: {}", part))?,
+            ),
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_value(a)?, parse_value(b)?)
+        } else {
+            let value = parse_value(range)?;
+            // `5/10` significa "de 5 até o fim, a cada 10"
+            (value, if part.contains('/') { max } else { value })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(format!("Cron value out of range ({}-{}): {}", min, max, part));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScheduleTarget {
+    Timer(String),
+    Group(String),
+}
+
+#[derive(Debug, Clone)]
+struct ScheduleEntry {
+    key: String,
+    name: String,
+    target: ScheduleTarget,
+    schedule: ScheduleConfig,
+}
+
+struct ScheduleRuntime {
+    schedule: ScheduleConfig,
+    next: Option<DateTime<Utc>>,
+}
+
+fn schedule_entries(config: &Value) -> Vec<ScheduleEntry> {
+    let mut entries = Vec::new();
+    let sources = [("icons", "timer"), ("timerGroups", "group")];
+
+    for (list, kind) in sources {
+        let Some(items) = config.get(list).and_then(|l| l.as_array()) else {
+            continue;
+        };
+        for item in items {
+            let (Some(id), Some(schedule)) = (item.get("id").and_then(|id| id.as_str()), item.get("schedule")) else {
+                continue;
+            };
+            let schedule = match serde_json::from_value::<ScheduleConfig>(schedule.clone()) {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    println!("[SCHEDULE] WARNING: Ignoring invalid schedule on {}: {}", id, e);
+                    continue;
+                }
+            };
+            if !schedule.enabled {
+                continue;
+            }
+            let target = if kind == "timer" {
+                ScheduleTarget::Timer(id.to_string())
+            } else {
+                ScheduleTarget::Group(id.to_string())
+            };
+            entries.push(ScheduleEntry {
+                key: format!("{}:{}", kind, id),
+                name: item
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or(id)
+                    .to_string(),
+                target,
+                schedule,
+            });
+        }
+    }
+    entries
+}
+
+fn parse_schedule(schedule: &ScheduleConfig) -> Result<(CronExpr, Zone), String> {
+    Ok((
+        CronExpr::parse(&schedule.cron)?,
+        Zone::parse(schedule.time_zone.as_deref())?,
+    ))
+}
+
+/// Recusa configs com expressões cron ou fusos inválidos.
+pub fn validate_schedules(config: &Value) -> Result<(), String> {
+    for entry in schedule_entries(config) {
+        parse_schedule(&entry.schedule)
+            .map_err(|e| format!("Invalid schedule on \"{}\": {}", entry.name, e))?;
+    }
+    Ok(())
+}
+
+/// Próximos `count` disparos de uma expressão (ms desde a epoch), para pré-visualização.
+pub fn preview_schedule(cron: &str, time_zone: Option<&str>, count: usize) -> Result<Vec<i64>, String> {
+    let cron = CronExpr::parse(cron)?;
+    let zone = Zone::parse(time_zone)?;
+    let mut runs = Vec::new();
+    let mut after = Utc::now();
+    while runs.len() < count {
+        match cron.next_after(&zone, after) {
+            Some(next) => {
+                runs.push(next.timestamp_millis());
+                after = next;
+            }
+            None => break,
+        }
+    }
+    Ok(runs)
+}
+
+fn state_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("scheduler_state.json"))
+}
+
+/// Último instante em que o scheduler estava rodando (para detectar disparos perdidos).
+fn load_last_seen(app: &AppHandle) -> Option<DateTime<Utc>> {
+    let content = fs::read_to_string(state_path(app)?).ok()?;
+    let value: Value = serde_json::from_str(&content).ok()?;
+    Utc.timestamp_millis_opt(value.get("lastSeen")?.as_i64()?).single()
+}
+
+fn save_last_seen(app: &AppHandle, now: DateTime<Utc>) {
+    let Some(path) = state_path(app) else {
+        return;
+    };
+    if let Err(e) = fs::write(&path, json!({ "lastSeen": now.timestamp_millis() }).to_string()) {
+        println!("[SCHEDULE] WARNING: Failed to save scheduler state: {}", e);
+    }
+}
+
+fn load_entries(app: &AppHandle) -> Vec<ScheduleEntry> {
+    load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .map(|config| schedule_entries(&config))
+        .unwrap_or_default()
+}
+
+fn fire(app: &AppHandle, entry: &ScheduleEntry) {
+    println!("[SCHEDULE] Schedule \"{}\" triggered ({})", entry.name, entry.schedule.cron);
+    match &entry.target {
+        ScheduleTarget::Timer(timer_id) => {
+            if let Err(e) = start_timer_from(app, timer_id.clone(), TriggerSource::Schedule) {
+                println!("[SCHEDULE] ERROR: Failed to start timer {}: {}", timer_id, e);
+            }
+        }
+        ScheduleTarget::Group(group_id) => {
+            if let Err(e) = run_timer_group_action(app, group_id, "start", TriggerSource::Schedule) {
+                println!("[SCHEDULE] ERROR: Failed to start group {}: {}", group_id, e);
+            }
+        }
+    }
+}
+
+/// Sincroniza o estado com o config e devolve as entradas que devem disparar agora.
+/// Na primeira passada, `missed_since` recupera disparos perdidos com o app fechado.
+fn sync_and_collect_due(
+    runtimes: &mut HashMap<String, ScheduleRuntime>,
+    entries: &[ScheduleEntry],
+    now: DateTime<Utc>,
+    missed_since: Option<DateTime<Utc>>,
+) -> Vec<ScheduleEntry> {
+    runtimes.retain(|key, _| entries.iter().any(|e| &e.key == key));
+    let mut due = Vec::new();
+
+    for entry in entries {
+        let Ok((cron, zone)) = parse_schedule(&entry.schedule) else {
+            continue;
+        };
+
+        let changed = runtimes
+            .get(&entry.key)
+            .map(|runtime| runtime.schedule != entry.schedule)
+            .unwrap_or(true);
+        if changed {
+            if let Some(last_seen) = missed_since.filter(|_| !entry.schedule.skip_missed) {
+                if cron.next_after(&zone, last_seen).is_some_and(|missed| missed <= now) {
+                    println!("[SCHEDULE] Catching up run of \"{}\" missed while the app was closed", entry.name);
+                    due.push(entry.clone());
+                }
+            }
+            runtimes.insert(
+                entry.key.clone(),
+                ScheduleRuntime {
+                    schedule: entry.schedule.clone(),
+                    next: cron.next_after(&zone, now),
+                },
+            );
+            continue;
+        }
+
+        if let Some(runtime) = runtimes.get_mut(&entry.key) {
+            if runtime.next.is_some_and(|next| next <= now) {
+                due.push(entry.clone());
+                runtime.next = cron.next_after(&zone, now);
+            }
+        }
+    }
+    due
+}
+
+/// Inicia a thread que dispara os timers e grupos agendados.
+pub fn start_schedule_runner(app: AppHandle) {
+    std::thread::spawn(move || {
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        std::thread::sleep(std::time::Duration::from_secs(STARTUP_DELAY_SECS));
+        println!("[SCHEDULE] Scheduler started");
+
+        let mut missed_since = load_last_seen(&app);
+        let mut entries = load_entries(&app);
+        let mut runtimes: HashMap<String, ScheduleRuntime> = HashMap::new();
+        let mut last_reload = Utc::now();
+        let mut last_saved = Utc::now() - Duration::seconds(LAST_SEEN_SAVE_SECS);
+
+        loop {
+            let now = Utc::now();
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst)
+                || now - last_reload >= Duration::seconds(CONFIG_RELOAD_SECS)
+            {
+                entries = load_entries(&app);
+                last_reload = now;
+            }
+
+            let due = sync_and_collect_due(&mut runtimes, &entries, now, missed_since.take());
+            for entry in &due {
+                fire(&app, entry);
+            }
+
+            // Depois de um disparo grava na hora, para um reinício logo em seguida
+            // não tratar o mesmo horário como perdido e disparar de novo
+            if !due.is_empty() || now - last_saved >= Duration::seconds(LAST_SEEN_SAVE_SECS) {
+                save_last_seen(&app, now);
+                last_saved = now;
+            }
+
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn bits(values: &[u32]) -> u64 {
+        values.iter().fold(0, |mask, v| mask | 1 << v)
+    }
+
+    fn next(cron: &str, zone: &Zone, after: DateTime<Utc>) -> DateTime<Utc> {
+        CronExpr::parse(cron).unwrap().next_after(zone, after).unwrap()
+    }
+
+    #[test]
+    fn parses_ranges_steps_and_lists() {
+        assert_eq!(parse_field("*", 0, 6, &[], 0), Ok(bits(&[0, 1, 2, 3, 4, 5, 6])));
+        assert_eq!(parse_field("1-5", 0, 59, &[], 0), Ok(bits(&[1, 2, 3, 4, 5])));
+        assert_eq!(parse_field("*/15", 0, 59, &[], 0), Ok(bits(&[0, 15, 30, 45])));
+        assert_eq!(parse_field("10-30/10", 0, 59, &[], 0), Ok(bits(&[10, 20, 30])));
+        assert_eq!(parse_field("5/20", 0, 59, &[], 0), Ok(bits(&[5, 25, 45])));
+        assert_eq!(parse_field("1,3,5", 0, 59, &[], 0), Ok(bits(&[1, 3, 5])));
+        assert_eq!(parse_field("0,20-22,*/30", 0, 59, &[], 0), Ok(bits(&[0, 20, 21, 22, 30])));
+    }
+
+    #[test]
+    fn parses_month_and_weekday_names() {
+        assert_eq!(parse_field("jan,DEC", 1, 12, &MONTH_NAMES, 1), Ok(bits(&[1, 12])));
+        assert_eq!(parse_field("mon-fri", 0, 7, &WEEKDAY_NAMES, 0), Ok(bits(&[1, 2, 3, 4, 5])));
+        assert_eq!(CronExpr::parse("0 9 * * sun"), CronExpr::parse("0 9 * * 0"));
+    }
+
+    #[test]
+    fn seven_is_sunday() {
+        assert_eq!(CronExpr::parse("0 9 * * 7"), CronExpr::parse("0 9 * * 0"));
+        assert_eq!(CronExpr::parse("0 9 * * 5-7"), CronExpr::parse("0 9 * * 0,5,6"));
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        for cron in [
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * 32 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "abc * * * *",
+            "* * * foo *",
+            "* * * * mon-",
+        ] {
+            assert!(CronExpr::parse(cron).is_err(), "{} should be rejected", cron);
+        }
+    }
+
+    #[test]
+    fn next_after_is_strictly_later() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(next("*/15 * * * *", &zone, utc(2025, 6, 10, 10, 7)), utc(2025, 6, 10, 10, 15));
+        assert_eq!(next("*/15 * * * *", &zone, utc(2025, 6, 10, 10, 15)), utc(2025, 6, 10, 10, 30));
+        assert_eq!(next("0 0 * * *", &zone, utc(2025, 12, 31, 23, 59)), utc(2026, 1, 1, 0, 0));
+    }
+
+    #[test]
+    fn next_after_skips_to_matching_weekday_and_month() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        // 2025-06-13 é uma sexta: o próximo dia útil é segunda
+        assert_eq!(next("30 9 * * 1-5", &zone, utc(2025, 6, 13, 10, 0)), utc(2025, 6, 16, 9, 30));
+        assert_eq!(next("0 0 1 jan *", &zone, utc(2025, 6, 1, 0, 0)), utc(2026, 1, 1, 0, 0));
+        assert_eq!(next("0 0 29 2 *", &zone, utc(2025, 3, 1, 0, 0)), utc(2028, 2, 29, 0, 0));
+    }
+
+    #[test]
+    fn day_of_month_or_day_of_week_when_both_restricted() {
+        let zone = Zone::Named(chrono_tz::UTC);
+        // Dia 13 OU sexta: 2025-07-11 é sexta e 2025-07-13 é domingo
+        assert_eq!(next("0 12 13 * 5", &zone, utc(2025, 7, 10, 0, 0)), utc(2025, 7, 11, 12, 0));
+        assert_eq!(next("0 12 13 * 5", &zone, utc(2025, 7, 11, 12, 0)), utc(2025, 7, 13, 12, 0));
+        // Só um dos campos restrito: vale apenas ele
+        assert_eq!(next("0 12 13 * *", &zone, utc(2025, 7, 10, 0, 0)), utc(2025, 7, 13, 12, 0));
+        assert_eq!(next("0 12 * * 5", &zone, utc(2025, 7, 11, 12, 0)), utc(2025, 7, 18, 12, 0));
+        // `*/2` no dia do mês conta como irrestrito, então só a sexta vale
+        assert_eq!(next("0 12 */2 * 5", &zone, utc(2025, 7, 11, 12, 0)), utc(2025, 7, 18, 12, 0));
+    }
+
+    #[test]
+    fn spring_forward_fires_after_the_gap() {
+        // Em Nova York, 2025-03-09 pula de 02:00 para 03:00 (EST -> EDT)
+        let zone = Zone::Named(New_York);
+        let fired = next("30 2 * * *", &zone, utc(2025, 3, 8, 12, 0));
+        assert_eq!(fired, utc(2025, 3, 9, 7, 0));
+        assert_eq!(next("30 2 * * *", &zone, fired), utc(2025, 3, 10, 6, 30));
+        // Horários depois do salto não mudam
+        assert_eq!(next("0 3 * * *", &zone, utc(2025, 3, 8, 12, 0)), utc(2025, 3, 9, 7, 0));
+    }
+
+    #[test]
+    fn fall_back_fires_repeated_time_once() {
+        // Em Nova York, 2025-11-02 repete 01:00-02:00 (EDT -> EST)
+        let zone = Zone::Named(New_York);
+        let fired = next("30 1 * * *", &zone, utc(2025, 11, 1, 12, 0));
+        assert_eq!(fired, utc(2025, 11, 2, 5, 30));
+        assert_eq!(next("30 1 * * *", &zone, fired), utc(2025, 11, 3, 6, 30));
+        // Durante a segunda passagem pela hora repetida nada dispara de novo
+        assert_eq!(next("*/30 * * * *", &zone, utc(2025, 11, 2, 6, 15)), utc(2025, 11, 2, 7, 0));
+    }
+}
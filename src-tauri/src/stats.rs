@@ -0,0 +1,542 @@
+use crate::history::{self, HistoryEntry, HistoryQuery, RunOutcome};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use tauri::AppHandle;
+
+/// Horas mais usadas listadas em cada resumo
+const TOP_HOURS: usize = 3;
+
+/// Intervalo das estatísticas em milissegundos desde a epoch; ausente = sem limite.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsRange {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsGroupBy {
+    #[default]
+    Timer,
+    Day,
+    Week,
+    /// Rótulo da execução; execuções sem rótulo ficam juntas
+    Label,
+}
+
+/// Números de um conjunto de execuções.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSummary {
+    pub runs: u32,
+    pub completed: u32,
+    pub cancelled: u32,
+    /// Concluídas / (concluídas + canceladas), de 0 a 1
+    pub completion_rate: Option<f64>,
+    pub total_seconds: u64,
+    pub average_seconds: Option<u64>,
+    /// Maior sequência de dias seguidos com pelo menos uma execução concluída
+    pub longest_streak_days: u32,
+    /// Execuções iniciadas em cada hora do dia (horário local)
+    pub hours: Vec<u32>,
+    /// Horas com mais execuções, da mais usada para a menos
+    pub top_hours: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsGroup {
+    /// Id do timer, data (`2026-10-18`), semana ISO (`2026-W42`) ou rótulo (vazio = sem rótulo)
+    pub key: String,
+    pub label: String,
+    pub summary: StatsSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsReport {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub group_by: StatsGroupBy,
+    pub overall: StatsSummary,
+    pub groups: Vec<StatsGroup>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Completed,
+    Cancelled,
+}
+
+/// Uma execução reconstruída a partir dos eventos do histórico.
+#[derive(Debug, Clone)]
+pub(crate) struct Run {
+    pub timer_id: String,
+    pub name: Option<String>,
+    pub started: DateTime<Local>,
+    /// Último evento terminal da execução
+    pub ended: DateTime<Local>,
+    pub outcome: Outcome,
+    pub seconds: u64,
+    pub planned_seconds: Option<u64>,
+    pub repeat_count: u32,
+    pub label: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Execuções do histórico (as mesmas de `get_run_history`) com horários locais.
+pub(crate) fn collect_runs(entries: &[HistoryEntry]) -> Vec<Run> {
+    history::collect_runs(entries)
+        .into_iter()
+        .filter_map(|record| {
+            Some(Run {
+                started: Local.timestamp_millis_opt(record.started_at).single()?,
+                ended: Local.timestamp_millis_opt(record.ended_at).single()?,
+                outcome: match record.outcome {
+                    RunOutcome::Finished => Outcome::Completed,
+                    RunOutcome::Cancelled => Outcome::Cancelled,
+                },
+                seconds: record.actual_seconds,
+                planned_seconds: record.durations_seconds.last().copied(),
+                repeat_count: record.repeat_count,
+                timer_id: record.timer_id,
+                name: record.name,
+                label: record.label,
+                note: record.note,
+            })
+        })
+        .collect()
+}
+
+fn longest_streak(days: &BTreeSet<NaiveDate>) -> u32 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        current = match previous {
+            Some(prev) if *day - prev == Duration::days(1) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(*day);
+    }
+    longest
+}
+
+fn summarize(runs: &[&Run]) -> StatsSummary {
+    let mut summary = StatsSummary {
+        hours: vec![0; 24],
+        ..Default::default()
+    };
+    let mut completed_days = BTreeSet::new();
+    for run in runs {
+        summary.runs += 1;
+        summary.total_seconds += run.seconds;
+        summary.hours[run.started.hour() as usize] += 1;
+        match run.outcome {
+            Outcome::Completed => {
+                summary.completed += 1;
+                completed_days.insert(run.started.date_naive());
+            }
+            Outcome::Cancelled => summary.cancelled += 1,
+        }
+    }
+    if summary.runs > 0 {
+        summary.completion_rate = Some(summary.completed as f64 / summary.runs as f64);
+        summary.average_seconds = Some(summary.total_seconds / summary.runs as u64);
+    }
+    summary.longest_streak_days = longest_streak(&completed_days);
+
+    let mut ranked: Vec<u32> = (0..24).filter(|h| summary.hours[*h as usize] > 0).collect();
+    ranked.sort_by_key(|h| (std::cmp::Reverse(summary.hours[*h as usize]), *h));
+    ranked.truncate(TOP_HOURS);
+    summary.top_hours = ranked;
+    summary
+}
+
+fn iso_week_key(date: NaiveDate) -> String {
+    let week = date.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+fn group_key(run: &Run, group_by: StatsGroupBy) -> (String, String) {
+    match group_by {
+        StatsGroupBy::Timer => (
+            run.timer_id.clone(),
+            run.name.clone().unwrap_or_else(|| run.timer_id.clone()),
+        ),
+        StatsGroupBy::Day => {
+            let date = run.started.date_naive();
+            (date.to_string(), date.format("%a %d %b %Y").to_string())
+        }
+        StatsGroupBy::Week => {
+            let date = run.started.date_naive();
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            (iso_week_key(date), format!("Week of {}", monday.format("%d %b %Y")))
+        }
+        StatsGroupBy::Label => (
+            run.label.clone().unwrap_or_default(),
+            run.label.clone().unwrap_or_else(|| "No label".to_string()),
+        ),
+    }
+}
+
+/// Execuções iniciadas no intervalo. O filtro vale para a execução inteira: uma que cruza
+/// o limite entra (ou não) com todos os seus eventos, nunca dividida.
+fn runs_in_range(runs: Vec<Run>, range: StatsRange) -> Vec<Run> {
+    runs.into_iter()
+        .filter(|run| range.from.is_none_or(|from| run.started.timestamp_millis() >= from))
+        .filter(|run| range.to.is_none_or(|to| run.started.timestamp_millis() <= to))
+        .collect()
+}
+
+/// Estatísticas de uso no intervalo, agrupadas por timer, dia ou semana.
+pub fn get_stats(app: &AppHandle, range: StatsRange, group_by: StatsGroupBy) -> StatsReport {
+    // Eventos de execuções iniciadas no intervalo acontecem depois de `from`, mas podem
+    // passar de `to`
+    let entries = history::query(
+        app,
+        &HistoryQuery {
+            from: range.from,
+            limit: Some(usize::MAX),
+            ..Default::default()
+        },
+    );
+    build_report(&entries, range, group_by)
+}
+
+fn build_report(entries: &[HistoryEntry], range: StatsRange, group_by: StatsGroupBy) -> StatsReport {
+    let runs = runs_in_range(collect_runs(entries), range);
+    let mut grouped: BTreeMap<String, (String, Vec<&Run>)> = BTreeMap::new();
+    for run in &runs {
+        let (key, label) = group_key(run, group_by);
+        let group = grouped.entry(key).or_insert_with(|| (label.clone(), Vec::new()));
+        // Mantém o nome mais recente do timer
+        group.0 = label;
+        group.1.push(run);
+    }
+
+    let mut groups: Vec<StatsGroup> = grouped
+        .into_iter()
+        .map(|(key, (label, runs))| StatsGroup {
+            key,
+            label,
+            summary: summarize(&runs),
+        })
+        .collect();
+    if matches!(group_by, StatsGroupBy::Timer | StatsGroupBy::Label) {
+        groups.sort_by_key(|g| std::cmp::Reverse(g.summary.total_seconds));
+    }
+
+    StatsReport {
+        from: range.from,
+        to: range.to,
+        group_by,
+        overall: summarize(&runs.iter().collect::<Vec<_>>()),
+        groups,
+    }
+}
+
+pub(crate) fn format_duration(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.0}%", r * 100.0)).unwrap_or_else(|| "–".to_string())
+}
+
+fn format_hours(hours: &[u32]) -> String {
+    if hours.is_empty() {
+        return "–".to_string();
+    }
+    hours
+        .iter()
+        .map(|h| format!("{:02}:00", h))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Resumo semanal em Markdown (segunda a domingo, horário local). `week_offset` 0 = semana
+/// atual, -1 = anterior, e assim por diante.
+pub fn weekly_summary(app: &AppHandle, week_offset: i32) -> String {
+    let today = Local::now().date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64)
+        + Duration::weeks(week_offset as i64);
+    let next_monday = monday + Duration::weeks(1);
+    let millis = |date: NaiveDate| {
+        date.and_hms_opt(0, 0, 0)
+            .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+            .map(|dt| dt.timestamp_millis())
+    };
+    let range = StatsRange {
+        from: millis(monday),
+        to: millis(next_monday).map(|ms| ms - 1),
+    };
+    let by_timer = get_stats(app, range, StatsGroupBy::Timer);
+    let by_day = get_stats(app, range, StatsGroupBy::Day);
+    let overall = &by_timer.overall;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Timer summary: {} – {} ({})\n",
+        monday.format("%d %b"),
+        (next_monday - Duration::days(1)).format("%d %b %Y"),
+        iso_week_key(monday)
+    );
+    if overall.runs == 0 {
+        let _ = writeln!(out, "No timer runs recorded this week.");
+        return out;
+    }
+
+    let _ = writeln!(out, "- **Runs:** {} ({} completed, {} reset)", overall.runs, overall.completed, overall.cancelled);
+    let _ = writeln!(out, "- **Completion rate:** {}", format_rate(overall.completion_rate));
+    let _ = writeln!(out, "- **Total time:** {}", format_duration(overall.total_seconds));
+    let _ = writeln!(
+        out,
+        "- **Average run:** {}",
+        overall.average_seconds.map(format_duration).unwrap_or_else(|| "–".to_string())
+    );
+    let _ = writeln!(out, "- **Longest streak:** {} day(s)", overall.longest_streak_days);
+    let _ = writeln!(out, "- **Busiest hours:** {}", format_hours(&overall.top_hours));
+
+    let _ = writeln!(out, "\n## By timer\n");
+    let _ = writeln!(out, "| Timer | Runs | Completion | Total | Average |");
+    let _ = writeln!(out, "| --- | ---: | ---: | ---: | ---: |");
+    for group in &by_timer.groups {
+        let s = &group.summary;
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            group.label.replace('|', "\\|"),
+            s.runs,
+            format_rate(s.completion_rate),
+            format_duration(s.total_seconds),
+            s.average_seconds.map(format_duration).unwrap_or_else(|| "–".to_string())
+        );
+    }
+
+    let by_label = get_stats(app, range, StatsGroupBy::Label);
+    if by_label.groups.iter().any(|group| !group.key.is_empty()) {
+        let _ = writeln!(out, "\n## By label\n");
+        let _ = writeln!(out, "| Label | Runs | Total |");
+        let _ = writeln!(out, "| --- | ---: | ---: |");
+        for group in &by_label.groups {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                group.label.replace('|', "\\|"),
+                group.summary.runs,
+                format_duration(group.summary.total_seconds)
+            );
+        }
+    }
+
+    let _ = writeln!(out, "\n## By day\n");
+    let _ = writeln!(out, "| Day | Runs | Total |");
+    let _ = writeln!(out, "| --- | ---: | ---: |");
+    for group in &by_day.groups {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} |",
+            group.label,
+            group.summary.runs,
+            format_duration(group.summary.total_seconds)
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::HistoryEventKind;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        // Outubro de 2026: dia 18 é domingo, 19 é segunda (semana ISO 43)
+        Local.with_ymd_and_hms(2026, 10, day, hour, minute, 0).unwrap()
+    }
+
+    fn entry(timer_id: &str, event: HistoryEventKind, started: DateTime<Local>, when: DateTime<Local>) -> HistoryEntry {
+        HistoryEntry {
+            timer_id: timer_id.to_string(),
+            name: Some(timer_id.to_uppercase()),
+            event,
+            at: when.timestamp_millis(),
+            run_started_at: Some(started.timestamp_millis()),
+            planned_seconds: Some(600),
+            actual_seconds: (event != HistoryEventKind::Started)
+                .then(|| (when - started).num_seconds() as u64),
+            repeat_count: 0,
+            source: None,
+            instance: None,
+            label: None,
+            note: None,
+            random_seed: None,
+            random_draw: None,
+        }
+    }
+
+    /// Execução de `minutes` minutos iniciada em `started`, com início e fim no histórico
+    fn run(timer_id: &str, started: DateTime<Local>, minutes: i64, end: HistoryEventKind) -> Vec<HistoryEntry> {
+        vec![
+            entry(timer_id, HistoryEventKind::Started, started, started),
+            entry(timer_id, end, started, started + Duration::minutes(minutes)),
+        ]
+    }
+
+    fn all_time() -> StatsRange {
+        StatsRange::default()
+    }
+
+    #[test]
+    fn collects_one_run_per_start_and_drops_unfinished_ones() {
+        let start = at(14, 9, 0);
+        let mut entries = run("tea", start, 10, HistoryEventKind::Finished);
+        // Reconhecimento do overtime e reset depois dele continuam contando como concluída
+        entries.push(entry("tea", HistoryEventKind::Acknowledged, start, start + Duration::minutes(12)));
+        entries.push(entry("tea", HistoryEventKind::Cancelled, start, start + Duration::minutes(13)));
+        entries.extend(run("egg", at(14, 10, 0), 3, HistoryEventKind::Cancelled));
+        // Ainda rodando
+        entries.push(entry("egg", HistoryEventKind::Started, at(14, 11, 0), at(14, 11, 0)));
+        // Instância paralela do mesmo timer, no mesmo instante, é outra execução
+        let mut stacked = run("tea", start, 5, HistoryEventKind::Finished);
+        stacked.iter_mut().for_each(|e| e.instance = Some(1));
+        entries.extend(stacked);
+
+        let runs = collect_runs(&entries);
+        let summary: Vec<(&str, Outcome, u64)> =
+            runs.iter().map(|r| (r.timer_id.as_str(), r.outcome, r.seconds)).collect();
+        assert_eq!(summary.len(), 3);
+        assert!(summary.contains(&("tea", Outcome::Completed, 13 * 60)));
+        assert!(summary.contains(&("tea", Outcome::Completed, 5 * 60)));
+        assert!(summary.contains(&("egg", Outcome::Cancelled, 3 * 60)));
+    }
+
+    #[test]
+    fn summarize_counts_rates_and_busiest_hours() {
+        let mut entries = Vec::new();
+        entries.extend(run("a", at(14, 9, 0), 10, HistoryEventKind::Finished));
+        entries.extend(run("a", at(14, 9, 30), 20, HistoryEventKind::Finished));
+        entries.extend(run("a", at(14, 14, 0), 6, HistoryEventKind::Cancelled));
+        entries.extend(run("a", at(15, 7, 0), 4, HistoryEventKind::Finished));
+        let report = build_report(&entries, all_time(), StatsGroupBy::Timer);
+        let s = &report.overall;
+
+        assert_eq!((s.runs, s.completed, s.cancelled), (4, 3, 1));
+        assert_eq!(s.completion_rate, Some(0.75));
+        assert_eq!(s.total_seconds, 40 * 60);
+        assert_eq!(s.average_seconds, Some(10 * 60));
+        assert_eq!(s.hours[9], 2);
+        // Empates ficam na ordem das horas
+        assert_eq!(s.top_hours, vec![9, 7, 14]);
+        assert_eq!(s.longest_streak_days, 2);
+    }
+
+    #[test]
+    fn empty_history_has_no_rates() {
+        let report = build_report(&[], all_time(), StatsGroupBy::Day);
+        assert_eq!(report.overall.runs, 0);
+        assert_eq!(report.overall.completion_rate, None);
+        assert_eq!(report.overall.average_seconds, None);
+        assert!(report.groups.is_empty());
+    }
+
+    #[test]
+    fn a_run_straddling_the_range_counts_whole_on_its_start_side() {
+        let mut entries = Vec::new();
+        // Começa antes de `from` e termina dentro do intervalo: fica de fora inteira
+        entries.extend(run("early", at(14, 23, 50), 20, HistoryEventKind::Finished));
+        // Começa dentro e termina depois de `to`: entra com o tempo todo
+        entries.extend(run("late", at(15, 23, 50), 30, HistoryEventKind::Finished));
+        let range = StatsRange {
+            from: Some(at(15, 0, 0).timestamp_millis()),
+            to: Some(at(16, 0, 0).timestamp_millis() - 1),
+        };
+
+        let report = build_report(&entries, range, StatsGroupBy::Timer);
+        let timers: Vec<&str> = report.groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(timers, vec!["late"]);
+        assert_eq!(report.overall.completed, 1);
+        assert_eq!(report.overall.total_seconds, 30 * 60);
+    }
+
+    #[test]
+    fn streaks_cross_midnight_and_week_boundaries() {
+        let days = |list: &[(i32, u32, u32)]| -> BTreeSet<NaiveDate> {
+            list.iter().map(|(y, m, d)| NaiveDate::from_ymd_opt(*y, *m, *d).unwrap()).collect()
+        };
+        let cases = [
+            (days(&[]), 0),
+            (days(&[(2026, 10, 14)]), 1),
+            // Sábado, domingo e segunda: atravessa a semana ISO
+            (days(&[(2026, 10, 17), (2026, 10, 18), (2026, 10, 19)]), 3),
+            (days(&[(2026, 10, 10), (2026, 10, 12), (2026, 10, 13)]), 2),
+            // Virada de mês e de ano
+            (days(&[(2026, 10, 31), (2026, 11, 1)]), 2),
+            (days(&[(2025, 12, 31), (2026, 1, 1), (2026, 1, 2)]), 3),
+        ];
+        for (set, expected) in cases {
+            assert_eq!(longest_streak(&set), expected, "{:?}", set);
+        }
+
+        // Uma concluída às 23:50 e outra às 00:10 contam dois dias seguidos;
+        // canceladas não mantêm a sequência
+        let mut entries = Vec::new();
+        entries.extend(run("a", at(18, 23, 50), 5, HistoryEventKind::Finished));
+        entries.extend(run("a", at(19, 0, 10), 5, HistoryEventKind::Finished));
+        entries.extend(run("a", at(20, 9, 0), 5, HistoryEventKind::Cancelled));
+        entries.extend(run("a", at(21, 9, 0), 5, HistoryEventKind::Finished));
+        let report = build_report(&entries, all_time(), StatsGroupBy::Timer);
+        assert_eq!(report.overall.longest_streak_days, 2);
+    }
+
+    #[test]
+    fn groups_runs_by_timer_day_week_and_label() {
+        let mut entries = Vec::new();
+        entries.extend(run("tea", at(18, 23, 50), 30, HistoryEventKind::Finished));
+        entries.extend(run("egg", at(19, 8, 0), 5, HistoryEventKind::Finished));
+        let mut labelled = run("tea", at(19, 9, 0), 10, HistoryEventKind::Finished);
+        labelled[1].label = Some("invoices".to_string());
+        labelled[1].name = Some("Green tea".to_string());
+        entries.extend(labelled);
+
+        let keys = |group_by| -> Vec<(String, String, u32)> {
+            build_report(&entries, all_time(), group_by)
+                .groups
+                .into_iter()
+                .map(|g| (g.key, g.label, g.summary.runs))
+                .collect()
+        };
+        let owned = |list: &[(&str, &str, u32)]| -> Vec<(String, String, u32)> {
+            list.iter().map(|(k, l, n)| (k.to_string(), l.to_string(), *n)).collect()
+        };
+
+        // Por timer: mais tempo primeiro, com o nome mais recente
+        assert_eq!(keys(StatsGroupBy::Timer), owned(&[("tea", "Green tea", 2), ("egg", "EGG", 1)]));
+        // Pelo dia em que a execução começou
+        assert_eq!(
+            keys(StatsGroupBy::Day),
+            owned(&[("2026-10-18", "Sun 18 Oct 2026", 1), ("2026-10-19", "Mon 19 Oct 2026", 2)])
+        );
+        assert_eq!(
+            keys(StatsGroupBy::Week),
+            owned(&[("2026-W42", "Week of 12 Oct 2026", 1), ("2026-W43", "Week of 19 Oct 2026", 2)])
+        );
+        assert_eq!(
+            keys(StatsGroupBy::Label),
+            owned(&[("", "No label", 2), ("invoices", "invoices", 1)])
+        );
+    }
+}
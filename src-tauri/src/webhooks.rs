@@ -0,0 +1,545 @@
+use crate::commands::load_config;
+use crate::engine::{self, TimerEvent, TimerEventKind};
+use crate::history::HistoryEntry;
+use crate::hooks;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Listener};
+
+/// Tentativas por entrega quando `maxAttempts` não está configurado
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+/// Espera antes da segunda tentativa; dobra a cada falha até `MAX_BACKOFF`
+#[cfg(not(test))]
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+#[cfg(test)]
+const INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(120);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Intervalo de verificação dos limiares de aviso das contagens regressivas
+const WARNING_CHECK_MS: u64 = 500;
+
+/// Destino de webhook em `webhooks` no config.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookTarget {
+    id: String,
+    name: Option<String>,
+    url: String,
+    /// Eventos de timer (`started`, `finished`...) e `warning`; vazio = todos
+    #[serde(default)]
+    events: Vec<String>,
+    /// Só estes timers; vazio = todos
+    #[serde(default)]
+    timer_ids: Vec<String>,
+    /// Segundos restantes que disparam `warning` nas contagens regressivas (ex.: [60, 10])
+    #[serde(default)]
+    warn_at_seconds: Vec<u64>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    /// Assina o corpo com HMAC-SHA256 no cabeçalho `X-Timer-Signature-256`
+    secret: Option<String>,
+    max_attempts: Option<u32>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Situação da última entrega de um webhook.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookStatus {
+    pub id: String,
+    pub delivered: u32,
+    pub failed: u32,
+    /// Entregas ainda tentando (incluindo as esperando o backoff)
+    pub pending: u32,
+    pub last_event: Option<String>,
+    pub last_delivery_id: Option<String>,
+    pub last_attempt_at: Option<i64>,
+    pub last_attempts: u32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<i64>,
+}
+
+static STATUS: LazyLock<Mutex<HashMap<String, WebhookStatus>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static DELIVERY_COUNTER: AtomicU64 = AtomicU64::new(0);
+static CONFIG_DIRTY: AtomicBool = AtomicBool::new(false);
+/// (timer, instância, início, fase) de uma contagem regressiva
+type CountdownKey = (String, u32, i64, u32);
+
+fn parse_targets(config: &Value) -> Vec<WebhookTarget> {
+    config
+        .get("webhooks")
+        .and_then(|w| w.as_array())
+        .map(|targets| {
+            targets
+                .iter()
+                .filter_map(|target| match serde_json::from_value::<WebhookTarget>(target.clone()) {
+                    Ok(target) => Some(target),
+                    Err(e) => {
+                        println!("[WEBHOOK] WARNING: Ignoring invalid webhook: {}", e);
+                        None
+                    }
+                })
+                .filter(|target| target.enabled && !target.url.trim().is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Valida `webhooks` antes de salvar a configuração.
+pub fn validate_webhooks(config: &Value) -> Result<(), String> {
+    let Some(targets) = config.get("webhooks").and_then(|w| w.as_array()) else {
+        return Ok(());
+    };
+    for target in targets {
+        let target: WebhookTarget = serde_json::from_value(target.clone()).map_err(|e| e.to_string())?;
+        let url = target.url.trim().to_ascii_lowercase();
+        let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"));
+        if rest.is_none_or(|rest| rest.is_empty() || rest.contains(char::is_whitespace)) {
+            return Err(format!("Webhook {}: use an http:// or https:// URL", target.id));
+        }
+        if let Some(event) = target
+            .events
+            .iter()
+            .find(|e| *e != "warning" && serde_json::from_value::<TimerEventKind>(json!(e)).is_err())
+        {
+            return Err(format!("Webhook {}: unknown event {}", target.id, event));
+        }
+        if target.max_attempts == Some(0) {
+            return Err(format!("Webhook {}: maxAttempts must be at least 1", target.id));
+        }
+    }
+    Ok(())
+}
+
+fn wants(target: &WebhookTarget, event: &str, timer_id: &str) -> bool {
+    (target.events.is_empty() || target.events.iter().any(|e| e == event))
+        && (target.timer_ids.is_empty() || target.timer_ids.iter().any(|id| id == timer_id))
+}
+
+fn update_status(id: &str, update: impl FnOnce(&mut WebhookStatus)) {
+    let mut status = STATUS.lock().unwrap();
+    let entry = status.entry(id.to_string()).or_insert_with(|| WebhookStatus {
+        id: id.to_string(),
+        ..Default::default()
+    });
+    update(entry);
+}
+
+/// `sha256=<hex>` do HMAC-SHA256 do corpo
+fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+enum AttemptError {
+    /// Vale tentar de novo (rede, 5xx, 429)
+    Retry(Option<u16>, String),
+    /// Recusado pelo servidor (outros 4xx); não adianta repetir
+    Fatal(Option<u16>, String),
+}
+
+fn post(target: &WebhookTarget, event: &str, delivery_id: &str, body: &str) -> Result<u16, AttemptError> {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+    let mut request = agent
+        .post(&target.url)
+        .set("Content-Type", "application/json")
+        .set("User-Agent", concat!("overlay-timer/", env!("CARGO_PKG_VERSION")))
+        .set("X-Timer-Event", event)
+        .set("X-Timer-Delivery", delivery_id);
+    for (name, value) in &target.headers {
+        request = request.set(name, value);
+    }
+    if let Some(secret) = target.secret.as_deref().filter(|s| !s.is_empty()) {
+        request = request.set("X-Timer-Signature-256", &signature(secret, body.as_bytes()));
+    }
+
+    match request.send_string(body) {
+        Ok(response) => Ok(response.status()),
+        Err(ureq::Error::Status(code, response)) => {
+            let text = response.into_string().unwrap_or_default();
+            let message = format!("HTTP {}: {}", code, text.chars().take(200).collect::<String>());
+            if code >= 500 || code == 429 {
+                Err(AttemptError::Retry(Some(code), message))
+            } else {
+                Err(AttemptError::Fatal(Some(code), message))
+            }
+        }
+        Err(e) => Err(AttemptError::Retry(None, e.to_string())),
+    }
+}
+
+/// Monta o corpo de uma entrega e a conta como pendente. Retorna (id da entrega, corpo).
+fn new_delivery(target: &WebhookTarget, event: &str, data: Value) -> (String, String) {
+    let delivery_id = format!(
+        "{}-{}",
+        Utc::now().timestamp_millis(),
+        DELIVERY_COUNTER.fetch_add(1, Ordering::SeqCst)
+    );
+    let body = json!({
+        "event": event,
+        "deliveryId": delivery_id,
+        "sentAt": Utc::now().timestamp_millis(),
+        "webhookId": target.id,
+        "data": data,
+    })
+    .to_string();
+    update_status(&target.id, |s| s.pending += 1);
+    (delivery_id, body)
+}
+
+/// Entrega em segundo plano, tentando de novo com backoff exponencial.
+fn deliver(target: WebhookTarget, event: String, data: Value) {
+    let (delivery_id, body) = new_delivery(&target, &event, data);
+    std::thread::spawn(move || send_with_retries(&target, &event, &delivery_id, &body));
+}
+
+/// Tenta a entrega até `maxAttempts` vezes, atualizando o status a cada tentativa.
+fn send_with_retries(target: &WebhookTarget, event: &str, delivery_id: &str, body: &str) {
+    let name = target.name.clone().unwrap_or_else(|| target.id.clone());
+    let max_attempts = target.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1);
+    let mut backoff = INITIAL_BACKOFF;
+    for attempt in 1..=max_attempts {
+        let result = post(target, event, delivery_id, body);
+        let (code, error, retry) = match &result {
+            Ok(code) => (Some(*code), None, false),
+            Err(AttemptError::Retry(code, e)) => (*code, Some(e.clone()), attempt < max_attempts),
+            Err(AttemptError::Fatal(code, e)) => (*code, Some(e.clone()), false),
+        };
+        update_status(&target.id, |s| {
+            s.last_event = Some(event.to_string());
+            s.last_delivery_id = Some(delivery_id.to_string());
+            s.last_attempt_at = Some(Utc::now().timestamp_millis());
+            s.last_attempts = attempt;
+            s.last_status_code = code;
+            s.last_error = error.clone();
+            if !retry {
+                s.pending = s.pending.saturating_sub(1);
+                if error.is_none() {
+                    s.delivered += 1;
+                    s.last_success_at = s.last_attempt_at;
+                } else {
+                    s.failed += 1;
+                }
+            }
+        });
+
+        match error {
+            None => {
+                println!("[WEBHOOK] {} delivered {} ({}) on attempt {}", name, event, delivery_id, attempt);
+                return;
+            }
+            Some(e) if retry => {
+                println!(
+                    "[WEBHOOK] WARNING: {} attempt {}/{} for {} failed: {}; retrying in {}s",
+                    name,
+                    attempt,
+                    max_attempts,
+                    event,
+                    e,
+                    backoff.as_secs()
+                );
+                std::thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            Some(e) => {
+                println!("[WEBHOOK] WARNING: {} gave up on {} after {} attempt(s): {}", name, event, attempt, e);
+                return;
+            }
+        }
+    }
+}
+
+/// Envia o evento de timer aos webhooks interessados. Não bloqueia o motor.
+pub fn on_timer_event(config: &Value, event: &TimerEvent, entry: Option<&HistoryEntry>) {
+    let targets = parse_targets(config);
+    if targets.is_empty() {
+        return;
+    }
+    let data = hooks::event_payload(config, event, entry);
+    let name = data["event"].as_str().unwrap_or_default().to_string();
+    for target in targets {
+        if wants(&target, &name, &event.timer_id) {
+            deliver(target, name.clone(), data.clone());
+        }
+    }
+}
+
+fn load_targets(app: &AppHandle) -> (Value, Vec<WebhookTarget>) {
+    let config = load_config(app.clone())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .unwrap_or(Value::Null);
+    let targets = parse_targets(&config);
+    (config, targets)
+}
+
+/// Acompanha as contagens regressivas e envia `warning` quando o restante cruza
+/// um dos `warnAtSeconds` de cada webhook.
+pub fn start_webhook_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        app.listen_any("config-changed", |_| CONFIG_DIRTY.store(true, Ordering::SeqCst));
+        let (mut config, mut targets) = load_targets(&app);
+        // Restante visto na primeira leitura: só limiares abaixo dele são "cruzados"
+        let mut first_seen: HashMap<CountdownKey, f64> = HashMap::new();
+        let mut warned: HashSet<(String, CountdownKey, u64)> = HashSet::new();
+        loop {
+            if CONFIG_DIRTY.swap(false, Ordering::SeqCst) {
+                (config, targets) = load_targets(&app);
+            }
+
+            let countdowns = engine::running_countdowns();
+            let active: HashSet<CountdownKey> = countdowns
+                .iter()
+                .map(|(t, i, s, p, _)| (t.clone(), *i, *s, *p))
+                .collect();
+            // Esquece execuções que já terminaram ou mudaram de fase
+            first_seen.retain(|key, _| active.contains(key));
+            warned.retain(|(_, key, _)| active.contains(key));
+
+            for target in targets.iter().filter(|t| !t.warn_at_seconds.is_empty()) {
+                for (timer_id, instance, started_at, phase, remaining) in &countdowns {
+                    if !wants(target, "warning", timer_id) {
+                        continue;
+                    }
+                    let run_key: CountdownKey = (timer_id.clone(), *instance, *started_at, *phase);
+                    let first = *first_seen.entry(run_key.clone()).or_insert(*remaining);
+                    for threshold in &target.warn_at_seconds {
+                        let threshold_secs = *threshold as f64;
+                        if *remaining > threshold_secs || first <= threshold_secs {
+                            continue;
+                        }
+                        if !warned.insert((target.id.clone(), run_key.clone(), *threshold)) {
+                            continue;
+                        }
+                        let timer = TimerEvent {
+                            timer_id: timer_id.clone(),
+                            event: TimerEventKind::Started,
+                            phase: Some(*phase),
+                            remaining: Some(remaining.round() as u64),
+                            instance: Some(*instance),
+                            seed: None,
+                            draw: None,
+                            detail: None,
+                        };
+                        let mut data = hooks::event_payload(&config, &timer, None);
+                        data["event"] = json!("warning");
+                        data["runStartedAt"] = json!(started_at);
+                        data["thresholdSeconds"] = json!(threshold);
+                        deliver(target.clone(), "warning".to_string(), data);
+                    }
+                }
+            }
+            std::thread::sleep(Duration::from_millis(WARNING_CHECK_MS));
+        }
+    });
+}
+
+/// Situação de cada webhook que já tentou entregar algo nesta sessão.
+pub fn webhook_status() -> Vec<WebhookStatus> {
+    STATUS.lock().unwrap().values().cloned().collect()
+}
+
+/// Envia um evento `test` para o webhook, ignorando o filtro de eventos.
+pub fn test_webhook(webhook: &Value) -> Result<(), String> {
+    let target: WebhookTarget =
+        serde_json::from_value(webhook.clone()).map_err(|e| format!("Invalid webhook: {}", e))?;
+    if target.url.trim().is_empty() {
+        return Err("Webhook URL is empty".to_string());
+    }
+    deliver(
+        target,
+        "test".to_string(),
+        json!({ "message": "Test delivery from overlay-timer", "at": Utc::now().timestamp_millis() }),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    /// Pedido recebido pelo servidor de teste: cabeçalhos (nomes em minúsculas) e corpo
+    type Received = Arc<Mutex<Vec<(HashMap<String, String>, String)>>>;
+
+    /// Servidor HTTP local que responde cada pedido com o próximo status da lista
+    /// (repetindo o último) e guarda os pedidos recebidos.
+    fn serve(statuses: Vec<u16>) -> (String, Received) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received: Received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        std::thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut headers = HashMap::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+                    }
+                }
+                let length = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                log.lock().unwrap().push((headers, String::from_utf8(body).unwrap()));
+
+                let status = statuses[index.min(statuses.len() - 1)];
+                let _ = write!(stream, "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+            }
+        });
+        (url, received)
+    }
+
+    fn target(id: &str, url: &str, secret: Option<&str>, max_attempts: u32) -> WebhookTarget {
+        WebhookTarget {
+            id: id.to_string(),
+            name: None,
+            url: url.to_string(),
+            events: Vec::new(),
+            timer_ids: Vec::new(),
+            warn_at_seconds: Vec::new(),
+            headers: HashMap::new(),
+            secret: secret.map(|s| s.to_string()),
+            max_attempts: Some(max_attempts),
+            enabled: true,
+        }
+    }
+
+    /// Entrega de forma síncrona e devolve o status do webhook ao final.
+    fn run(target: &WebhookTarget) -> WebhookStatus {
+        let (delivery_id, body) = new_delivery(target, "finished", json!({ "timerId": "tea" }));
+        send_with_retries(target, "finished", &delivery_id, &body);
+        STATUS.lock().unwrap()[&target.id].clone()
+    }
+
+    #[test]
+    fn signature_matches_known_hmac_sha256() {
+        // RFC 4231, caso de teste 2
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn signs_the_exact_body_sent() {
+        let (url, received) = serve(vec![200]);
+        let status = run(&target("signed", &url, Some("s3cret"), 1));
+        assert_eq!(status.delivered, 1);
+
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(body.as_bytes());
+        let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+        assert_eq!(headers.get("x-timer-signature-256"), Some(&expected));
+        assert_eq!(headers.get("x-timer-event").map(String::as_str), Some("finished"));
+        assert_eq!(serde_json::from_str::<Value>(body).unwrap()["data"]["timerId"], "tea");
+    }
+
+    #[test]
+    fn unsigned_without_secret() {
+        let (url, received) = serve(vec![200]);
+        run(&target("unsigned", &url, None, 1));
+        assert!(!received.lock().unwrap()[0].0.contains_key("x-timer-signature-256"));
+    }
+
+    #[test]
+    fn retries_server_errors_up_to_max_attempts() {
+        let (url, received) = serve(vec![503]);
+        let status = run(&target("always-503", &url, None, 3));
+        assert_eq!(received.lock().unwrap().len(), 3);
+        assert_eq!(status.last_attempts, 3);
+        assert_eq!(status.last_status_code, Some(503));
+        assert_eq!((status.delivered, status.failed, status.pending), (0, 1, 0));
+    }
+
+    #[test]
+    fn retries_rate_limits() {
+        let (url, received) = serve(vec![429, 429, 200]);
+        let status = run(&target("rate-limited", &url, None, 5));
+        assert_eq!(received.lock().unwrap().len(), 3);
+        assert_eq!(status.last_status_code, Some(200));
+        assert_eq!((status.delivered, status.failed, status.pending), (1, 0, 0));
+        assert!(status.last_error.is_none());
+    }
+
+    #[test]
+    fn does_not_retry_client_errors() {
+        let (url, received) = serve(vec![404, 200]);
+        let status = run(&target("not-found", &url, None, 5));
+        assert_eq!(received.lock().unwrap().len(), 1);
+        assert_eq!(status.last_attempts, 1);
+        assert_eq!(status.last_status_code, Some(404));
+        assert_eq!((status.delivered, status.failed, status.pending), (0, 1, 0));
+    }
+
+    #[test]
+    fn counts_delivered_failed_and_pending() {
+        let (url, _) = serve(vec![500, 200, 400]);
+        let webhook = target("counts", &url, None, 2);
+
+        let (first_id, first_body) = new_delivery(&webhook, "started", json!({}));
+        let (second_id, second_body) = new_delivery(&webhook, "finished", json!({}));
+        assert_eq!(STATUS.lock().unwrap()["counts"].pending, 2);
+
+        // 500 e depois 200: entregue na segunda tentativa
+        send_with_retries(&webhook, "started", &first_id, &first_body);
+        let status = STATUS.lock().unwrap()["counts"].clone();
+        assert_eq!((status.delivered, status.failed, status.pending), (1, 0, 1));
+        assert_eq!(status.last_attempts, 2);
+        assert!(status.last_success_at.is_some());
+
+        // 400: desiste na hora
+        send_with_retries(&webhook, "finished", &second_id, &second_body);
+        let status = STATUS.lock().unwrap()["counts"].clone();
+        assert_eq!((status.delivered, status.failed, status.pending), (1, 1, 0));
+        assert_eq!(status.last_delivery_id, Some(second_id));
+    }
+
+    #[test]
+    fn validation_rejects_malformed_webhooks() {
+        let valid = json!({ "id": "w", "url": "HTTPS://example.com/hook", "events": ["finished", "warning"] });
+        assert!(validate_webhooks(&json!({ "webhooks": [valid.clone()] })).is_ok());
+
+        let broken = [
+            json!({ "url": "" }),
+            json!({ "url": "ftp://example.com" }),
+            json!({ "url": "https://" }),
+            json!({ "url": "https://example.com/a b" }),
+            json!({ "events": ["exploded"] }),
+            json!({ "maxAttempts": 0 }),
+            json!({ "headers": ["X-Token"] }),
+        ];
+        for patch in broken {
+            let mut webhook = valid.clone();
+            for (key, value) in patch.as_object().unwrap() {
+                webhook[key] = value.clone();
+            }
+            assert!(validate_webhooks(&json!({ "webhooks": [webhook.clone()] })).is_err(), "{}", webhook);
+        }
+    }
+}
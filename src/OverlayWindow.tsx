@@ -1,4 +1,4 @@
-import { useEffect, useRef } from 'react';
+import { Fragment, useCallback, useEffect, useRef, useState } from 'react';
 import { MantineProvider } from '@mantine/core';
 import { register, unregisterAll } from '@tauri-apps/plugin-global-shortcut';
 import { getCurrentWindow, PhysicalPosition, PhysicalSize } from '@tauri-apps/api/window';
@@ -7,14 +7,65 @@ import { useConfig } from './hooks/useConfig';
 import { useMonitors } from './hooks/useMonitors';
 import { OverlayIcon } from './components/OverlayIcon';
 import { overlayLogger } from './utils/logger';
+import { AdjustKeybind, IconConfig, profileIcons, TIMER_ADJUST_SHORTCUT_PREFIX, TIMER_GROUP_SHORTCUT_PREFIX, TIMER_LAP_SHORTCUT_PREFIX } from './types/config';
 import '@mantine/core/styles.css';
 import './App.css';
 
 function OverlayWindow() {
   const { config, loading, reloadConfig } = useConfig();
   const { monitors } = useMonitors();
-  const timerRefs = useRef<{ [key: string]: () => void }>({});
+  const timerRefs = useRef<{ [key: string]: (durationSeconds?: number) => void }>({});
   const resetTimerRefs = useRef<{ [key: string]: () => void }>({});
+  const pauseTimerRefs = useRef<{ [key: string]: () => void }>({});
+  const pauseOnlyTimerRefs = useRef<{ [key: string]: () => void }>({});
+  // Timers pedidos antes de existirem no overlay (ex.: criados por uma cadeia a partir de preset)
+  const pendingStartsRef = useRef<Set<string>>(new Set());
+  // Instâncias paralelas de cada timer (política `stack`), na ordem em que foram criadas
+  const [stackedInstances, setStackedInstances] = useState<Record<string, number[]>>({});
+  const nextInstanceRef = useRef(1);
+  const stackedCount = Object.values(stackedInstances).reduce((total, list) => total + list.length, 0);
+  // Timers rápidos efêmeros: vivem só no Rust e somem ao terminar
+  const [quickTimers, setQuickTimers] = useState<IconConfig[]>([]);
+  // Um timer mantido aparece na configuração antes de sair da lista de efêmeros;
+  // com um perfil ativo, só os timers dele aparecem
+  const icons = [...profileIcons(config), ...quickTimers.filter((quick) => !config.icons.some((icon) => icon.id === quick.id))];
+
+  useEffect(() => {
+    invoke<IconConfig[]>('get_quick_timers')
+      .then(setQuickTimers)
+      .catch((error) => overlayLogger.error('[OVERLAY] Failed to load quick timers:', error));
+
+    const unlisten = getCurrentWindow().listen<IconConfig[]>('quick-timers-changed', (event) => {
+      overlayLogger.info(`[OVERLAY] Quick timers changed (${event.payload.length} running)`);
+      setQuickTimers(event.payload);
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn()).catch((err) => {
+        overlayLogger.error('Failed to unlisten quick-timers-changed event:', err);
+      });
+    };
+  }, []);
+
+  const stackInstance = useCallback((timerId: string) => {
+    const instance = nextInstanceRef.current++;
+    overlayLogger.info(`[OVERLAY] Stacking instance ${instance} of timer ${timerId}`);
+    setStackedInstances((prev) => ({ ...prev, [timerId]: [...(prev[timerId] || []), instance] }));
+  }, []);
+
+  const removeStackedInstance = useCallback((timerId: string, instance: number) => {
+    delete resetTimerRefs.current[`${timerId}#${instance}`];
+    setStackedInstances((prev) => {
+      const remaining = (prev[timerId] || []).filter((i) => i !== instance);
+      const next = { ...prev };
+      if (remaining.length > 0) {
+        next[timerId] = remaining;
+      } else {
+        delete next[timerId];
+      }
+      return next;
+    });
+  }, []);
 
   // Adicionar listener para F12 para abrir DevTools
   useEffect(() => {
@@ -79,6 +130,10 @@ function OverlayWindow() {
         }
       });
       overlayLogger.info(`[OVERLAY] Reset ${Object.keys(resetTimerRefs.current).length} timers`);
+      Object.keys(resetTimerRefs.current)
+        .filter((key) => key.includes('#'))
+        .forEach((key) => delete resetTimerRefs.current[key]);
+      setStackedInstances({});
     });
 
     const unlistenResetSpecific = window.listen<string>('reset-specific-timer', (event) => {
@@ -91,6 +146,19 @@ function OverlayWindow() {
       } else {
         overlayLogger.warn(`[OVERLAY] Timer not found for reset: ${timerId}`);
       }
+      // Reset de um timer também encerra as instâncias paralelas dele
+      Object.keys(resetTimerRefs.current)
+        .filter((key) => key.startsWith(`${timerId}#`))
+        .forEach((key) => {
+          resetTimerRefs.current[key]();
+          delete resetTimerRefs.current[key];
+        });
+      setStackedInstances((prev) => {
+        if (!prev[timerId]) return prev;
+        const next = { ...prev };
+        delete next[timerId];
+        return next;
+      });
     });
 
     const unlistenStartSpecific = window.listen<string>('start-specific-timer', (event) => {
@@ -104,11 +172,47 @@ function OverlayWindow() {
         } catch (error) {
           overlayLogger.error(`[OVERLAY] Failed to start timer ${timerId}:`, error);
         }
+      } else {
+        overlayLogger.warn(`[OVERLAY] Timer not found for start, queueing until it is ready: ${timerId}`);
+        pendingStartsRef.current.add(timerId);
+      }
+    });
+
+    // Gatilhos de log podem iniciar o timer com uma duração capturada da linha
+    const unlistenStartWithDuration = window.listen<{ timerId: string; durationSeconds: number }>('start-timer-with-duration', (event) => {
+      const { timerId, durationSeconds } = event.payload;
+      overlayLogger.info(`[OVERLAY] Start timer ${timerId} with duration ${durationSeconds}s`);
+      const startFn = timerRefs.current[timerId];
+      if (startFn) {
+        startFn(durationSeconds);
       } else {
         overlayLogger.warn(`[OVERLAY] Timer not found for start: ${timerId}`);
       }
     });
 
+    const unlistenPauseSpecific = window.listen<string>('pause-specific-timer', (event) => {
+      const timerId = event.payload;
+      overlayLogger.info(`[OVERLAY] Pause specific timer event received for: ${timerId}`);
+      const pauseFn = pauseTimerRefs.current[timerId];
+      if (pauseFn) {
+        pauseFn();
+      } else {
+        overlayLogger.warn(`[OVERLAY] Timer not found for pause: ${timerId}`);
+      }
+    });
+
+    // Diferente de pause-specific-timer, não retoma um timer já pausado
+    const unlistenPauseOnly = window.listen<string>('pause-only-timer', (event) => {
+      const timerId = event.payload;
+      overlayLogger.info(`[OVERLAY] Pause-only event received for: ${timerId}`);
+      const pauseFn = pauseOnlyTimerRefs.current[timerId];
+      if (pauseFn) {
+        pauseFn();
+      } else {
+        overlayLogger.warn(`[OVERLAY] Timer not found for pause: ${timerId}`);
+      }
+    });
+
     overlayLogger.info('[OVERLAY] Listening for config-changed, position-changed and reset-all-timers events');
 
     return () => {
@@ -130,6 +234,15 @@ function OverlayWindow() {
       unlistenStartSpecific.then((unlistenFn) => unlistenFn()).catch((err) => {
         overlayLogger.error('Failed to unlisten start-specific-timer event:', err);
       });
+      unlistenStartWithDuration.then((unlistenFn) => unlistenFn()).catch((err) => {
+        overlayLogger.error('Failed to unlisten start-timer-with-duration event:', err);
+      });
+      unlistenPauseSpecific.then((unlistenFn) => unlistenFn()).catch((err) => {
+        overlayLogger.error('Failed to unlisten pause-specific-timer event:', err);
+      });
+      unlistenPauseOnly.then((unlistenFn) => unlistenFn()).catch((err) => {
+        overlayLogger.error('Failed to unlisten pause-only-timer event:', err);
+      });
     };
   }, [reloadConfig]);
 
@@ -137,7 +250,7 @@ function OverlayWindow() {
   const getOverlaySize = () => {
     const iconSize = config.compactMode ? 60 : 80;
     const gap = 8;
-    const iconCount = config.icons.length || 1;
+    const iconCount = icons.length + stackedCount || 1;
     const padding = 4; // Padding de segurança para evitar corte
 
     if (config.overlayOrientation === 'vertical') {
@@ -218,6 +331,12 @@ function OverlayWindow() {
         overlayLogger.info('=== Starting shortcut registration ===');
         
         const useLowLevelHook = navigator.platform.includes('Win');
+
+        // Atalhos de ajuste por timer e globais (sem alvo = timer ativo mais recente)
+        const adjustShortcuts: { adjust: AdjustKeybind; target?: string }[] = [
+          ...config.icons.flatMap(icon => (icon.adjustKeybinds || []).map(adjust => ({ adjust, target: icon.id }))),
+          ...(config.adjustKeybinds || []).map(adjust => ({ adjust }))
+        ].filter(({ adjust }) => adjust.keybind.trim() && adjust.deltaSeconds !== 0);
         
         if (useLowLevelHook) {
           try {
@@ -263,6 +382,49 @@ function OverlayWindow() {
               }
             }
 
+            for (const group of config.timerGroups || []) {
+              const normalizedKeybind = group.keybind?.trim();
+              if (!normalizedKeybind) continue;
+
+              try {
+                await invoke('register_low_level_shortcut', {
+                  shortcut: normalizedKeybind,
+                  iconId: `${TIMER_GROUP_SHORTCUT_PREFIX}${group.id}`
+                });
+                overlayLogger.info(`✓ Registered low-level group shortcut "${normalizedKeybind}" for group: ${group.id}`);
+              } catch (error) {
+                overlayLogger.error(`✗ Failed to register low-level group shortcut "${normalizedKeybind}":`, error);
+              }
+            }
+
+            for (const icon of config.icons) {
+              const normalizedKeybind = icon.timerType === 'stopwatch' ? icon.lapKeybind?.trim() : undefined;
+              if (!normalizedKeybind) continue;
+
+              try {
+                await invoke('register_low_level_shortcut', {
+                  shortcut: normalizedKeybind,
+                  iconId: `${TIMER_LAP_SHORTCUT_PREFIX}${icon.id}`
+                });
+                overlayLogger.info(`✓ Registered low-level lap shortcut "${normalizedKeybind}" for stopwatch: ${icon.id}`);
+              } catch (error) {
+                overlayLogger.error(`✗ Failed to register low-level lap shortcut "${normalizedKeybind}":`, error);
+              }
+            }
+
+            for (const { adjust, target } of adjustShortcuts) {
+              const normalizedKeybind = adjust.keybind.trim();
+              try {
+                await invoke('register_low_level_shortcut', {
+                  shortcut: normalizedKeybind,
+                  iconId: `${TIMER_ADJUST_SHORTCUT_PREFIX}${adjust.deltaSeconds}:${target ?? '*'}`
+                });
+                overlayLogger.info(`✓ Registered low-level adjust shortcut "${normalizedKeybind}" (${adjust.deltaSeconds}s) for: ${target ?? 'active timer'}`);
+              } catch (error) {
+                overlayLogger.error(`✗ Failed to register low-level adjust shortcut "${normalizedKeybind}":`, error);
+              }
+            }
+
             console.log(`[SHORTCUT] Using low-level keyboard hook for games`);
             overlayLogger.info('Using low-level keyboard hook for games');
             return;
@@ -341,6 +503,62 @@ function OverlayWindow() {
           }
         }
 
+        for (const group of config.timerGroups || []) {
+          const normalizedKeybind = group.keybind?.trim();
+          if (!normalizedKeybind) continue;
+
+          const groupId = group.id;
+          const action = group.keybindAction || 'start';
+          try {
+            await register(normalizedKeybind, () => {
+              overlayLogger.info(`[SHORTCUT] Group shortcut triggered: "${normalizedKeybind}" (${action}) for group: ${groupId}`);
+              invoke(`${action}_timer_group`, { groupId }).catch((error) => {
+                overlayLogger.error(`Failed to ${action} timer group ${groupId}:`, error);
+              });
+            });
+            overlayLogger.info(`✓ Registered group shortcut "${normalizedKeybind}" for group: ${groupId}`);
+          } catch (error) {
+            overlayLogger.error(`✗ Failed to register group shortcut "${normalizedKeybind}":`, error);
+          }
+        }
+
+        for (const icon of config.icons) {
+          const normalizedKeybind = icon.timerType === 'stopwatch' ? icon.lapKeybind?.trim() : undefined;
+          if (!normalizedKeybind) continue;
+
+          const timerId = icon.id;
+          try {
+            await register(normalizedKeybind, () => {
+              overlayLogger.info(`[SHORTCUT] Lap shortcut triggered: "${normalizedKeybind}" for stopwatch: ${timerId}`);
+              invoke('lap_timer', { timerId }).catch((error) => {
+                overlayLogger.warn(`Could not record lap on ${timerId}:`, error);
+              });
+            });
+            overlayLogger.info(`✓ Registered lap shortcut "${normalizedKeybind}" for stopwatch: ${timerId}`);
+          } catch (error) {
+            overlayLogger.error(`✗ Failed to register lap shortcut "${normalizedKeybind}":`, error);
+          }
+        }
+
+        for (const { adjust, target } of adjustShortcuts) {
+          const normalizedKeybind = adjust.keybind.trim();
+          const deltaSeconds = adjust.deltaSeconds;
+          try {
+            await register(normalizedKeybind, () => {
+              overlayLogger.info(`[SHORTCUT] Adjust shortcut triggered: "${normalizedKeybind}" (${deltaSeconds}s) for: ${target ?? 'active timer'}`);
+              const request = target
+                ? invoke('adjust_timer', { timerId: target, deltaSeconds })
+                : invoke('adjust_active_timer', { deltaSeconds });
+              request.catch((error) => {
+                overlayLogger.warn(`Could not adjust timer:`, error);
+              });
+            });
+            overlayLogger.info(`✓ Registered adjust shortcut "${normalizedKeybind}" (${deltaSeconds}s) for: ${target ?? 'active timer'}`);
+          } catch (error) {
+            overlayLogger.error(`✗ Failed to register adjust shortcut "${normalizedKeybind}":`, error);
+          }
+        }
+
         console.log(`[SHORTCUT] ========== ALL SHORTCUTS REGISTRATION COMPLETED ==========`);
         overlayLogger.info('=== All shortcuts registration completed ===');
       } catch (error) {
@@ -365,7 +583,7 @@ function OverlayWindow() {
       });
       invoke('unregister_all_low_level_shortcuts').catch(console.error);
     };
-  }, [config.icons, config.resetAllTimersKeybind, loading]);
+  }, [config.icons, config.resetAllTimersKeybind, config.timerGroups, config.adjustKeybinds, loading]);
 
   // Flag para ignorar reposicionamento quando position-changed foi recebido
   const positionChangedRef = useRef(false);
@@ -542,7 +760,8 @@ function OverlayWindow() {
     config.overlayPosition, 
     config.overlayCorner, 
     config.overlayOrientation, 
-    config.icons.length, 
+    icons.length, 
+    stackedCount,
     config.compactMode,
     config.overlayMonitor,
     loading, 
@@ -557,7 +776,7 @@ function OverlayWindow() {
     );
   }
 
-  if (config.icons.length === 0) {
+  if (icons.length === 0) {
     return (
       <MantineProvider>
         <div
@@ -583,7 +802,7 @@ function OverlayWindow() {
   return (
     <MantineProvider>
       <div
-        key={`overlay-${config.overlayOrientation}-${config.icons.length}-${config.compactMode}`}
+        key={`overlay-${config.overlayOrientation}-${config.compactMode}`}
         onContextMenu={(e) => e.preventDefault()}
         style={{
           display: 'flex',
@@ -597,26 +816,50 @@ function OverlayWindow() {
           boxSizing: 'border-box'
         }}
       >
-        {config.icons.map((icon) => (
-          <OverlayIcon
-            key={icon.id}
-            config={icon}
-            compactMode={config.compactMode}
-            timerColor={config.timerColor || '#2196F3'}
-            timerRunningColor={config.timerRunningColor || '#4CAF50'}
-            onConfigClick={async () => {
-              // Não fazer nada - overlay não é interativo
-            }}
-            onStartTimerReady={(startFn) => {
-              overlayLogger.debug(`Timer ready for icon: ${icon.id}`);
-              console.log(`[TIMER] Timer ready for icon: ${icon.id}, duration: ${icon.timerDuration}s`);
-              timerRefs.current[icon.id] = startFn;
-            }}
-            onResetTimerReady={(resetFn) => {
-              resetTimerRefs.current[icon.id] = resetFn;
-              overlayLogger.debug(`Reset function registered for icon: ${icon.id}`);
-            }}
-          />
+        {icons.map((icon) => (
+          <Fragment key={icon.id}>
+            <OverlayIcon
+              config={icon}
+              compactMode={config.compactMode}
+              timerColor={config.timerColor || '#2196F3'}
+              timerRunningColor={config.timerRunningColor || '#4CAF50'}
+              onConfigClick={async () => {
+                // Não fazer nada - overlay não é interativo
+              }}
+              onStartTimerReady={(startFn) => {
+                overlayLogger.debug(`Timer ready for icon: ${icon.id}`);
+                console.log(`[TIMER] Timer ready for icon: ${icon.id}, duration: ${icon.timerDuration}s`);
+                timerRefs.current[icon.id] = startFn;
+                if (pendingStartsRef.current.delete(icon.id)) {
+                  overlayLogger.info(`[OVERLAY] Starting queued timer: ${icon.id}`);
+                  startFn();
+                }
+              }}
+              onResetTimerReady={(resetFn) => {
+                resetTimerRefs.current[icon.id] = resetFn;
+                overlayLogger.debug(`Reset function registered for icon: ${icon.id}`);
+              }}
+              onPauseTimerReady={(pauseFn, pauseOnlyFn) => {
+                pauseTimerRefs.current[icon.id] = pauseFn;
+                pauseOnlyTimerRefs.current[icon.id] = pauseOnlyFn;
+              }}
+              onStackInstance={() => stackInstance(icon.id)}
+            />
+            {(stackedInstances[icon.id] || []).map((instance) => (
+              <OverlayIcon
+                key={`${icon.id}#${instance}`}
+                config={icon}
+                instance={instance}
+                compactMode={config.compactMode}
+                timerColor={config.timerColor || '#2196F3'}
+                timerRunningColor={config.timerRunningColor || '#4CAF50'}
+                onResetTimerReady={(resetFn) => {
+                  resetTimerRefs.current[`${icon.id}#${instance}`] = resetFn;
+                }}
+                onInstanceDone={() => removeStackedInstance(icon.id, instance)}
+              />
+            ))}
+          </Fragment>
         ))}
       </div>
     </MantineProvider>
@@ -7,6 +7,7 @@ import { SettingsTabs } from './components/SettingsTabs';
 import { SettingsHeader } from './components/SettingsHeader';
 import { IconConfig } from './types/config';
 import { settingsLogger } from './utils/logger';
+import { iconKeybinds } from './utils/keybinds';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import '@mantine/core/styles.css';
 import './Settings.css';
@@ -76,7 +77,13 @@ function SettingsWindow() {
       icons: newIcons
     };
 
-    await saveConfig(newConfig);
+    try {
+      await saveConfig(newConfig);
+    } catch (error) {
+      // O backend recusa configs inválidos (ex.: cadeias de timers em loop infinito)
+      settingsLogger.error('Failed to save timer:', error);
+      window.alert(`Could not save timer: ${error}`);
+    }
   };
 
   const handleAddIcon = () => {
@@ -102,8 +109,7 @@ function SettingsWindow() {
 
   const existingKeybinds = config.icons
     .filter(icon => !selectedIcon || icon.id !== selectedIcon.id)
-    .map(icon => icon.keybind?.trim())
-    .filter((key): key is string => Boolean(key));
+    .flatMap(iconKeybinds);
 
   const showContent = !loading || forceShow;
 
@@ -150,6 +156,8 @@ function SettingsWindow() {
                 config={selectedIcon}
                 onSave={handleSaveIcon}
                 existingKeybinds={existingKeybinds}
+                allTimers={config.icons}
+                presets={config.timerPresets}
               />
               <WelcomeModal
                 opened={welcomeOpen}
@@ -0,0 +1,74 @@
+import { Stack, Group, Text, Button, NumberInput, ActionIcon } from '@mantine/core';
+import * as Icons from 'lucide-react';
+import { AdjustKeybind } from '../types/config';
+import { KeybindInput } from './KeybindInput';
+
+interface AdjustKeybindsEditorProps {
+  value: AdjustKeybind[];
+  onChange: (value: AdjustKeybind[]) => void;
+  existingKeybinds?: string[];
+  title?: string;
+  description?: string;
+}
+
+export function AdjustKeybindsEditor({
+  value,
+  onChange,
+  existingKeybinds = [],
+  title = 'Adjust Shortcuts',
+  description = 'Add or remove time while the timer runs, is paused or in its interval'
+}: AdjustKeybindsEditorProps) {
+  const update = (index: number, changes: Partial<AdjustKeybind>) => {
+    onChange(value.map((item, i) => (i === index ? { ...item, ...changes } : item)));
+  };
+
+  return (
+    <div>
+      <Group justify="space-between" mb="xs">
+        <div>
+          <Text size="sm" fw={500}>{title}</Text>
+          <Text size="xs" c="dimmed">{description}</Text>
+        </div>
+        <Button
+          size="xs"
+          variant="light"
+          onClick={() => onChange([...value, { keybind: '', deltaSeconds: 30 }])}
+        >
+          Add shortcut
+        </Button>
+      </Group>
+      <Stack gap="xs">
+        {value.map((item, index) => (
+          <Group key={index} gap="xs" align="flex-end" wrap="nowrap">
+            <div style={{ flex: 1 }}>
+              <KeybindInput
+                value={item.keybind}
+                onChange={(keybind) => update(index, { keybind })}
+                existingKeybinds={[
+                  ...existingKeybinds,
+                  ...value.filter((_, i) => i !== index).map(other => other.keybind)
+                ].filter(Boolean)}
+                optional
+              />
+            </div>
+            <NumberInput
+              label="Seconds"
+              description="Negative subtracts"
+              value={item.deltaSeconds}
+              onChange={(delta) => update(index, { deltaSeconds: typeof delta === 'number' ? delta : 0 })}
+              style={{ width: 120 }}
+            />
+            <ActionIcon
+              color="red"
+              variant="light"
+              mb={4}
+              onClick={() => onChange(value.filter((_, i) => i !== index))}
+            >
+              <Icons.Trash2 size={16} />
+            </ActionIcon>
+          </Group>
+        ))}
+      </Stack>
+    </div>
+  );
+}
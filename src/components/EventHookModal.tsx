@@ -0,0 +1,164 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Stack, TextInput, Textarea, MultiSelect, Select, NumberInput, Text } from '@mantine/core';
+import { invoke } from '@tauri-apps/api/core';
+import { EventHook, HookEvent, IconConfig } from '../types/config';
+
+interface EventHookModalProps {
+  opened: boolean;
+  onClose: () => void;
+  hook: EventHook | null;
+  // Timer dono do hook; null = global
+  timerId: string | null;
+  timers: IconConfig[];
+  onSave: (hook: EventHook, timerId: string | null) => void;
+}
+
+export const HOOK_EVENT_LABELS: Record<HookEvent, string> = {
+  started: 'Started',
+  phase: 'New repeat',
+  paused: 'Paused',
+  resumed: 'Resumed',
+  finished: 'Finished',
+  cancelled: 'Reset',
+  acknowledged: 'Overtime acknowledged'
+};
+
+export function EventHookModal({ opened, onClose, hook, timerId, timers, onSave }: EventHookModalProps) {
+  const [name, setName] = useState<string>('');
+  const [owner, setOwner] = useState<string | null>(null);
+  const [events, setEvents] = useState<string[]>([]);
+  const [command, setCommand] = useState<string>('');
+  const [args, setArgs] = useState<string>('');
+  const [workingDir, setWorkingDir] = useState<string>('');
+  const [timeoutSeconds, setTimeoutSeconds] = useState<number>(30);
+  const [testMessage, setTestMessage] = useState<string>('');
+
+  useEffect(() => {
+    setName(hook?.name || '');
+    setOwner(timerId);
+    setEvents(hook?.events || ['finished']);
+    setCommand(hook?.command || '');
+    setArgs((hook?.args || []).join('\n'));
+    setWorkingDir(hook?.workingDir || '');
+    setTimeoutSeconds(hook?.timeoutSeconds ?? 30);
+    setTestMessage('');
+  }, [hook, timerId, opened]);
+
+  const buildHook = (): EventHook => ({
+    id: hook?.id || `hook-${Date.now()}`,
+    name: name.trim() || undefined,
+    events: events as HookEvent[],
+    command: command.trim(),
+    args: args.split('\n').map(a => a.trim()).filter(Boolean),
+    workingDir: workingDir.trim() || undefined,
+    timeoutSeconds,
+    enabled: hook?.enabled ?? true
+  });
+
+  const handleSave = () => {
+    if (!command.trim()) {
+      return;
+    }
+    onSave(buildHook(), owner);
+    onClose();
+  };
+
+  const handleTest = async () => {
+    try {
+      await invoke('test_event_hook', { hook: buildHook() });
+      setTestMessage('Test event sent. The result shows up in the hook list once the command ends.');
+    } catch (error) {
+      setTestMessage(String(error));
+    }
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={hook ? 'Edit Event Hook' : 'Create Event Hook'}
+      size="md"
+    >
+      <Stack gap="md">
+        <TextInput
+          label="Name"
+          placeholder="e.g., Dim the lights"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+
+        <Select
+          label="Timer"
+          description="Leave empty to run for every timer"
+          placeholder="All timers"
+          clearable
+          value={owner}
+          onChange={setOwner}
+          data={timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))}
+          searchable
+        />
+
+        <MultiSelect
+          label="Events"
+          placeholder={events.length === 0 ? 'All events' : undefined}
+          value={events}
+          onChange={setEvents}
+          data={Object.entries(HOOK_EVENT_LABELS).map(([value, label]) => ({ value, label }))}
+        />
+
+        <TextInput
+          label="Command"
+          description="Program to run, e.g. /usr/bin/curl or powershell.exe"
+          value={command}
+          onChange={(e) => setCommand(e.target.value)}
+          required
+        />
+
+        <Textarea
+          label="Arguments"
+          description="One per line"
+          autosize
+          minRows={2}
+          value={args}
+          onChange={(e) => setArgs(e.currentTarget.value)}
+        />
+
+        <Group grow>
+          <TextInput
+            label="Working Directory"
+            placeholder="Optional"
+            value={workingDir}
+            onChange={(e) => setWorkingDir(e.target.value)}
+          />
+          <NumberInput
+            label="Timeout (seconds)"
+            min={1}
+            value={timeoutSeconds}
+            onChange={(value) => setTimeoutSeconds(typeof value === 'number' ? value : 30)}
+          />
+        </Group>
+
+        <Text size="xs" c="dimmed">
+          The command gets TIMER_ID, TIMER_NAME, TIMER_EVENT, TIMER_PHASE, TIMER_REMAINING, TIMER_LABEL and more as
+          environment variables, and the full event as JSON on stdin (Pomodoro phases and turn clock players under
+          "detail"). Its output goes to the app log.
+        </Text>
+        {testMessage && (
+          <Text size="xs" c="dimmed">{testMessage}</Text>
+        )}
+
+        <Group justify="space-between" mt="md">
+          <Button variant="light" onClick={handleTest} disabled={!command.trim()}>
+            Test
+          </Button>
+          <Group gap="xs">
+            <Button variant="subtle" onClick={onClose}>Cancel</Button>
+            <Button onClick={handleSave} disabled={!command.trim()}>
+              {hook ? 'Update' : 'Create'}
+            </Button>
+          </Group>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
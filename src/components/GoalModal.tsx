@@ -0,0 +1,186 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Stack, TextInput, Select, NumberInput, SegmentedControl, Autocomplete } from '@mantine/core';
+import { GoalDefinition, IconConfig, TimerGroup } from '../types/config';
+import { DurationInput } from './DurationInput';
+
+interface GoalModalProps {
+  opened: boolean;
+  onClose: () => void;
+  goal: GoalDefinition | null;
+  timers: IconConfig[];
+  groups: TimerGroup[];
+  recentLabels: string[];
+  onSave: (goal: GoalDefinition) => void;
+}
+
+export function GoalModal({ opened, onClose, goal, timers, groups, recentLabels, onSave }: GoalModalProps) {
+  const [name, setName] = useState<string>('');
+  const [kind, setKind] = useState<GoalDefinition['kind']>('goal');
+  const [period, setPeriod] = useState<GoalDefinition['period']>('day');
+  const [scope, setScope] = useState<GoalDefinition['scope']>('timer');
+  const [target, setTarget] = useState<string>('');
+  const [metric, setMetric] = useState<GoalDefinition['metric']>('runs');
+  const [amount, setAmount] = useState<number>(4);
+  const [warnAt, setWarnAt] = useState<number>(90);
+
+  useEffect(() => {
+    if (goal) {
+      setName(goal.name || '');
+      setKind(goal.kind);
+      setPeriod(goal.period);
+      setScope(goal.scope);
+      setTarget(goal.target);
+      setMetric(goal.metric);
+      setAmount(goal.amount);
+      setWarnAt(Math.round((goal.warnAt ?? 0.9) * 100));
+    } else {
+      setName('');
+      setKind('goal');
+      setPeriod('day');
+      setScope('timer');
+      setTarget('');
+      setMetric('runs');
+      setAmount(4);
+      setWarnAt(90);
+    }
+  }, [goal, opened]);
+
+  const handleSave = () => {
+    if (!target.trim() || amount <= 0) {
+      return;
+    }
+
+    onSave({
+      id: goal?.id || `goal-${Date.now()}`,
+      name: name.trim() || undefined,
+      kind,
+      period,
+      scope,
+      target: target.trim(),
+      metric,
+      amount,
+      warnAt: kind === 'budget' ? warnAt / 100 : undefined,
+      enabled: goal?.enabled ?? true
+    });
+    onClose();
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={goal ? 'Edit Goal' : 'Create Goal'}
+      size="md"
+    >
+      <Stack gap="md">
+        <SegmentedControl
+          value={kind}
+          onChange={(value) => setKind(value as GoalDefinition['kind'])}
+          data={[
+            { value: 'goal', label: 'Goal (at least)' },
+            { value: 'budget', label: 'Budget (at most)' }
+          ]}
+        />
+
+        <TextInput
+          label="Name"
+          placeholder={kind === 'goal' ? 'e.g., Daily focus' : 'e.g., Gaming limit'}
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+
+        <Group grow>
+          <Select
+            label="Counts"
+            value={scope}
+            onChange={(value) => {
+              setScope((value as GoalDefinition['scope']) || 'timer');
+              setTarget('');
+            }}
+            data={[
+              { value: 'timer', label: 'One timer' },
+              { value: 'group', label: 'A timer group' },
+              { value: 'label', label: 'Runs with a label' }
+            ]}
+          />
+          {scope === 'label' ? (
+            <Autocomplete
+              label="Label"
+              value={target}
+              onChange={setTarget}
+              data={recentLabels}
+            />
+          ) : (
+            <Select
+              label={scope === 'timer' ? 'Timer' : 'Group'}
+              value={target || null}
+              onChange={(value) => setTarget(value || '')}
+              data={scope === 'timer'
+                ? timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))
+                : groups.map(group => ({ value: group.id, label: group.name }))}
+              searchable
+            />
+          )}
+        </Group>
+
+        <Group grow>
+          <Select
+            label="Measure"
+            value={metric}
+            onChange={(value) => {
+              const next = (value as GoalDefinition['metric']) || 'runs';
+              setMetric(next);
+              setAmount(next === 'runs' ? 4 : 3600);
+            }}
+            data={[
+              { value: 'runs', label: kind === 'goal' ? 'Completed runs' : 'Runs' },
+              { value: 'seconds', label: 'Time counted' }
+            ]}
+          />
+          <Select
+            label="Per"
+            value={period}
+            onChange={(value) => setPeriod((value as GoalDefinition['period']) || 'day')}
+            data={[
+              { value: 'day', label: 'Day' },
+              { value: 'week', label: 'Week (Monday to Sunday)' }
+            ]}
+          />
+        </Group>
+
+        {metric === 'runs' ? (
+          <NumberInput
+            label={kind === 'goal' ? 'Target Runs' : 'Maximum Runs'}
+            min={1}
+            value={amount}
+            onChange={(value) => setAmount(typeof value === 'number' ? value : 1)}
+          />
+        ) : (
+          <DurationInput
+            label={kind === 'goal' ? 'Target Time' : 'Maximum Time'}
+            value={amount}
+            onChange={setAmount}
+          />
+        )}
+
+        {kind === 'budget' && (
+          <NumberInput
+            label="Warn At (%)"
+            description="A notification is sent when this much of the budget is used"
+            min={1}
+            max={100}
+            value={warnAt}
+            onChange={(value) => setWarnAt(typeof value === 'number' ? value : 90)}
+          />
+        )}
+
+        <Group justify="flex-end" mt="md">
+          <Button variant="subtle" onClick={onClose}>Cancel</Button>
+          <Button onClick={handleSave} disabled={!target.trim() || amount <= 0}>
+            {goal ? 'Update' : 'Create'}
+          </Button>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
@@ -0,0 +1,186 @@
+import { useCallback, useEffect, useState } from 'react';
+import { Stack, Text, Card, Button, Group, ActionIcon, Progress, Badge, Switch } from '@mantine/core';
+import { AppConfig, GoalDefinition } from '../types/config';
+import { GoalModal } from './GoalModal';
+import { formatSeconds, getGoalProgress, getRecentLabels, GoalProgress } from '../utils/history';
+import * as Icons from 'lucide-react';
+
+interface GoalsTabProps {
+  config: AppConfig;
+  onConfigChange: (config: AppConfig) => void;
+}
+
+// Progresso é recalculado com esta frequência enquanto a aba está aberta
+const REFRESH_MS = 30000;
+
+export function GoalsTab({ config, onConfigChange }: GoalsTabProps) {
+  const [goalModalOpened, setGoalModalOpened] = useState(false);
+  const [selectedGoal, setSelectedGoal] = useState<GoalDefinition | null>(null);
+  const [progress, setProgress] = useState<Record<string, GoalProgress>>({});
+  const [recentLabels, setRecentLabels] = useState<string[]>([]);
+
+  const goals = config.goals || [];
+
+  const refresh = useCallback(async () => {
+    try {
+      const result = await getGoalProgress();
+      setProgress(Object.fromEntries(result.map(p => [p.id, p])));
+    } catch (error) {
+      console.error('Failed to load goal progress', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const interval = setInterval(refresh, REFRESH_MS);
+    return () => clearInterval(interval);
+  }, [refresh, config.goals]);
+
+  useEffect(() => {
+    getRecentLabels()
+      .then(setRecentLabels)
+      .catch((error) => console.error('Failed to load recent labels', error));
+  }, []);
+
+  const handleSaveGoal = (goal: GoalDefinition) => {
+    const existingIndex = goals.findIndex(g => g.id === goal.id);
+
+    if (existingIndex >= 0) {
+      const newGoals = [...goals];
+      newGoals[existingIndex] = goal;
+      onConfigChange({ ...config, goals: newGoals });
+    } else {
+      onConfigChange({ ...config, goals: [...goals, goal] });
+    }
+    setSelectedGoal(null);
+  };
+
+  const handleDeleteGoal = (goalId: string) => {
+    onConfigChange({ ...config, goals: goals.filter(g => g.id !== goalId) });
+  };
+
+  const handleToggleGoal = (goalId: string, enabled: boolean) => {
+    onConfigChange({ ...config, goals: goals.map(g => (g.id === goalId ? { ...g, enabled } : g)) });
+  };
+
+  const formatAmount = (goal: GoalDefinition, value: number) =>
+    goal.metric === 'runs' ? `${value} run${value === 1 ? '' : 's'}` : formatSeconds(value);
+
+  const targetName = (goal: GoalDefinition) => {
+    if (goal.scope === 'label') return `label "${goal.target}"`;
+    if (goal.scope === 'group') {
+      return (config.timerGroups || []).find(g => g.id === goal.target)?.name || goal.target;
+    }
+    const timer = config.icons.find(icon => icon.id === goal.target);
+    return timer ? timer.name || timer.iconName : goal.target;
+  };
+
+  const progressColor = (goal: GoalDefinition, current?: GoalProgress) => {
+    if (!current) return 'gray';
+    if (goal.kind === 'goal') return current.met ? 'green' : 'blue';
+    if (!current.met) return 'red';
+    return current.warning ? 'yellow' : 'teal';
+  };
+
+  return (
+    <Stack gap="md">
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Goals & Budgets</Text>
+            <Text size="xs" c="dimmed">
+              Daily or weekly targets and limits for a timer, a group or a label, with a notification when they are reached
+            </Text>
+          </div>
+          <Button size="xs" onClick={() => {
+            setSelectedGoal(null);
+            setGoalModalOpened(true);
+          }}>
+            Add Goal
+          </Button>
+        </Group>
+        <Stack gap="xs">
+          {goals.length > 0 ? (
+            goals.map((goal) => {
+              const current = progress[goal.id];
+              const enabled = goal.enabled ?? true;
+              return (
+                <Card key={goal.id} padding="sm" withBorder>
+                  <Group justify="space-between" mb={enabled ? 'xs' : 0}>
+                    <div style={{ flex: 1 }}>
+                      <Group gap="xs">
+                        <Text size="sm" fw={500}>{goal.name || targetName(goal)}</Text>
+                        <Badge size="xs" variant="light" color={goal.kind === 'goal' ? 'blue' : 'orange'}>
+                          {goal.kind === 'goal' ? 'Goal' : 'Budget'}
+                        </Badge>
+                        {current && current.streak > 0 && (
+                          <Badge size="xs" variant="light" color="grape">
+                            {current.streak} {goal.period === 'day' ? 'day' : 'week'} streak
+                          </Badge>
+                        )}
+                      </Group>
+                      <Text size="xs" c="dimmed">
+                        {goal.kind === 'goal' ? 'At least' : 'At most'} {formatAmount(goal, goal.amount)} per {goal.period} | {targetName(goal)}
+                      </Text>
+                    </div>
+                    <Group gap="xs">
+                      <Switch
+                        size="xs"
+                        checked={enabled}
+                        onChange={(event) => handleToggleGoal(goal.id, event.currentTarget.checked)}
+                      />
+                      <Button size="xs" variant="light" onClick={() => {
+                        setSelectedGoal(goal);
+                        setGoalModalOpened(true);
+                      }}>
+                        Edit
+                      </Button>
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        onClick={() => handleDeleteGoal(goal.id)}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  </Group>
+                  {enabled && current && (
+                    <>
+                      <Progress
+                        value={Math.min(current.fraction, 1) * 100}
+                        color={progressColor(goal, current)}
+                        size="sm"
+                      />
+                      <Text size="xs" c="dimmed" mt={4}>
+                        {formatAmount(goal, current.current)} of {formatAmount(goal, current.amount)}{' '}
+                        {goal.period === 'day' ? 'today' : 'this week'}
+                        {goal.kind === 'budget' && !current.met && ' (over budget)'}
+                      </Text>
+                    </>
+                  )}
+                </Card>
+              );
+            })
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No goals yet. Add a target like 4 focus runs per day, or a budget like 10h of gaming per week.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <GoalModal
+        opened={goalModalOpened}
+        onClose={() => {
+          setGoalModalOpened(false);
+          setSelectedGoal(null);
+        }}
+        goal={selectedGoal}
+        timers={config.icons}
+        groups={config.timerGroups || []}
+        recentLabels={recentLabels}
+        onSave={handleSaveGoal}
+      />
+    </Stack>
+  );
+}
@@ -0,0 +1,226 @@
+import { useState } from 'react';
+import { Stack, Text, Card, Button, Group, ActionIcon, Badge } from '@mantine/core';
+import { AppConfig, applyProfile, TimerGroup, TimerProfile } from '../types/config';
+import { TimerGroupModal } from './TimerGroupModal';
+import { ProfileModal } from './ProfileModal';
+import { allConfiguredKeybinds } from '../utils/keybinds';
+import * as Icons from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+
+interface GroupsTabProps {
+  config: AppConfig;
+  onConfigChange: (config: AppConfig) => void;
+}
+
+export function GroupsTab({ config, onConfigChange }: GroupsTabProps) {
+  const [groupModalOpened, setGroupModalOpened] = useState(false);
+  const [selectedGroup, setSelectedGroup] = useState<TimerGroup | null>(null);
+  const [profileModalOpened, setProfileModalOpened] = useState(false);
+  const [selectedProfile, setSelectedProfile] = useState<TimerProfile | null>(null);
+
+  const allKeybinds = allConfiguredKeybinds(config);
+
+  const handleSaveGroup = (group: TimerGroup) => {
+    const groups = config.timerGroups || [];
+    const existingIndex = groups.findIndex(g => g.id === group.id);
+
+    if (existingIndex >= 0) {
+      const newGroups = [...groups];
+      newGroups[existingIndex] = group;
+      onConfigChange({ ...config, timerGroups: newGroups });
+    } else {
+      onConfigChange({ ...config, timerGroups: [...groups, group] });
+    }
+    setSelectedGroup(null);
+  };
+
+  const handleDeleteGroup = (groupId: string) => {
+    const groups = (config.timerGroups || []).filter(g => g.id !== groupId);
+    onConfigChange({ ...config, timerGroups: groups });
+  };
+
+  const handleSaveProfile = (profile: TimerProfile) => {
+    const profiles = config.profiles || [];
+    const existingIndex = profiles.findIndex(p => p.id === profile.id);
+    const newProfiles = existingIndex >= 0
+      ? profiles.map(p => (p.id === profile.id ? profile : p))
+      : [...profiles, profile];
+    const updated = { ...config, profiles: newProfiles };
+    // Editar o perfil ativo reaplica o layout dele
+    onConfigChange(config.activeProfileId === profile.id ? applyProfile(updated, profile) : updated);
+    setSelectedProfile(null);
+  };
+
+  const handleDeleteProfile = (profileId: string) => {
+    onConfigChange({
+      ...config,
+      profiles: (config.profiles || []).filter(p => p.id !== profileId),
+      activeProfileId: config.activeProfileId === profileId ? undefined : config.activeProfileId
+    });
+  };
+
+  const handleToggleProfile = (profile: TimerProfile) => {
+    if (config.activeProfileId === profile.id) {
+      onConfigChange({ ...config, activeProfileId: undefined });
+    } else {
+      onConfigChange(applyProfile(config, profile));
+    }
+  };
+
+  const handleGroupAction = async (groupId: string, action: 'start' | 'pause' | 'reset') => {
+    try {
+      await invoke(`${action}_timer_group`, { groupId });
+    } catch (error) {
+      console.error(`Failed to ${action} timer group`, error);
+    }
+  };
+
+  return (
+    <Stack gap="md">
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Timer Groups</Text>
+            <Text size="xs" c="dimmed">
+              Start, pause or reset related timers together from the tray or a single shortcut
+            </Text>
+          </div>
+          <Button size="xs" onClick={() => {
+            setSelectedGroup(null);
+            setGroupModalOpened(true);
+          }}>
+            Add Group
+          </Button>
+        </Group>
+        <Stack gap="xs">
+          {config.timerGroups && config.timerGroups.length > 0 ? (
+            config.timerGroups.map((group) => (
+              <Card key={group.id} padding="sm" withBorder>
+                <Group justify="space-between">
+                  <div style={{ flex: 1 }}>
+                    <Text size="sm" fw={500}>{group.name}</Text>
+                    <Text size="xs" c="dimmed">
+                      {group.timerIds.length} timers | Shortcut: {group.keybind ? `${group.keybind} (${group.keybindAction || 'start'})` : 'None'}
+                    </Text>
+                  </div>
+                  <Group gap="xs">
+                    <ActionIcon variant="light" title="Start all" onClick={() => handleGroupAction(group.id, 'start')}>
+                      <Icons.Play size={16} />
+                    </ActionIcon>
+                    <ActionIcon variant="light" title="Pause all" onClick={() => handleGroupAction(group.id, 'pause')}>
+                      <Icons.Pause size={16} />
+                    </ActionIcon>
+                    <ActionIcon variant="light" title="Reset all" onClick={() => handleGroupAction(group.id, 'reset')}>
+                      <Icons.RotateCcw size={16} />
+                    </ActionIcon>
+                    <Button size="xs" variant="light" onClick={() => {
+                      setSelectedGroup(group);
+                      setGroupModalOpened(true);
+                    }}>
+                      Edit
+                    </Button>
+                    <ActionIcon
+                      color="red"
+                      variant="light"
+                      onClick={() => handleDeleteGroup(group.id)}
+                    >
+                      <Icons.Trash2 size={16} />
+                    </ActionIcon>
+                  </Group>
+                </Group>
+              </Card>
+            ))
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No groups configured. Create a group to control several timers at once.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Profiles</Text>
+            <Text size="xs" c="dimmed">
+              Show only some timers and switch the overlay layout, by hand or from a process rule
+            </Text>
+          </div>
+          <Button size="xs" onClick={() => {
+            setSelectedProfile(null);
+            setProfileModalOpened(true);
+          }}>
+            Add Profile
+          </Button>
+        </Group>
+        <Stack gap="xs">
+          {config.profiles && config.profiles.length > 0 ? (
+            config.profiles.map((profile) => {
+              const active = config.activeProfileId === profile.id;
+              return (
+                <Card key={profile.id} padding="sm" withBorder>
+                  <Group justify="space-between">
+                    <div style={{ flex: 1 }}>
+                      <Group gap="xs">
+                        <Text size="sm" fw={500}>{profile.name}</Text>
+                        {active && <Badge color="green" variant="light">Active</Badge>}
+                      </Group>
+                      <Text size="xs" c="dimmed">
+                        {profile.timerIds.length > 0 ? `${profile.timerIds.length} timers` : 'All timers'}
+                      </Text>
+                    </div>
+                    <Group gap="xs">
+                      <Button size="xs" variant={active ? 'default' : 'light'} onClick={() => handleToggleProfile(profile)}>
+                        {active ? 'Deactivate' : 'Activate'}
+                      </Button>
+                      <Button size="xs" variant="light" onClick={() => {
+                        setSelectedProfile(profile);
+                        setProfileModalOpened(true);
+                      }}>
+                        Edit
+                      </Button>
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        onClick={() => handleDeleteProfile(profile.id)}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  </Group>
+                </Card>
+              );
+            })
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No profiles configured. Create one to switch between sets of timers.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <TimerGroupModal
+        opened={groupModalOpened}
+        onClose={() => {
+          setGroupModalOpened(false);
+          setSelectedGroup(null);
+        }}
+        group={selectedGroup}
+        timers={config.icons}
+        onSave={handleSaveGroup}
+        existingKeybinds={allKeybinds.filter(k => !selectedGroup || selectedGroup.keybind !== k)}
+      />
+
+      <ProfileModal
+        opened={profileModalOpened}
+        onClose={() => {
+          setProfileModalOpened(false);
+          setSelectedProfile(null);
+        }}
+        profile={selectedProfile}
+        timers={config.icons}
+        onSave={handleSaveProfile}
+      />
+    </Stack>
+  );
+}
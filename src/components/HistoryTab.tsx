@@ -0,0 +1,378 @@
+import { useCallback, useEffect, useState } from 'react';
+import { Stack, Text, Card, Group, Badge, NumberInput, Select, MultiSelect, Button, SegmentedControl, Modal, Textarea, Autocomplete, UnstyledButton } from '@mantine/core';
+import { save } from '@tauri-apps/plugin-dialog';
+import { AppConfig } from '../types/config';
+import {
+  EXPORT_FORMATS,
+  ExportFormat,
+  exportHistory,
+  formatSeconds,
+  getRecentLabels,
+  getStats,
+  getWeeklySummary,
+  HISTORY_EVENT_LABELS,
+  HistoryEntry,
+  HistoryEventKind,
+  labelRun,
+  queryHistory,
+  StatsGroupBy,
+  StatsReport,
+  TRIGGER_SOURCE_LABELS
+} from '../utils/history';
+
+interface HistoryTabProps {
+  config: AppConfig;
+  onConfigChange: (config: AppConfig) => void;
+}
+
+const RANGE_OPTIONS = [
+  { value: '1', label: 'Last 24 hours' },
+  { value: '7', label: 'Last 7 days' },
+  { value: '30', label: 'Last 30 days' },
+  { value: 'all', label: 'All time' }
+];
+
+// Início do intervalo escolhido em ms desde a epoch; undefined = desde sempre
+function rangeStart(range: string): number | undefined {
+  const days = range === 'all' ? null : Number(range);
+  return days ? Date.now() - days * 24 * 60 * 60 * 1000 : undefined;
+}
+
+const EVENT_COLORS: Record<HistoryEventKind, string> = {
+  started: 'blue',
+  repeated: 'cyan',
+  paused: 'yellow',
+  resumed: 'teal',
+  finished: 'green',
+  cancelled: 'red',
+  acknowledged: 'grape'
+};
+
+export function HistoryTab({ config, onConfigChange }: HistoryTabProps) {
+  const [entries, setEntries] = useState<HistoryEntry[]>([]);
+  const [range, setRange] = useState<string>('7');
+  const [timerId, setTimerId] = useState<string | null>(null);
+  const [events, setEvents] = useState<string[]>([]);
+  const [groupBy, setGroupBy] = useState<StatsGroupBy>('timer');
+  const [stats, setStats] = useState<StatsReport | null>(null);
+  const [summaryOpened, setSummaryOpened] = useState(false);
+  const [weekOffset, setWeekOffset] = useState(0);
+  const [summary, setSummary] = useState('');
+  const [exportFormat, setExportFormat] = useState<ExportFormat>('csv');
+  const [exportMessage, setExportMessage] = useState<string | null>(null);
+  const [editing, setEditing] = useState<HistoryEntry | null>(null);
+  const [labelText, setLabelText] = useState('');
+  const [noteText, setNoteText] = useState('');
+  const [recentLabels, setRecentLabels] = useState<string[]>([]);
+
+  const refresh = useCallback(async () => {
+    try {
+      const from = rangeStart(range);
+      const [result, report] = await Promise.all([
+        queryHistory({
+          from,
+          timerId: timerId || undefined,
+          events: events.length > 0 ? (events as HistoryEventKind[]) : undefined,
+          limit: 200
+        }),
+        getStats({ from }, groupBy)
+      ]);
+      setEntries(result);
+      setStats(report);
+    } catch (error) {
+      console.error('Failed to load history', error);
+    }
+  }, [range, timerId, events, groupBy]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    if (!summaryOpened) return;
+    getWeeklySummary(weekOffset)
+      .then(setSummary)
+      .catch((error) => console.error('Failed to build weekly summary', error));
+  }, [summaryOpened, weekOffset]);
+
+  const openLabelEditor = (entry: HistoryEntry) => {
+    if (entry.runStartedAt === undefined || entry.runStartedAt === null) return;
+    setEditing(entry);
+    setLabelText(entry.label || '');
+    setNoteText(entry.note || '');
+    getRecentLabels()
+      .then(setRecentLabels)
+      .catch((error) => console.error('Failed to load recent labels', error));
+  };
+
+  const handleSaveLabel = async () => {
+    if (!editing) return;
+    try {
+      await labelRun(editing.timerId, editing.runStartedAt, labelText, noteText);
+      setEditing(null);
+      refresh();
+    } catch (error) {
+      console.error('Failed to label run', error);
+    }
+  };
+
+  const handleExport = async () => {
+    const format = EXPORT_FORMATS.find(f => f.value === exportFormat)!;
+    const path = await save({
+      defaultPath: `timer-history.${format.extension}`,
+      filters: [{ name: format.label, extensions: [format.extension] }]
+    });
+    if (!path) return;
+    try {
+      const count = await exportHistory(path, exportFormat, {
+        from: rangeStart(range),
+        timerIds: timerId ? [timerId] : undefined
+      });
+      setExportMessage(`Exported ${count} session${count === 1 ? '' : 's'} to ${path}`);
+    } catch (error) {
+      setExportMessage(String(error));
+    }
+  };
+
+  const rate = (value?: number) => (value === undefined || value === null ? '–' : `${Math.round(value * 100)}%`);
+
+  const timerName = (entry: HistoryEntry) => {
+    if (entry.name) return entry.name;
+    const timer = config.icons.find(icon => icon.id === entry.timerId);
+    return timer ? timer.name || timer.iconName : entry.timerId;
+  };
+
+  return (
+    <Stack gap="md">
+      <Card withBorder p="md">
+        <Text size="lg" fw={600}>History</Text>
+        <Text size="xs" c="dimmed" mb="md">
+          Every start, repeat, pause, finish and reset is kept on disk with when it happened and what triggered it
+        </Text>
+        <NumberInput
+          label="Keep History For (days)"
+          description="Older entries are removed automatically. Use 0 to keep everything."
+          min={0}
+          value={config.historyRetentionDays ?? 90}
+          onChange={(value) => onConfigChange({
+            ...config,
+            historyRetentionDays: typeof value === 'number' ? value : undefined
+          })}
+        />
+      </Card>
+
+      {stats && (
+        <Card withBorder p="md">
+          <Group justify="space-between" mb="md">
+            <div>
+              <Text size="lg" fw={600}>Statistics</Text>
+              <Text size="xs" c="dimmed">
+                {stats.overall.runs} runs, {rate(stats.overall.completionRate)} completed,{' '}
+                {formatSeconds(stats.overall.totalSeconds)} total, longest streak {stats.overall.longestStreakDays} day(s)
+                {stats.overall.topHours.length > 0 && `, busiest at ${stats.overall.topHours.map(h => `${String(h).padStart(2, '0')}:00`).join(', ')}`}
+              </Text>
+            </div>
+            <Group gap="xs">
+              <SegmentedControl
+                size="xs"
+                value={groupBy}
+                onChange={(value) => setGroupBy(value as StatsGroupBy)}
+                data={[
+                  { value: 'timer', label: 'Timer' },
+                  { value: 'day', label: 'Day' },
+                  { value: 'week', label: 'Week' },
+                  { value: 'label', label: 'Label' }
+                ]}
+              />
+              <Button size="xs" variant="light" onClick={() => {
+                setWeekOffset(0);
+                setSummaryOpened(true);
+              }}>
+                Weekly Summary
+              </Button>
+            </Group>
+          </Group>
+          <Stack gap={4}>
+            {stats.groups.length > 0 ? (
+              stats.groups.map((group) => (
+                <Group key={group.key} justify="space-between" wrap="nowrap">
+                  <Text size="sm" truncate="end">{group.label}</Text>
+                  <Group gap="md" wrap="nowrap">
+                    <Text size="xs" c="dimmed">{group.summary.runs} runs</Text>
+                    <Text size="xs" c="dimmed">{rate(group.summary.completionRate)} done</Text>
+                    <Text size="xs" c="dimmed">{formatSeconds(group.summary.totalSeconds)} total</Text>
+                    <Text size="xs" c="dimmed">
+                      {group.summary.averageSeconds !== undefined && group.summary.averageSeconds !== null
+                        ? `${formatSeconds(group.summary.averageSeconds)} avg`
+                        : ''}
+                    </Text>
+                  </Group>
+                </Group>
+              ))
+            ) : (
+              <Text size="sm" c="dimmed" ta="center" py="md">
+                No finished or reset runs in this range yet.
+              </Text>
+            )}
+          </Stack>
+        </Card>
+      )}
+
+      <Modal
+        opened={summaryOpened}
+        onClose={() => setSummaryOpened(false)}
+        title="Weekly Summary"
+        size="lg"
+      >
+        <Stack gap="sm">
+          <Group justify="space-between">
+            <Button size="xs" variant="subtle" onClick={() => setWeekOffset(weekOffset - 1)}>
+              Previous week
+            </Button>
+            <Button size="xs" variant="subtle" disabled={weekOffset >= 0} onClick={() => setWeekOffset(weekOffset + 1)}>
+              Next week
+            </Button>
+          </Group>
+          <Textarea
+            readOnly
+            autosize
+            minRows={10}
+            maxRows={24}
+            value={summary}
+            styles={{ input: { fontFamily: 'monospace', fontSize: 12 } }}
+          />
+          <Group justify="flex-end">
+            <Button size="xs" onClick={() => navigator.clipboard.writeText(summary)}>
+              Copy Markdown
+            </Button>
+          </Group>
+        </Stack>
+      </Modal>
+
+      <Modal
+        opened={editing !== null}
+        onClose={() => setEditing(null)}
+        title="Label Run"
+      >
+        <Stack gap="sm">
+          {editing && (
+            <Text size="xs" c="dimmed">
+              {timerName(editing)}, started {new Date(editing.runStartedAt!).toLocaleString()}
+            </Text>
+          )}
+          <Autocomplete
+            label="Label"
+            placeholder="What was this run for?"
+            value={labelText}
+            onChange={setLabelText}
+            data={recentLabels}
+          />
+          <Textarea
+            label="Note"
+            autosize
+            minRows={2}
+            value={noteText}
+            onChange={(event) => setNoteText(event.currentTarget.value)}
+          />
+          <Group justify="flex-end">
+            <Button size="xs" onClick={handleSaveLabel}>
+              Save
+            </Button>
+          </Group>
+        </Stack>
+      </Modal>
+
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md" align="flex-end">
+          <Group gap="xs" align="flex-end">
+            <Select
+              label="Range"
+              size="xs"
+              value={range}
+              onChange={(value) => setRange(value || '7')}
+              data={RANGE_OPTIONS}
+            />
+            <Select
+              label="Timer"
+              size="xs"
+              placeholder="All timers"
+              clearable
+              value={timerId}
+              onChange={setTimerId}
+              data={config.icons.map(icon => ({ value: icon.id, label: icon.name || icon.iconName }))}
+            />
+            <MultiSelect
+              label="Events"
+              size="xs"
+              placeholder={events.length === 0 ? 'All events' : undefined}
+              value={events}
+              onChange={setEvents}
+              data={Object.entries(HISTORY_EVENT_LABELS).map(([value, label]) => ({ value, label }))}
+            />
+          </Group>
+          <Group gap="xs" align="flex-end">
+            <Select
+              label="Export As"
+              size="xs"
+              w={130}
+              value={exportFormat}
+              onChange={(value) => setExportFormat((value as ExportFormat) || 'csv')}
+              data={EXPORT_FORMATS.map(({ value, label }) => ({ value, label }))}
+            />
+            <Button size="xs" variant="light" onClick={handleExport}>
+              Export
+            </Button>
+            <Button size="xs" variant="light" onClick={refresh}>
+              Refresh
+            </Button>
+          </Group>
+        </Group>
+        {exportMessage && (
+          <Text size="xs" c="dimmed" mb="sm">
+            {exportMessage}
+          </Text>
+        )}
+        <Stack gap={4}>
+          {entries.length > 0 ? (
+            entries.map((entry, index) => (
+              <Group key={`${entry.at}-${entry.timerId}-${index}`} justify="space-between" wrap="nowrap">
+                <Group gap="xs" wrap="nowrap" style={{ minWidth: 0 }}>
+                  <Badge size="sm" variant="light" color={EVENT_COLORS[entry.event]}>
+                    {HISTORY_EVENT_LABELS[entry.event]}
+                  </Badge>
+                  <Text size="sm" truncate="end">{timerName(entry)}</Text>
+                  {entry.runStartedAt !== undefined && entry.runStartedAt !== null && (
+                    <UnstyledButton onClick={() => openLabelEditor(entry)} title={entry.note || 'Add a label'}>
+                      <Text size="xs" c={entry.label ? undefined : 'dimmed'} fs={entry.label ? undefined : 'italic'}>
+                        {entry.label || '+ label'}
+                      </Text>
+                    </UnstyledButton>
+                  )}
+                  {entry.source && (
+                    <Text size="xs" c="dimmed">via {TRIGGER_SOURCE_LABELS[entry.source]}</Text>
+                  )}
+                </Group>
+                <Group gap="xs" wrap="nowrap">
+                  {entry.actualSeconds !== undefined && entry.actualSeconds !== null && (
+                    <Text size="xs" c="dimmed">
+                      {formatSeconds(entry.actualSeconds)}
+                      {entry.plannedSeconds ? ` / ${formatSeconds(entry.plannedSeconds)}` : ''}
+                    </Text>
+                  )}
+                  {entry.repeatCount > 0 && (
+                    <Text size="xs" c="dimmed">×{entry.repeatCount + 1}</Text>
+                  )}
+                  <Text size="xs" c="dimmed">{new Date(entry.at).toLocaleString()}</Text>
+                </Group>
+              </Group>
+            ))
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No history for these filters yet.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+    </Stack>
+  );
+}
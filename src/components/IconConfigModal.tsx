@@ -1,7 +1,9 @@
 import React, { useState, useEffect } from 'react';
-import { Modal, Radio, Button, Group, Text, Stack, Divider, TextInput, Switch, NumberInput, SegmentedControl } from '@mantine/core';
-import { IconConfig } from '../types/config';
+import { Modal, Radio, Button, Group, Text, Stack, Divider, TextInput, Switch, NumberInput, SegmentedControl, Select, ActionIcon, Chip } from '@mantine/core';
+import { AdjustKeybind, AlarmConfig, ChainRule, ChessClockConfig, IconConfig, PomodoroConfig, RandomDurationConfig, ResetConfig, TimerPreset, TimerSchedule, TriggerPolicy } from '../types/config';
 import { KeybindInput } from './KeybindInput';
+import { ScheduleEditor } from './ScheduleEditor';
+import { AdjustKeybindsEditor } from './AdjustKeybindsEditor';
 import { DurationInput } from './DurationInput';
 import * as Icons from 'lucide-react';
 
@@ -11,8 +13,37 @@ interface IconConfigModalProps {
   config: IconConfig | null;
   onSave: (config: IconConfig) => void;
   existingKeybinds: string[];
+  allTimers?: IconConfig[];
+  presets?: TimerPreset[];
 }
 
+const WEEKDAY_LABELS = ['Sun', 'Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat'];
+
+const DEFAULT_ALARM: AlarmConfig = {
+  time: '08:00',
+  recurrence: 'once',
+  weekdays: [],
+  snoozeMinutes: 9,
+  enabled: true
+};
+
+const DEFAULT_POMODORO: PomodoroConfig = {
+  shortBreak: 300,
+  longBreak: 900,
+  longBreakEvery: 4,
+  autoAdvance: false
+};
+
+const DEFAULT_CHESS_CLOCK: ChessClockConfig = {
+  players: ['Player 1', 'Player 2']
+};
+
+const DEFAULT_RESET: ResetConfig = {
+  time: '04:00',
+  weekdays: [],
+  timeZone: 'UTC'
+};
+
 const LUCIDE_ICONS = [
   // Core timer icons
   'Timer', 'Alarm', 'Clock', 'Watch', 'Hourglass', 'Calendar', 'CalendarClock', 'TimerReset', 'TimerOff',
@@ -45,13 +76,25 @@ const LUCIDE_ICONS = [
   'Volleyball', 'Wand', 'Axe', 'Pickaxe', 'Cannabis'
 ];
 
-export function IconConfigModal({ opened, onClose, config, onSave, existingKeybinds }: IconConfigModalProps) {
+export function IconConfigModal({ opened, onClose, config, onSave, existingKeybinds, allTimers = [], presets = [] }: IconConfigModalProps) {
   const [name, setName] = useState<string>('');
   const [iconName, setIconName] = useState<string>('');
   const [keybind, setKeybind] = useState<string>('');
+  const [lapKeybind, setLapKeybind] = useState<string>('');
+  const [adjustKeybinds, setAdjustKeybinds] = useState<AdjustKeybind[]>([]);
   const [timerDuration, setTimerDuration] = useState<number>(90);
   const [notificationType, setNotificationType] = useState<'none' | 'sound' | 'notification' | 'both'>('sound');
-  const [timerType, setTimerType] = useState<'countdown' | 'stopwatch'>('countdown');
+  const [timerType, setTimerType] = useState<NonNullable<IconConfig['timerType']>>('countdown');
+  const [maxCharges, setMaxCharges] = useState<number>(2);
+  const [pomodoro, setPomodoro] = useState<PomodoroConfig>(DEFAULT_POMODORO);
+  const [chessClock, setChessClock] = useState<ChessClockConfig>(DEFAULT_CHESS_CLOCK);
+  const [randomDuration, setRandomDuration] = useState<RandomDurationConfig | undefined>(undefined);
+  const [alarm, setAlarm] = useState<AlarmConfig>(DEFAULT_ALARM);
+  const [reset, setReset] = useState<ResetConfig>(DEFAULT_RESET);
+  const [schedule, setSchedule] = useState<TimerSchedule | undefined>(undefined);
+  const [overtime, setOvertime] = useState<boolean>(false);
+  const [triggerPolicy, setTriggerPolicy] = useState<TriggerPolicy>('restart');
+  const [suspendPolicy, setSuspendPolicy] = useState<'realTime' | 'pause'>('realTime');
   const [repeatEnabled, setRepeatEnabled] = useState<boolean>(false);
   const [repeatTimes, setRepeatTimes] = useState<number>(1);
   const [repeatInterval, setRepeatInterval] = useState<number>(0);
@@ -59,9 +102,16 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
   const [repeatIntervalNotification, setRepeatIntervalNotification] = useState<boolean>(false);
   const [repeatIntervalNotificationText, setRepeatIntervalNotificationText] = useState<string>('');
   const [completionNotificationText, setCompletionNotificationText] = useState<string>('');
+  const [chainRules, setChainRules] = useState<ChainRule[]>([]);
   const [keybindError, setKeybindError] = useState<string>('');
 
   const isCountdown = timerType === 'countdown';
+  const isAlarm = timerType === 'alarm';
+  const isReset = timerType === 'reset';
+  const isCharges = timerType === 'charges';
+  const isPomodoro = timerType === 'pomodoro';
+  const isChessClock = timerType === 'chessClock';
+  const chessBonus = chessClock.delay ? 'delay' : chessClock.increment ? 'increment' : 'none';
   const showIntervalNotificationText =
     repeatIntervalNotification &&
     repeatInterval > 0 &&
@@ -72,9 +122,18 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
       setName(config.name || '');
       setIconName(config.iconName || '');
       setKeybind(config.keybind || '');
+      setLapKeybind(config.lapKeybind || '');
+      setAdjustKeybinds(config.adjustKeybinds || []);
       setTimerDuration(config.timerDuration);
       setNotificationType(config.notificationType);
       setTimerType(config.timerType || 'countdown');
+      setOvertime(config.overtime || false);
+      setMaxCharges(config.charges?.max || 2);
+      setPomodoro(config.pomodoro || DEFAULT_POMODORO);
+      setChessClock(config.chessClock || DEFAULT_CHESS_CLOCK);
+      setRandomDuration(config.randomDuration);
+      setTriggerPolicy(config.triggerPolicy || 'restart');
+      setSuspendPolicy(config.suspendPolicy || 'realTime');
       setRepeatEnabled(config.repeat?.enabled || false);
       setRepeatTimes(config.repeat?.times || 1);
       setRepeatInterval(config.repeat?.interval || 0);
@@ -82,14 +141,27 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
       setRepeatIntervalNotification(config.repeat?.intervalNotification || false);
       setRepeatIntervalNotificationText(config.repeat?.intervalNotificationText || '');
       setCompletionNotificationText(config.completionNotificationText || '');
+      setChainRules(config.chain || []);
+      setAlarm(config.alarm || DEFAULT_ALARM);
+      setReset(config.reset || DEFAULT_RESET);
+      setSchedule(config.schedule);
       setKeybindError('');
     } else {
       setName('');
       setIconName('Timer');
       setKeybind('');
+      setLapKeybind('');
+      setAdjustKeybinds([]);
       setTimerDuration(90);
       setNotificationType('notification');
       setTimerType('countdown');
+      setOvertime(false);
+      setMaxCharges(2);
+      setPomodoro(DEFAULT_POMODORO);
+      setChessClock(DEFAULT_CHESS_CLOCK);
+      setRandomDuration(undefined);
+      setTriggerPolicy('restart');
+      setSuspendPolicy('realTime');
       setRepeatEnabled(false);
       setRepeatTimes(1);
       setRepeatInterval(0);
@@ -97,6 +169,10 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
       setRepeatIntervalNotification(false);
       setRepeatIntervalNotificationText('');
       setCompletionNotificationText('');
+      setChainRules([]);
+      setAlarm(DEFAULT_ALARM);
+      setReset(DEFAULT_RESET);
+      setSchedule(undefined);
       setKeybindError('');
     }
   }, [config, opened]);
@@ -119,6 +195,24 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
       }
     }
 
+    const trimmedLapKeybind = timerType === 'stopwatch' ? lapKeybind.trim() : '';
+    if (trimmedLapKeybind) {
+      const isSameAsCurrent = trimmedLapKeybind === (config?.lapKeybind?.trim() ?? '');
+      if (trimmedLapKeybind === trimmedKeybind || (!isSameAsCurrent && existingKeybinds.includes(trimmedLapKeybind))) {
+        setKeybindError('The lap shortcut is already in use');
+        return;
+      }
+    }
+
+    const validAdjustKeybinds = isCountdown || timerType === 'stopwatch'
+      ? adjustKeybinds.filter(a => a.keybind.trim() && a.deltaSeconds !== 0)
+      : [];
+    const ownKeybinds = [trimmedKeybind, trimmedLapKeybind].filter(Boolean);
+    if (validAdjustKeybinds.some(a => ownKeybinds.includes(a.keybind.trim()))) {
+      setKeybindError('An adjust shortcut is already used by this timer');
+      return;
+    }
+
     setKeybindError('');
 
     if (!iconName) {
@@ -126,14 +220,39 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
       return;
     }
 
+    if (isAlarm && !/^\d{1,2}:\d{2}$/.test(alarm.time)) {
+      setKeybindError('Alarm time must use the HH:MM format');
+      return;
+    }
+
+    if (isAlarm && alarm.recurrence === 'weekly' && !alarm.weekdays?.length) {
+      setKeybindError('Pick at least one weekday for a weekly alarm');
+      return;
+    }
+
+    if (isReset && !/^\d{1,2}:\d{2}$/.test(reset.time)) {
+      setKeybindError('Reset time must use the HH:MM format');
+      return;
+    }
+
+    if (isCountdown && randomDuration && randomDuration.max < randomDuration.min) {
+      setKeybindError('The random duration maximum must be at least the minimum');
+      return;
+    }
+
     const newConfig: IconConfig = {
       id: config?.id || `icon-${Date.now()}`,
       name: name.trim() || undefined,
       iconName,
       keybind: trimmedKeybind || undefined,
+      lapKeybind: trimmedLapKeybind || undefined,
+      adjustKeybinds: validAdjustKeybinds.length > 0 ? validAdjustKeybinds : undefined,
       timerDuration,
       notificationType,
       timerType,
+      overtime: isCountdown && overtime ? true : undefined,
+      triggerPolicy: isCountdown && triggerPolicy !== 'restart' ? triggerPolicy : undefined,
+      suspendPolicy: (isCountdown || timerType === 'stopwatch') && suspendPolicy !== 'realTime' ? suspendPolicy : undefined,
       repeat: repeatEnabled ? {
         enabled: true,
         times: repeatTimes,
@@ -142,13 +261,59 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
         intervalNotification: repeatInterval > 0 ? repeatIntervalNotification : false,
         intervalNotificationText: repeatInterval > 0 && repeatIntervalNotificationText ? repeatIntervalNotificationText : undefined
       } : undefined,
-      completionNotificationText: completionNotificationText || undefined
+      completionNotificationText: completionNotificationText || undefined,
+      chain: !isPomodoro && chainRules.length > 0 ? chainRules : undefined,
+      alarm: isAlarm ? {
+        ...alarm,
+        weekdays: alarm.recurrence === 'weekly' ? alarm.weekdays : undefined,
+        timeZone: alarm.timeZone?.trim() || undefined
+      } : undefined,
+      charges: isCharges ? { max: maxCharges } : undefined,
+      pomodoro: isPomodoro ? pomodoro : undefined,
+      randomDuration: isCountdown && randomDuration ? {
+        ...randomDuration,
+        gapMin: repeatEnabled ? randomDuration.gapMin : undefined,
+        gapMax: repeatEnabled && randomDuration.gapMin !== undefined ? randomDuration.gapMax : undefined
+      } : undefined,
+      chessClock: isChessClock ? {
+        players: chessClock.players.map((player, index) => player.trim() || `Player ${index + 1}`),
+        increment: chessClock.increment || undefined,
+        delay: chessClock.delay || undefined
+      } : undefined,
+      reset: isReset ? {
+        ...reset,
+        weekdays: reset.weekdays?.length && !reset.monthDay ? reset.weekdays : undefined,
+        timeZone: reset.timeZone?.trim() || undefined
+      } : undefined,
+      schedule: isCountdown || timerType === 'stopwatch' ? schedule : undefined
     };
 
     onSave(newConfig);
     onClose();
   };
 
+  const chainTargetOptions = [
+    ...allTimers.map(timer => ({
+      value: `timer:${timer.id}`,
+      label: `Timer: ${timer.name || timer.iconName || timer.id}`
+    })),
+    ...presets.map(preset => ({
+      value: `preset:${preset.id}`,
+      label: `Preset: ${preset.name}`
+    }))
+  ];
+
+  const updateChainRule = (ruleId: string, changes: Partial<ChainRule>) => {
+    setChainRules(rules => rules.map(rule => rule.id === ruleId ? { ...rule, ...changes } : rule));
+  };
+
+  const parseChainTarget = (value: string | null): ChainRule['target'] | null => {
+    if (!value) return null;
+    const [type, ...rest] = value.split(':');
+    const id = rest.join(':');
+    return type === 'preset' ? { type: 'preset', presetId: id } : { type: 'timer', timerId: id };
+  };
+
   const selectedIconComponent = iconName && (Icons as any)[iconName]
     ? React.createElement((Icons as any)[iconName], { size: 40 })
     : React.createElement(Icons.Timer, { size: 40 });
@@ -167,21 +332,42 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
           <SegmentedControl
             value={timerType}
             onChange={(value) => {
-              setTimerType(value as 'countdown' | 'stopwatch');
-              if (value === 'stopwatch') {
+              setTimerType(value as NonNullable<IconConfig['timerType']>);
+              if (value !== 'countdown') {
                 setRepeatEnabled(false);
               }
+              if (value === 'pomodoro' && !config?.pomodoro) {
+                setTimerDuration(1500);
+              }
+              if (value === 'chessClock' && !config?.chessClock) {
+                setTimerDuration(600);
+              }
             }}
             data={[
               { value: 'countdown', label: 'Countdown Timer' },
-              { value: 'stopwatch', label: 'Stopwatch' }
+              { value: 'stopwatch', label: 'Stopwatch' },
+              { value: 'alarm', label: 'Alarm' },
+              { value: 'reset', label: 'Reset Countdown' },
+              { value: 'charges', label: 'Charges' },
+              { value: 'pomodoro', label: 'Pomodoro' },
+              { value: 'chessClock', label: 'Turn Clock' }
             ]}
             fullWidth
           />
           <Text size="xs" c="dimmed" mt={4}>
             {timerType === 'countdown' 
               ? 'Timer counts down from a set duration' 
-              : 'Stopwatch counts up from 0 (start/stop controls)'}
+              : timerType === 'stopwatch'
+                ? 'Stopwatch counts up from 0 (start/stop controls)'
+                : timerType === 'alarm'
+                  ? 'Alarm rings at a wall-clock time, once or on a schedule'
+                  : timerType === 'reset'
+                    ? 'Counts down to a recurring reset (e.g. daily 04:00 server time) and rolls over on its own'
+                    : timerType === 'charges'
+                      ? 'Ability with several charges that recharge one at a time'
+                      : timerType === 'pomodoro'
+                        ? 'Focus and break cycle with a long break every few rounds and a daily counter'
+                        : 'Chess-style clock where each player has a time bank and one shortcut passes the turn'}
           </Text>
         </div>
 
@@ -300,15 +486,365 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
             <Text size="xs" c="dimmed" mt={4}>
               {timerType === 'stopwatch' 
                 ? 'Shortcuts let you tap to start/pause and hold to reset. Leave empty to control it from the UI or tray.'
-                : 'Add Alt/Ctrl/Shift + key if you want a global shortcut. Leave empty to start this timer from the Settings or tray.'}
+                : isAlarm
+                  ? 'The shortcut dismisses the alarm while it is ringing. Snooze it from the tray.'
+                  : isCharges
+                    ? 'Each press uses a charge. Resetting the timer refills every charge.'
+                    : isPomodoro
+                      ? 'The shortcut starts or pauses the current phase. Skip a phase or reset the day from the tray.'
+                      : isChessClock
+                      ? 'The first press starts the clock, every press after that passes the turn. Resetting gives every player a full bank.'
+                      : 'Add Alt/Ctrl/Shift + key if you want a global shortcut. Leave empty to start this timer from the Settings or tray.'}
             </Text>
           </div>
 
+          {timerType === 'stopwatch' && (
+            <div>
+              <Text size="sm" fw={500} mb="xs">Lap Shortcut (optional)</Text>
+              <KeybindInput
+                value={lapKeybind}
+                onChange={(value) => {
+                  setLapKeybind(value);
+                  setKeybindError('');
+                }}
+                onError={setKeybindError}
+                existingKeybinds={[...existingKeybinds, keybind.trim()].filter(Boolean)}
+                currentTimerId={config?.id}
+                allTimers={[]}
+                optional
+              />
+              <Text size="xs" c="dimmed" mt={4}>
+                Records a split while the stopwatch runs. Laps are kept until the stopwatch restarts.
+              </Text>
+            </div>
+          )}
+
+          {isCountdown && (
+            <Stack gap="md">
+              {!randomDuration && (
+                <DurationInput
+                  label="Timer Duration"
+                  value={timerDuration}
+                  onChange={setTimerDuration}
+                />
+              )}
+              <Switch
+                label="Random Duration"
+                description="Pick a new duration within a range on every run, e.g. for reaction drills"
+                checked={Boolean(randomDuration)}
+                onChange={(e) => setRandomDuration(e.currentTarget.checked
+                  ? { min: Math.max(1, Math.floor(timerDuration / 2)), max: timerDuration }
+                  : undefined)}
+              />
+              {randomDuration && (
+                <Stack gap="md" pl="md" style={{ borderLeft: '2px solid #e9ecef' }}>
+                  <Group grow align="flex-start">
+                    <DurationInput
+                      label="Minimum"
+                      value={randomDuration.min}
+                      onChange={(value) => setRandomDuration({ ...randomDuration, min: value })}
+                    />
+                    <DurationInput
+                      label="Maximum"
+                      value={randomDuration.max}
+                      onChange={(value) => {
+                        setRandomDuration({ ...randomDuration, max: value });
+                        setTimerDuration(value);
+                      }}
+                    />
+                  </Group>
+                  <NumberInput
+                    label="Seed (optional)"
+                    description="The same seed replays the same sequence of durations. Leave empty for a new sequence each session"
+                    value={randomDuration.seed ?? ''}
+                    onChange={(value) => setRandomDuration({ ...randomDuration, seed: typeof value === 'number' ? value : undefined })}
+                    min={0}
+                    max={Number.MAX_SAFE_INTEGER}
+                    allowDecimal={false}
+                  />
+                </Stack>
+              )}
+            </Stack>
+          )}
+
+          {isCharges && (
+            <Group grow align="flex-start">
+              <NumberInput
+                label="Max Charges"
+                description="Charges available when fully recharged"
+                value={maxCharges}
+                onChange={(value) => setMaxCharges(typeof value === 'number' && value > 0 ? value : 1)}
+                min={1}
+                max={20}
+              />
+              <DurationInput
+                label="Recharge Duration"
+                value={timerDuration}
+                onChange={setTimerDuration}
+              />
+            </Group>
+          )}
+
+          {isPomodoro && (
+            <Stack gap="md">
+              <DurationInput
+                label="Focus Duration"
+                value={timerDuration}
+                onChange={setTimerDuration}
+              />
+              <Group grow align="flex-start">
+                <DurationInput
+                  label="Short Break"
+                  value={pomodoro.shortBreak}
+                  onChange={(value) => setPomodoro({ ...pomodoro, shortBreak: value })}
+                />
+                <DurationInput
+                  label="Long Break"
+                  value={pomodoro.longBreak}
+                  onChange={(value) => setPomodoro({ ...pomodoro, longBreak: value })}
+                />
+              </Group>
+              <NumberInput
+                label="Long Break Every"
+                description="Number of focus sessions before a long break"
+                value={pomodoro.longBreakEvery}
+                onChange={(value) => setPomodoro({ ...pomodoro, longBreakEvery: typeof value === 'number' && value > 0 ? value : 1 })}
+                min={1}
+                max={12}
+              />
+              <Switch
+                label="Auto-advance"
+                description="Start the next phase as soon as the current one ends"
+                checked={pomodoro.autoAdvance || false}
+                onChange={(e) => setPomodoro({ ...pomodoro, autoAdvance: e.currentTarget.checked })}
+              />
+            </Stack>
+          )}
+
+          {isChessClock && (
+            <Stack gap="md">
+              <DurationInput
+                label="Time Bank per Player"
+                value={timerDuration}
+                onChange={setTimerDuration}
+              />
+              <div>
+                <Text size="sm" fw={500} mb="xs">Players</Text>
+                <Stack gap="xs">
+                  {chessClock.players.map((player, index) => (
+                    <Group key={index} gap="xs" wrap="nowrap">
+                      <TextInput
+                        placeholder={`Player ${index + 1}`}
+                        value={player}
+                        onChange={(e) => {
+                          const players = [...chessClock.players];
+                          players[index] = e.target.value;
+                          setChessClock({ ...chessClock, players });
+                        }}
+                        style={{ flex: 1 }}
+                      />
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        disabled={chessClock.players.length <= 2}
+                        onClick={() => setChessClock({ ...chessClock, players: chessClock.players.filter((_, i) => i !== index) })}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  ))}
+                </Stack>
+                <Button
+                  size="xs"
+                  variant="light"
+                  mt="xs"
+                  onClick={() => setChessClock({ ...chessClock, players: [...chessClock.players, `Player ${chessClock.players.length + 1}`] })}
+                >
+                  Add Player
+                </Button>
+              </div>
+              <Group grow align="flex-start">
+                <Select
+                  label="Per-move Bonus"
+                  value={chessBonus}
+                  onChange={(value) => {
+                    const seconds = chessClock.increment || chessClock.delay || 5;
+                    setChessClock({
+                      ...chessClock,
+                      increment: value === 'increment' ? seconds : undefined,
+                      delay: value === 'delay' ? seconds : undefined
+                    });
+                  }}
+                  data={[
+                    { value: 'none', label: 'None' },
+                    { value: 'increment', label: 'Increment (added after each move)' },
+                    { value: 'delay', label: 'Delay (free time before the bank runs)' }
+                  ]}
+                />
+                <NumberInput
+                  label="Bonus Seconds"
+                  value={chessClock.increment || chessClock.delay || 0}
+                  onChange={(value) => {
+                    const seconds = typeof value === 'number' && value > 0 ? value : 0;
+                    setChessClock({
+                      ...chessClock,
+                      increment: chessBonus === 'increment' ? seconds : undefined,
+                      delay: chessBonus === 'delay' ? seconds : undefined
+                    });
+                  }}
+                  min={0}
+                  max={600}
+                  disabled={chessBonus === 'none'}
+                />
+              </Group>
+            </Stack>
+          )}
+
+          {isReset && (
+            <Stack gap="md">
+              <Group grow>
+                <TextInput
+                  label="Reset Time"
+                  description="24h format, HH:MM"
+                  placeholder="04:00"
+                  value={reset.time}
+                  onChange={(e) => setReset({ ...reset, time: e.target.value })}
+                />
+                <TextInput
+                  label="Time Zone"
+                  description="Server time zone, e.g. UTC or America/New_York"
+                  placeholder="Local"
+                  value={reset.timeZone || ''}
+                  onChange={(e) => setReset({ ...reset, timeZone: e.target.value })}
+                />
+              </Group>
+              <NumberInput
+                label="Day of Month"
+                description="For monthly resets; shorter months use their last day. Leave empty to pick weekdays."
+                placeholder="Not monthly"
+                value={reset.monthDay ?? ''}
+                onChange={(value) => setReset({ ...reset, monthDay: typeof value === 'number' ? value : undefined })}
+                min={1}
+                max={31}
+              />
+              {!reset.monthDay && (
+                <div>
+                  <Text size="sm" fw={500} mb={4}>Reset Days</Text>
+                  <Chip.Group
+                    multiple
+                    value={(reset.weekdays || []).map(String)}
+                    onChange={(values) => setReset({ ...reset, weekdays: values.map(Number).sort() })}
+                  >
+                    <Group gap="xs">
+                      {WEEKDAY_LABELS.map((label, index) => (
+                        <Chip key={label} value={String(index)} size="xs">{label}</Chip>
+                      ))}
+                    </Group>
+                  </Chip.Group>
+                  <Text size="xs" c="dimmed" mt={4}>
+                    Leave every day unselected for a daily reset
+                  </Text>
+                </div>
+              )}
+            </Stack>
+          )}
+
+          {isAlarm && (
+            <Stack gap="md">
+              <Group grow>
+                <TextInput
+                  label="Alarm Time"
+                  description="24h format, HH:MM"
+                  placeholder="08:00"
+                  value={alarm.time}
+                  onChange={(e) => setAlarm({ ...alarm, time: e.target.value })}
+                />
+                <Select
+                  label="Repeat"
+                  description="When the alarm rings again"
+                  value={alarm.recurrence}
+                  onChange={(value) => setAlarm({ ...alarm, recurrence: (value as AlarmConfig['recurrence']) || 'once' })}
+                  data={[
+                    { value: 'once', label: 'Once' },
+                    { value: 'daily', label: 'Every day' },
+                    { value: 'weekly', label: 'Selected weekdays' }
+                  ]}
+                />
+              </Group>
+              {alarm.recurrence === 'weekly' && (
+                <Chip.Group
+                  multiple
+                  value={(alarm.weekdays || []).map(String)}
+                  onChange={(values) => setAlarm({ ...alarm, weekdays: values.map(Number).sort() })}
+                >
+                  <Group gap="xs">
+                    {WEEKDAY_LABELS.map((label, index) => (
+                      <Chip key={label} value={String(index)} size="xs">{label}</Chip>
+                    ))}
+                  </Group>
+                </Chip.Group>
+              )}
+              <Group grow>
+                <TextInput
+                  label="Time Zone"
+                  description="IANA name like Europe/Berlin (empty = system time zone)"
+                  placeholder="Local"
+                  value={alarm.timeZone || ''}
+                  onChange={(e) => setAlarm({ ...alarm, timeZone: e.target.value })}
+                />
+                <NumberInput
+                  label="Snooze (minutes)"
+                  description="Default snooze length"
+                  value={alarm.snoozeMinutes || 9}
+                  onChange={(value) => setAlarm({ ...alarm, snoozeMinutes: typeof value === 'number' && value > 0 ? value : 9 })}
+                  min={1}
+                  max={120}
+                />
+              </Group>
+              <Switch
+                label="Alarm Enabled"
+                description="Disabled alarms stay configured but never ring"
+                checked={alarm.enabled !== false}
+                onChange={(e) => setAlarm({ ...alarm, enabled: e.currentTarget.checked })}
+              />
+            </Stack>
+          )}
+
           {isCountdown && (
-            <DurationInput
-              label="Timer Duration"
-              value={timerDuration}
-              onChange={setTimerDuration}
+            <Select
+              label="When Triggered While Running"
+              description="What the shortcut does if this timer is already counting down"
+              value={triggerPolicy}
+              onChange={(value) => setTriggerPolicy((value as TriggerPolicy) || 'restart')}
+              data={[
+                { value: 'restart', label: 'Restart from the full duration' },
+                { value: 'ignore', label: 'Ignore the trigger' },
+                { value: 'pause', label: 'Toggle pause' },
+                { value: 'reset', label: 'Reset and stop' },
+                { value: 'stack', label: 'Start another instance in parallel' }
+              ]}
+            />
+          )}
+
+          {(isCountdown || timerType === 'stopwatch') && (
+            <Select
+              label="While the Computer Sleeps"
+              description="What happens to a running timer when the system is suspended"
+              value={suspendPolicy}
+              onChange={(value) => setSuspendPolicy((value as 'realTime' | 'pause') || 'realTime')}
+              data={[
+                { value: 'realTime', label: 'Keep counting real time' },
+                { value: 'pause', label: 'Pause while suspended' }
+              ]}
+            />
+          )}
+
+          {isCountdown && (
+            <Switch
+              label="Overtime"
+              description="Keep counting as negative time after reaching zero until you press the shortcut or reset"
+              checked={overtime}
+              onChange={(e) => setOvertime(e.currentTarget.checked)}
             />
           )}
 
@@ -336,6 +872,34 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
                     value={repeatInterval}
                     onChange={setRepeatInterval}
                   />
+                  {randomDuration && (
+                    <div>
+                      <Switch
+                        label="Random Interval"
+                        description="Pick the interval before each repeat within a range too"
+                        checked={randomDuration.gapMin !== undefined}
+                        onChange={(e) => setRandomDuration({
+                          ...randomDuration,
+                          gapMin: e.currentTarget.checked ? Math.max(1, repeatInterval) : undefined,
+                          gapMax: e.currentTarget.checked ? Math.max(1, repeatInterval) * 2 : undefined
+                        })}
+                      />
+                      {randomDuration.gapMin !== undefined && (
+                        <Group grow align="flex-start" mt="xs">
+                          <DurationInput
+                            label="Minimum Interval"
+                            value={randomDuration.gapMin}
+                            onChange={(value) => setRandomDuration({ ...randomDuration, gapMin: value })}
+                          />
+                          <DurationInput
+                            label="Maximum Interval"
+                            value={randomDuration.gapMax ?? randomDuration.gapMin}
+                            onChange={(value) => setRandomDuration({ ...randomDuration, gapMax: value })}
+                          />
+                        </Group>
+                      )}
+                    </div>
+                  )}
                   {repeatInterval > 0 && (
                     <>
                       <div>
@@ -387,6 +951,105 @@ export function IconConfigModal({ opened, onClose, config, onSave, existingKeybi
             </div>
           )}
 
+          {(isCountdown || timerType === 'stopwatch') && (
+            <AdjustKeybindsEditor
+              value={adjustKeybinds}
+              onChange={setAdjustKeybinds}
+              existingKeybinds={existingKeybinds}
+            />
+          )}
+
+          {(isCountdown || timerType === 'stopwatch') && (
+            <ScheduleEditor value={schedule} onChange={setSchedule} />
+          )}
+
+          {/* As fases do Pomodoro rodam no backend e não disparam regras de encadeamento */}
+          {!isPomodoro && (
+            <div>
+              <Group justify="space-between" mb="xs">
+                <div>
+                  <Text size="sm" fw={500}>Chain Rules</Text>
+                  <Text size="xs" c="dimmed">Start another timer or preset when this one finishes, reaches a phase or is cancelled</Text>
+                </div>
+                <Button
+                  size="xs"
+                  variant="light"
+                  disabled={chainTargetOptions.length === 0}
+                  onClick={() => {
+                    const target = parseChainTarget(chainTargetOptions[0]?.value ?? null);
+                    if (!target) return;
+                    setChainRules(rules => [...rules, { id: `chain-${Date.now()}`, trigger: 'finished', target }]);
+                  }}
+                >
+                  Add rule
+                </Button>
+              </Group>
+              <Stack gap="xs">
+                {chainRules.map((rule) => (
+                  <Group key={rule.id} gap="xs" align="flex-end" wrap="nowrap">
+                    <Select
+                      label="When"
+                      value={rule.trigger}
+                      onChange={(value) => updateChainRule(rule.id, {
+                        trigger: (value as ChainRule['trigger']) || 'finished',
+                        phase: value === 'phase' ? rule.phase || 2 : undefined
+                      })}
+                      data={[
+                        { value: 'finished', label: 'Finished' },
+                        { value: 'phase', label: 'Phase reached' },
+                        { value: 'cancelled', label: 'Cancelled' }
+                      ]}
+                      style={{ width: 130 }}
+                    />
+                    {rule.trigger === 'phase' && (
+                      <NumberInput
+                        label="Phase"
+                        value={rule.phase || 1}
+                        onChange={(value) => updateChainRule(rule.id, { phase: typeof value === 'number' ? value : 1 })}
+                        min={1}
+                        style={{ width: 80 }}
+                      />
+                    )}
+                    <Select
+                      label="Start"
+                      value={rule.target.type === 'timer' ? `timer:${rule.target.timerId}` : `preset:${rule.target.presetId}`}
+                      onChange={(value) => {
+                        const target = parseChainTarget(value);
+                        if (target) updateChainRule(rule.id, { target });
+                      }}
+                      data={chainTargetOptions}
+                      searchable
+                      style={{ flex: 1 }}
+                    />
+                    <NumberInput
+                      label="Delay (s)"
+                      value={rule.delay || 0}
+                      onChange={(value) => updateChainRule(rule.id, { delay: typeof value === 'number' && value > 0 ? value : undefined })}
+                      min={0}
+                      style={{ width: 90 }}
+                    />
+                    <NumberInput
+                      label="Max runs"
+                      description="0 = no cap"
+                      value={rule.maxRuns || 0}
+                      onChange={(value) => updateChainRule(rule.id, { maxRuns: typeof value === 'number' && value > 0 ? value : undefined })}
+                      min={0}
+                      style={{ width: 90 }}
+                    />
+                    <ActionIcon
+                      color="red"
+                      variant="light"
+                      mb={4}
+                      onClick={() => setChainRules(rules => rules.filter(r => r.id !== rule.id))}
+                    >
+                      <Icons.Trash2 size={16} />
+                    </ActionIcon>
+                  </Group>
+                ))}
+              </Stack>
+            </div>
+          )}
+
           <div>
             <Text size="sm" fw={500} mb="xs">Notification on Completion</Text>
             <Radio.Group
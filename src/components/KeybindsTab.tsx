@@ -2,6 +2,8 @@ import { useState } from 'react';
 import { Stack, Text, Card, Button, Group, ActionIcon } from '@mantine/core';
 import { AppConfig, TimerPreset } from '../types/config';
 import { KeybindInput } from './KeybindInput';
+import { allConfiguredKeybinds } from '../utils/keybinds';
+import { AdjustKeybindsEditor } from './AdjustKeybindsEditor';
 import { TimerPresetModal } from './TimerPresetModal';
 import * as Icons from 'lucide-react';
 
@@ -14,12 +16,7 @@ export function KeybindsTab({ config, onConfigChange }: KeybindsTabProps) {
   const [presetModalOpened, setPresetModalOpened] = useState(false);
   const [selectedPreset, setSelectedPreset] = useState<TimerPreset | null>(null);
 
-  const allKeybinds = [
-    ...(config.icons?.map(i => i.keybind?.trim()) || []),
-    ...(config.timerPresets?.map(p => p.keybind?.trim()) || []),
-    config.resetAllTimersKeybind?.trim(),
-    config.quickCreateTimerKeybind?.trim()
-  ].filter((key): key is string => Boolean(key));
+  const allKeybinds = allConfiguredKeybinds(config);
 
   const handleSavePreset = (preset: TimerPreset) => {
     const presets = config.timerPresets || [];
@@ -88,6 +85,17 @@ export function KeybindsTab({ config, onConfigChange }: KeybindsTabProps) {
               Keyboard shortcut to quickly create a timer with direct input
             </Text>
           </div>
+
+          <AdjustKeybindsEditor
+            title="Adjust Active Timer"
+            description="Add or remove time on the most recently started timer that is still running or paused"
+            value={config.adjustKeybinds || []}
+            onChange={(adjustKeybinds) => onConfigChange({
+              ...config,
+              adjustKeybinds: adjustKeybinds.length > 0 ? adjustKeybinds : undefined
+            })}
+            existingKeybinds={allKeybinds.filter(k => !(config.adjustKeybinds || []).some(a => a.keybind === k))}
+          />
         </Stack>
       </Card>
 
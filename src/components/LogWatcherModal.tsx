@@ -0,0 +1,157 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Text, Stack, TextInput, Select, Switch } from '@mantine/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { IconConfig, LogWatchAction, LogWatcher } from '../types/config';
+
+interface LogWatcherModalProps {
+  opened: boolean;
+  onClose: () => void;
+  watcher: LogWatcher | null;
+  timers: IconConfig[];
+  onSave: (watcher: LogWatcher) => void;
+}
+
+export function LogWatcherModal({ opened, onClose, watcher, timers, onSave }: LogWatcherModalProps) {
+  const [name, setName] = useState<string>('');
+  const [path, setPath] = useState<string>('');
+  const [pattern, setPattern] = useState<string>('');
+  const [action, setAction] = useState<LogWatchAction>('start');
+  const [timerId, setTimerId] = useState<string | null>(null);
+  const [durationGroup, setDurationGroup] = useState<string>('');
+  const [enabled, setEnabled] = useState<boolean>(true);
+  const [error, setError] = useState<string>('');
+
+  useEffect(() => {
+    setName(watcher?.name || '');
+    setPath(watcher?.path || '');
+    setPattern(watcher?.pattern || '');
+    setAction(watcher?.action || 'start');
+    setTimerId(watcher?.timerId || null);
+    setDurationGroup(watcher?.durationGroup || '');
+    setEnabled(watcher?.enabled !== false);
+    setError('');
+  }, [watcher, opened]);
+
+  const handleBrowse = async () => {
+    const selected = await open({
+      multiple: false,
+      directory: false,
+      filters: [{ name: 'Log files', extensions: ['log', 'txt'] }, { name: 'All files', extensions: ['*'] }]
+    });
+    if (typeof selected === 'string') {
+      setPath(selected);
+    }
+  };
+
+  const handleSave = () => {
+    if (!path.trim()) {
+      setError('Choose the log file to watch');
+      return;
+    }
+    if (!pattern.trim()) {
+      setError('Enter a pattern to match');
+      return;
+    }
+    if (!timerId) {
+      setError('Choose the timer to control');
+      return;
+    }
+
+    onSave({
+      id: watcher?.id || `logwatch-${Date.now()}`,
+      name: name.trim() || undefined,
+      path: path.trim(),
+      pattern,
+      action,
+      timerId,
+      durationGroup: action === 'start' && durationGroup.trim() ? durationGroup.trim() : undefined,
+      enabled
+    });
+    onClose();
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={watcher ? 'Edit Log Trigger' : 'Create Log Trigger'}
+      size="md"
+    >
+      <Stack gap="md">
+        <TextInput
+          label="Name"
+          placeholder="e.g., Boss engaged"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+
+        <Group align="flex-end" gap="xs">
+          <TextInput
+            label="Log File"
+            placeholder="C:\Games\MyGame\combat.log"
+            value={path}
+            onChange={(e) => setPath(e.target.value)}
+            style={{ flex: 1 }}
+          />
+          <Button variant="light" onClick={handleBrowse}>Browse</Button>
+        </Group>
+
+        <TextInput
+          label="Pattern"
+          description={'Regular expression matched against each new line, e.g. You are stunned for (?<secs>\\d+) seconds'}
+          placeholder="Boss engaged"
+          value={pattern}
+          onChange={(e) => setPattern(e.target.value)}
+          styles={{ input: { fontFamily: 'monospace' } }}
+        />
+
+        <Group grow align="flex-start">
+          <Select
+            label="Action"
+            value={action}
+            onChange={(value) => setAction((value as LogWatchAction) || 'start')}
+            data={[
+              { value: 'start', label: 'Start timer' },
+              { value: 'reset', label: 'Reset timer' },
+              { value: 'pause', label: 'Pause timer' },
+              { value: 'toggle', label: 'Pause / resume timer' }
+            ]}
+          />
+          <Select
+            label="Timer"
+            placeholder="Select a timer"
+            value={timerId}
+            onChange={setTimerId}
+            data={timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))}
+            searchable
+          />
+        </Group>
+
+        {action === 'start' && (
+          <TextInput
+            label="Duration Capture Group (optional)"
+            description="Name or number of the group holding the duration, like 90, 1:30 or 2m. Empty = timer's own duration"
+            placeholder="secs"
+            value={durationGroup}
+            onChange={(e) => setDurationGroup(e.target.value)}
+          />
+        )}
+
+        <Switch
+          label="Enabled"
+          checked={enabled}
+          onChange={(e) => setEnabled(e.currentTarget.checked)}
+        />
+
+        {error && <Text size="xs" c="red">{error}</Text>}
+
+        <Group justify="flex-end" mt="md">
+          <Button variant="subtle" onClick={onClose}>Cancel</Button>
+          <Button onClick={handleSave}>
+            {watcher ? 'Update' : 'Create'}
+          </Button>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
@@ -1,16 +1,34 @@
-import React, { useEffect, useMemo, useRef } from 'react';
+import React, { useCallback, useEffect, useMemo, useRef, useState } from 'react';
 import * as Icons from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+import { getCurrentWindow } from '@tauri-apps/api/window';
 import { IconConfig } from '../types/config';
 import { useTimer } from '../hooks/useTimer';
 import { useNotifications } from '../hooks/useNotifications';
 import { timerLogger } from '../utils/logger';
+import { ChargeSnapshot, ChessClockSnapshot, getTimerStates, PomodoroPhase, PomodoroSnapshot, RandomRoll, reportTimerEvent, TimerCorrection, TimerEventDetails, TimerEventKind } from '../utils/timerEvents';
+
+const OVERTIME_COLOR = '#F44336';
+const BREAK_COLOR = '#FF9800';
+
+const POMODORO_PHASE_LABELS: Record<PomodoroPhase, string> = {
+  focus: 'Focus',
+  shortBreak: 'Break',
+  longBreak: 'Long break'
+};
 
 interface OverlayIconProps {
   config: IconConfig;
   onConfigClick?: () => void;
   onTimerComplete?: () => void;
-  onStartTimerReady?: (startFn: () => void) => void;
+  onStartTimerReady?: (startFn: (durationSeconds?: number) => void) => void;
   onResetTimerReady?: (resetFn: () => void) => void;
+  // `pauseOnlyFn` não retoma timers já pausados (ações de grupo e gatilhos)
+  onPauseTimerReady?: (pauseFn: () => void, pauseOnlyFn: () => void) => void;
+  // Instância paralela criada pela política `stack` (ausente = instância principal)
+  instance?: number;
+  onStackInstance?: () => void;
+  onInstanceDone?: () => void;
   compactMode?: boolean;
   strokeColor?: string;
   strokeWidth?: number;
@@ -24,6 +42,10 @@ export function OverlayIcon({
   onTimerComplete, 
   onStartTimerReady,
   onResetTimerReady,
+  onPauseTimerReady,
+  instance,
+  onStackInstance,
+  onInstanceDone,
   compactMode = false,
   strokeColor: _strokeColor,
   strokeWidth: _strokeWidth = 0,
@@ -32,6 +54,35 @@ export function OverlayIcon({
 }: OverlayIconProps) {
   const { sendNotification: sendNotificationWithSound } = useNotifications();
 
+  const report = useCallback(
+    (event: TimerEventKind, details: TimerEventDetails = {}) => {
+      reportTimerEvent(config.id, event, instance ? { ...details, instance } : details);
+    },
+    [config.id, instance]
+  );
+  // Instâncias paralelas terminam direto, sem overtime
+  const overtimeEnabled = Boolean(config.overtime) && !instance;
+
+  // Durações aleatórias são sorteadas no Rust (semente reproduzível); a próxima fica
+  // pré-sorteada para a repetição começar sem esperar
+  const isRandom = Boolean(config.randomDuration) && (config.timerType ?? 'countdown') === 'countdown';
+  const currentRollRef = useRef<RandomRoll | null>(null);
+  const pendingRollRef = useRef<RandomRoll | null>(null);
+
+  const prefetchRoll = useCallback(() => {
+    pendingRollRef.current = null;
+    invoke<RandomRoll>('roll_random_duration', { timerId: config.id })
+      .then((roll) => {
+        pendingRollRef.current = roll;
+      })
+      .catch((error) => timerLogger.error(`[RANDOM] Failed to roll next duration for ${config.id}:`, error));
+  }, [config.id]);
+
+  // Início do overtime (ms) quando a contagem chegou a zero e ainda não foi reconhecida
+  const [overtimeSince, setOvertimeSince] = useState<number | null>(null);
+  const overtimeSinceRef = useRef<number | null>(null);
+  overtimeSinceRef.current = overtimeSince;
+
   const timerCallbacks = useMemo(
     () => ({
       onTimerComplete: ({ repeatCount }: { repeatCount: number }) => {
@@ -56,6 +107,24 @@ export function OverlayIcon({
 
         timerLogger.info(`[TIMER] Completion notified for icon ${config.id} (repeat ${repeatCount})`);
         onTimerComplete?.();
+      },
+      onFinalComplete: () => {
+        report('finished');
+        if (overtimeEnabled) {
+          setOvertimeSince(Date.now());
+        }
+        onInstanceDone?.();
+      },
+      onPhaseStart: ({ phase, duration }: { phase: number; duration: number }) => {
+        report('phase', { phase, remaining: duration });
+      },
+      nextRepeat: () => {
+        if (!isRandom) return undefined;
+        const current = currentRollRef.current;
+        const pending = pendingRollRef.current;
+        currentRollRef.current = pending;
+        prefetchRoll();
+        return { duration: pending?.durationSeconds, interval: current?.gapSeconds };
       }
     }),
     [
@@ -65,12 +134,29 @@ export function OverlayIcon({
       config.name,
       config.notificationType,
       config.soundPath,
+      overtimeEnabled,
+      isRandom,
+      prefetchRoll,
+      onInstanceDone,
       onTimerComplete,
+      report,
       sendNotificationWithSound
     ]
   );
 
-  const timer = useTimer(config.timerDuration, config.repeat, config.timerType || 'countdown', timerCallbacks);
+  const isAlarm = config.timerType === 'alarm';
+  const isReset = config.timerType === 'reset';
+  const isCharges = config.timerType === 'charges';
+  const isPomodoro = config.timerType === 'pomodoro';
+  const isChessClock = config.timerType === 'chessClock';
+  const timer = useTimer(
+    config.timerDuration,
+    config.repeat,
+    config.timerType === 'stopwatch' ? 'stopwatch' : 'countdown',
+    timerCallbacks
+  );
+  const timerStateRef = useRef(timer);
+  timerStateRef.current = timer;
   const prevIntervalStateRef = useRef<boolean>(timer.isInInterval || false);
   const intervalNotifiedRepeatsRef = useRef<Set<number>>(new Set());
 
@@ -128,22 +214,556 @@ export function OverlayIcon({
     timer.running
   ]);
 
+  // Alarmes são agendados no Rust; o overlay só mostra o estado e notifica
+  const [alarmRinging, setAlarmRinging] = useState(false);
+  const alarmRingingRef = useRef(false);
+  alarmRingingRef.current = alarmRinging;
+
+  useEffect(() => {
+    if (!isAlarm) return;
+
+    const appWindow = getCurrentWindow();
+    const unlistenTriggered = appWindow.listen<{ timerId: string; name: string }>('alarm-triggered', (event) => {
+      if (event.payload.timerId !== config.id) return;
+      setAlarmRinging(true);
+      timerLogger.info(`[ALARM] Alarm ringing for icon ${config.id}`);
+
+      if (config.notificationType === 'none') return;
+      sendNotificationWithSound({
+        title: 'Alarm',
+        body: config.completionNotificationText || `${config.name || 'Alarm'} (${config.alarm?.time ?? ''})`,
+        playSound: config.notificationType === 'sound' || config.notificationType === 'both',
+        sendNotification: config.notificationType === 'notification' || config.notificationType === 'both',
+        soundPath: config.soundPath
+      });
+    });
+    const unlistenStopped = appWindow.listen<{ timerId: string }>('alarm-stopped', (event) => {
+      if (event.payload.timerId === config.id) {
+        setAlarmRinging(false);
+      }
+    });
+
+    return () => {
+      unlistenTriggered.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten alarm-triggered event:', err);
+      });
+      unlistenStopped.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten alarm-stopped event:', err);
+      });
+    };
+  }, [
+    isAlarm,
+    config.id,
+    config.name,
+    config.alarm?.time,
+    config.completionNotificationText,
+    config.notificationType,
+    config.soundPath,
+    sendNotificationWithSound
+  ]);
+
+  // Última volta do cronômetro, mostrada abaixo do tempo
+  const [lastLap, setLastLap] = useState<{ index: number; deltaMs: number } | null>(null);
+
+  useEffect(() => {
+    if (config.timerType !== 'stopwatch') return;
+
+    const unlisten = getCurrentWindow().listen<{ timerId: string; lap: { index: number; deltaMs: number } }>('timer-lap', (event) => {
+      if (event.payload.timerId === config.id) {
+        setLastLap(event.payload.lap);
+      }
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten timer-lap event:', err);
+      });
+    };
+  }, [config.id, config.timerType]);
+
+  useEffect(() => {
+    const unlisten = getCurrentWindow().listen<{ timerId: string; instance: number; deltaSeconds: number }>('adjust-timer', (event) => {
+      if (event.payload.timerId === config.id && event.payload.instance === (instance ?? 0)) {
+        timer.adjust(event.payload.deltaSeconds);
+      }
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten adjust-timer event:', err);
+      });
+    };
+  }, [config.id, instance, timer.adjust]);
+
+  // Suspensão ou mudança no relógio: o Rust decide quanto mover o início conforme a política do timer
+  useEffect(() => {
+    const unlisten = getCurrentWindow().listen<TimerCorrection>('timer-corrected', (event) => {
+      const { timerId, shiftMs, reason } = event.payload;
+      if (timerId !== config.id || event.payload.instance !== (instance ?? 0) || shiftMs === 0) return;
+      timerLogger.info(`[TIMER] ${config.id} corrected by ${shiftMs}ms (${reason})`);
+      timer.shiftStart(shiftMs);
+      setOvertimeSince(prev => (prev === null ? null : prev + shiftMs));
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten timer-corrected event:', err);
+      });
+    };
+  }, [config.id, instance, timer.shiftStart]);
+
+  // Contagens até reset: o Rust calcula o próximo reset (recorrência + fuso) e o overlay só conta até ele
+  const [resetTarget, setResetTarget] = useState<{ nextResetAt: number; duration: number } | null>(null);
+  const [now, setNow] = useState(() => Date.now());
+  const handledResetRef = useRef<number | null>(null);
+
+  const fetchResetTarget = useCallback(async () => {
+    try {
+      const states = await getTimerStates();
+      const state = states.find((s) => s.timerId === config.id);
+      if (state?.nextResetAt) {
+        setResetTarget({ nextResetAt: state.nextResetAt, duration: state.durationSeconds ?? 0 });
+      } else {
+        setResetTarget(null);
+      }
+    } catch (error) {
+      timerLogger.error(`[RESET] Failed to load next reset for ${config.id}:`, error);
+    }
+  }, [config.id]);
+
+  useEffect(() => {
+    if (!isReset) return;
+    fetchResetTarget();
+    const tick = window.setInterval(() => setNow(Date.now()), 1000);
+    return () => clearInterval(tick);
+  }, [isReset, fetchResetTarget, config.reset?.time, config.reset?.timeZone, config.reset?.weekdays?.join(','), config.reset?.monthDay]);
+
+  useEffect(() => {
+    if (!isReset || !resetTarget || now < resetTarget.nextResetAt) return;
+    if (handledResetRef.current === resetTarget.nextResetAt) return;
+    handledResetRef.current = resetTarget.nextResetAt;
+
+    timerLogger.info(`[RESET] Reset reached for icon ${config.id}, rolling over`);
+    report('finished');
+    if (config.notificationType !== 'none') {
+      sendNotificationWithSound({
+        title: 'Reset',
+        body: config.completionNotificationText || `${config.name || 'Reset'} is up!`,
+        playSound: config.notificationType === 'sound' || config.notificationType === 'both',
+        sendNotification: config.notificationType === 'notification' || config.notificationType === 'both',
+        soundPath: config.soundPath
+      });
+    }
+    fetchResetTarget();
+  }, [
+    isReset,
+    now,
+    resetTarget,
+    fetchResetTarget,
+    report,
+    config.id,
+    config.name,
+    config.completionNotificationText,
+    config.notificationType,
+    config.soundPath,
+    sendNotificationWithSound
+  ]);
+
+  // Cargas são controladas no Rust; o overlay mostra as disponíveis e a recarga em andamento
+  const [charges, setCharges] = useState<ChargeSnapshot | null>(null);
+
+  useEffect(() => {
+    if (!isCharges) {
+      setCharges(null);
+      return;
+    }
+
+    getTimerStates()
+      .then((states) => setCharges(states.find((s) => s.timerId === config.id)?.charges ?? null))
+      .catch((error) => timerLogger.error(`[CHARGES] Failed to load charges for ${config.id}:`, error));
+
+    const appWindow = getCurrentWindow();
+    const unlistenChanged = appWindow.listen<ChargeSnapshot>('charges-changed', (event) => {
+      if (event.payload.timerId === config.id) {
+        setCharges(event.payload);
+      }
+    });
+    const unlistenReady = appWindow.listen<{ timerId: string; available: number; max: number; allReady: boolean }>('charge-ready', (event) => {
+      const { timerId, available, max, allReady } = event.payload;
+      if (timerId !== config.id || config.notificationType === 'none') return;
+
+      const label = config.name || 'Ability';
+      sendNotificationWithSound({
+        title: allReady ? 'All Charges Ready' : 'Charge Ready',
+        body: allReady ? `${label}: all ${max} charges ready` : `${label}: ${available}/${max} charges ready`,
+        playSound: config.notificationType === 'sound' || config.notificationType === 'both',
+        sendNotification: config.notificationType === 'notification' || config.notificationType === 'both',
+        soundPath: config.soundPath
+      });
+    });
+
+    return () => {
+      unlistenChanged.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten charges-changed event:', err);
+      });
+      unlistenReady.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten charge-ready event:', err);
+      });
+    };
+  }, [
+    isCharges,
+    config.id,
+    config.name,
+    config.notificationType,
+    config.soundPath,
+    config.charges?.max,
+    config.timerDuration,
+    sendNotificationWithSound
+  ]);
+
+  useEffect(() => {
+    if (!charges?.nextChargeAt) return;
+    const tick = window.setInterval(() => setNow(Date.now()), 1000);
+    return () => clearInterval(tick);
+  }, [charges?.nextChargeAt]);
+
+  const chargeRemainingMs = charges?.nextChargeAt ? Math.max(0, charges.nextChargeAt - now) : 0;
+
+  // O ciclo Pomodoro roda no Rust; o overlay conta o restante da fase a partir do último estado
+  const [pomodoro, setPomodoro] = useState<{ state: PomodoroSnapshot; receivedAt: number } | null>(null);
+
+  useEffect(() => {
+    if (!isPomodoro) {
+      setPomodoro(null);
+      return;
+    }
+
+    getTimerStates()
+      .then((states) => {
+        const state = states.find((s) => s.timerId === config.id)?.pomodoro;
+        setPomodoro(state ? { state, receivedAt: Date.now() } : null);
+      })
+      .catch((error) => timerLogger.error(`[POMODORO] Failed to load cycle for ${config.id}:`, error));
+
+    const appWindow = getCurrentWindow();
+    const unlistenChanged = appWindow.listen<PomodoroSnapshot>('pomodoro-changed', (event) => {
+      if (event.payload.timerId === config.id) {
+        setPomodoro({ state: event.payload, receivedAt: Date.now() });
+      }
+    });
+    const unlistenFinished = appWindow.listen<{ timerId: string; finished: PomodoroPhase; next: PomodoroPhase; completedToday: number; autoAdvance: boolean }>('pomodoro-phase-finished', (event) => {
+      const { timerId, finished, next, completedToday, autoAdvance } = event.payload;
+      if (timerId !== config.id || config.notificationType === 'none') return;
+
+      const nextLabel = POMODORO_PHASE_LABELS[next].toLowerCase();
+      sendNotificationWithSound({
+        title: finished === 'focus' ? 'Focus Complete' : 'Break Over',
+        body: finished === 'focus'
+          ? `${completedToday} done today. Time for a ${nextLabel}${autoAdvance ? '' : ' - press the shortcut to start it'}.`
+          : `Back to focus${autoAdvance ? '' : ' - press the shortcut to start'}.`,
+        playSound: config.notificationType === 'sound' || config.notificationType === 'both',
+        sendNotification: config.notificationType === 'notification' || config.notificationType === 'both',
+        soundPath: config.soundPath
+      });
+    });
+
+    return () => {
+      unlistenChanged.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten pomodoro-changed event:', err);
+      });
+      unlistenFinished.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten pomodoro-phase-finished event:', err);
+      });
+    };
+  }, [
+    isPomodoro,
+    config.id,
+    config.notificationType,
+    config.soundPath,
+    config.timerDuration,
+    config.pomodoro?.shortBreak,
+    config.pomodoro?.longBreak,
+    config.pomodoro?.longBreakEvery,
+    config.pomodoro?.autoAdvance,
+    sendNotificationWithSound
+  ]);
+
+  const pomodoroRunning = pomodoro?.state.status === 'running';
+  useEffect(() => {
+    if (!pomodoroRunning) return;
+    const tick = window.setInterval(() => setNow(Date.now()), 1000);
+    return () => clearInterval(tick);
+  }, [pomodoroRunning]);
+
+  const pomodoroRemainingMs = pomodoro
+    ? Math.max(0, pomodoro.state.remainingMs - (pomodoroRunning ? now - pomodoro.receivedAt : 0))
+    : 0;
+
+  const togglePomodoro = useCallback(() => {
+    invoke('toggle_pomodoro', { timerId: config.id }).catch((error) => {
+      timerLogger.error(`[POMODORO] Failed to toggle ${config.id}:`, error);
+    });
+  }, [config.id]);
+
+  // O relógio de xadrez roda no Rust; o overlay mostra o tempo do jogador da vez
+  const [chessClock, setChessClock] = useState<{ state: ChessClockSnapshot; receivedAt: number } | null>(null);
+
+  useEffect(() => {
+    if (!isChessClock) {
+      setChessClock(null);
+      return;
+    }
+
+    getTimerStates()
+      .then((states) => {
+        const state = states.find((s) => s.timerId === config.id)?.chessClock;
+        setChessClock(state ? { state, receivedAt: Date.now() } : null);
+      })
+      .catch((error) => timerLogger.error(`[CHESS_CLOCK] Failed to load clock for ${config.id}:`, error));
+
+    const appWindow = getCurrentWindow();
+    const unlistenChanged = appWindow.listen<ChessClockSnapshot>('chess-clock-changed', (event) => {
+      if (event.payload.timerId === config.id) {
+        setChessClock({ state: event.payload, receivedAt: Date.now() });
+      }
+    });
+    const unlistenFlagged = appWindow.listen<{ timerId: string; player: number; name: string; finished: boolean }>('chess-clock-flagged', (event) => {
+      const { timerId, name, finished } = event.payload;
+      if (timerId !== config.id || config.notificationType === 'none') return;
+
+      sendNotificationWithSound({
+        title: 'Out of Time',
+        body: finished ? `${name} ran out of time. Game over.` : `${name} ran out of time.`,
+        playSound: config.notificationType === 'sound' || config.notificationType === 'both',
+        sendNotification: config.notificationType === 'notification' || config.notificationType === 'both',
+        soundPath: config.soundPath
+      });
+    });
+
+    return () => {
+      unlistenChanged.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten chess-clock-changed event:', err);
+      });
+      unlistenFlagged.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten chess-clock-flagged event:', err);
+      });
+    };
+  }, [
+    isChessClock,
+    config.id,
+    config.notificationType,
+    config.soundPath,
+    config.timerDuration,
+    config.chessClock?.players.join('|'),
+    config.chessClock?.increment,
+    config.chessClock?.delay,
+    sendNotificationWithSound
+  ]);
+
+  const chessClockRunning = chessClock?.state.status === 'running';
+  useEffect(() => {
+    if (!chessClockRunning) return;
+    const tick = window.setInterval(() => setNow(Date.now()), 250);
+    return () => clearInterval(tick);
+  }, [chessClockRunning]);
+
+  const activePlayer = chessClock ? chessClock.state.players[chessClock.state.activePlayer] : null;
+  // O delay da jogada é consumido antes do banco
+  const chessClockRemainingMs = chessClock && activePlayer
+    ? (chessClockRunning
+      ? Math.max(0, activePlayer.remainingMs - Math.max(0, now - chessClock.receivedAt - chessClock.state.delayRemainingMs))
+      : activePlayer.remainingMs)
+    : 0;
+
+  const toggleChessClock = useCallback(() => {
+    invoke('toggle_chess_clock', { timerId: config.id }).catch((error) => {
+      timerLogger.error(`[CHESS_CLOCK] Failed to toggle ${config.id}:`, error);
+    });
+  }, [config.id]);
+
+  const acknowledgeOvertime = useCallback(() => {
+    if (overtimeSinceRef.current === null) return false;
+    timerLogger.info(`[TIMER] Overtime acknowledged for icon ${config.id}`);
+    report('acknowledged');
+    setOvertimeSince(null);
+    return true;
+  }, [config.id, report]);
+
+  useEffect(() => {
+    if (overtimeSince === null) return;
+    const tick = window.setInterval(() => setNow(Date.now()), 1000);
+    return () => clearInterval(tick);
+  }, [overtimeSince]);
+
+  useEffect(() => {
+    if (!overtimeEnabled) {
+      setOvertimeSince(null);
+      return;
+    }
+
+    const unlisten = getCurrentWindow().listen<string>('acknowledge-timer', (event) => {
+      if (event.payload === config.id) {
+        acknowledgeOvertime();
+      }
+    });
+
+    return () => {
+      unlisten.then((unlistenFn) => unlistenFn()).catch((err) => {
+        timerLogger.error('Failed to unlisten acknowledge-timer event:', err);
+      });
+    };
+  }, [config.id, overtimeEnabled, acknowledgeOvertime]);
+
+  const overtimeSeconds = overtimeSince !== null ? Math.max(0, Math.floor((now - overtimeSince) / 1000)) : 0;
+
+  const resetRemaining = resetTarget ? Math.max(0, Math.ceil((resetTarget.nextResetAt - now) / 1000)) : 0;
+
+  const togglePause = useCallback(() => {
+    const current = timerStateRef.current;
+    if (current.running) {
+      report('paused', { remaining: current.remaining });
+    } else if (current.startTime && !current.isInInterval) {
+      report('resumed', { remaining: current.remaining });
+    }
+    timer.togglePause();
+  }, [report, timer.togglePause]);
+
+  const pauseOnly = useCallback(() => {
+    if (isPomodoro) {
+      if (pomodoroRunning) togglePomodoro();
+      return;
+    }
+    if (isChessClock) {
+      if (chessClockRunning) toggleChessClock();
+      return;
+    }
+    if (timerStateRef.current.running) {
+      togglePause();
+    }
+  }, [isPomodoro, isChessClock, pomodoroRunning, chessClockRunning, togglePomodoro, toggleChessClock, togglePause]);
+
+  // Começa uma execução; timers aleatórios sorteiam a duração antes
+  const startRun = useCallback((durationSeconds?: number) => {
+    if (!isRandom || durationSeconds !== undefined) {
+      timer.start(durationSeconds);
+      report('started', durationSeconds ? { remaining: durationSeconds } : {});
+      return;
+    }
+    invoke<RandomRoll>('roll_random_duration', { timerId: config.id })
+      .then((roll) => {
+        currentRollRef.current = roll;
+        timer.start(roll.durationSeconds);
+        report('started', { remaining: roll.durationSeconds, seed: roll.seed, draw: roll.draw });
+        prefetchRoll();
+      })
+      .catch((error) => {
+        timerLogger.error(`[RANDOM] Failed to roll duration for ${config.id}, using the configured one:`, error);
+        timer.start();
+        report('started');
+      });
+  }, [config.id, isRandom, prefetchRoll, report, timer.start]);
+
   // Expose start function to parent - usar useCallback para garantir referência estável
   useEffect(() => {
     if (onStartTimerReady) {
       timerLogger.debug(`Exposing start function for icon: ${config.id}`);
-      const startWrapper = () => {
-        timer.start();
+      const startWrapper = (durationSeconds?: number) => {
+        if (isAlarm) {
+          // O atalho de um alarme dispensa o alarme que está tocando
+          if (alarmRingingRef.current) {
+            invoke('dismiss_alarm', { timerId: config.id }).catch((error) => {
+              timerLogger.error(`[ALARM] Failed to dismiss alarm ${config.id}:`, error);
+            });
+          }
+          return;
+        }
+        // Contagens até reset rodam sozinhas
+        if (isReset) return;
+        if (isPomodoro) {
+          togglePomodoro();
+          return;
+        }
+        if (isChessClock) {
+          invoke('pass_turn', { timerId: config.id }).catch((error) => {
+            timerLogger.error(`[CHESS_CLOCK] Failed to pass turn on ${config.id}:`, error);
+          });
+          return;
+        }
+        if (isCharges) {
+          invoke('use_charge', { timerId: config.id }).catch((error) => {
+            timerLogger.warn(`[CHARGES] Could not use a charge of ${config.id}:`, error);
+          });
+          return;
+        }
+        // O primeiro toque durante o overtime só reconhece
+        if (acknowledgeOvertime()) return;
+        const current = timerStateRef.current;
+        const active = current.running || current.isInInterval || Boolean(current.startTime);
+        if (active && config.triggerPolicy && config.triggerPolicy !== 'restart') {
+          timerLogger.info(`[TIMER] Trigger policy ${config.triggerPolicy} for running icon ${config.id}`);
+          switch (config.triggerPolicy) {
+            case 'ignore':
+              return;
+            case 'pause':
+              togglePause();
+              return;
+            case 'reset':
+              report('cancelled');
+              timer.reset();
+              return;
+            case 'stack':
+              onStackInstance?.();
+              return;
+          }
+        }
+        setLastLap(null);
+        startRun(durationSeconds);
       };
       onStartTimerReady(startWrapper);
     }
     if (onResetTimerReady) {
       const resetWrapper = () => {
+        if (isChessClock) {
+          invoke('reset_chess_clock', { timerId: config.id }).catch((error) => {
+            timerLogger.error(`[CHESS_CLOCK] Failed to reset ${config.id}:`, error);
+          });
+          return;
+        }
+        if (isPomodoro) {
+          invoke('restart_pomodoro_cycle', { timerId: config.id }).catch((error) => {
+            timerLogger.error(`[POMODORO] Failed to restart cycle of ${config.id}:`, error);
+          });
+          return;
+        }
+        if (isCharges) {
+          invoke('reset_charges', { timerId: config.id }).catch((error) => {
+            timerLogger.error(`[CHARGES] Failed to refill charges of ${config.id}:`, error);
+          });
+          return;
+        }
+        const current = timerStateRef.current;
+        // Reset durante o overtime conta como reconhecimento
+        const acknowledged = acknowledgeOvertime();
+        if (!acknowledged && (current.running || current.isInInterval || current.startTime)) {
+          report('cancelled');
+        }
+        setLastLap(null);
         timer.reset();
       };
       onResetTimerReady(resetWrapper);
     }
-  }, [config.id, config.keybind, config.timerDuration, onStartTimerReady, onResetTimerReady, timer.start, timer.reset]);
+    if (onPauseTimerReady) {
+      onPauseTimerReady(isPomodoro ? togglePomodoro : isChessClock ? toggleChessClock : togglePause, pauseOnly);
+    }
+  }, [config.id, config.keybind, config.timerDuration, config.triggerPolicy, isAlarm, isReset, isCharges, isPomodoro, isChessClock, acknowledgeOvertime, report, startRun, togglePause, pauseOnly, togglePomodoro, toggleChessClock, onStackInstance, onStartTimerReady, onResetTimerReady, onPauseTimerReady, timer.start, timer.reset]);
+
+  // Instâncias paralelas começam a contar assim que aparecem
+  const autoStartedRef = useRef(false);
+  useEffect(() => {
+    if (!instance || autoStartedRef.current) return;
+    autoStartedRef.current = true;
+    startRun();
+  }, [instance, startRun]);
 
   const formatTime = (seconds: number) => {
     const mins = Math.floor(seconds / 60);
@@ -151,7 +771,61 @@ export function OverlayIcon({
     return `${mins}:${secs.toString().padStart(2, '0')}`;
   };
 
-  const progress = timer.duration > 0 ? (timer.remaining / timer.duration) : 0;
+  // Resets podem estar a dias de distância
+  const formatLongTime = (seconds: number) => {
+    const days = Math.floor(seconds / 86400);
+    const hours = Math.floor((seconds % 86400) / 3600);
+    if (days > 0) return `${days}d ${hours}h`;
+    if (hours > 0) {
+      const mins = Math.floor((seconds % 3600) / 60);
+      return `${hours}:${mins.toString().padStart(2, '0')}:${(seconds % 60).toString().padStart(2, '0')}`;
+    }
+    return formatTime(seconds);
+  };
+
+  const displayText = isAlarm
+    ? (alarmRinging ? 'Ring!' : config.alarm?.time || '--:--')
+    : isReset
+      ? (resetTarget ? formatLongTime(resetRemaining) : '--:--')
+      : isCharges
+        ? (charges ? `${charges.available}/${charges.max}` : '--')
+        : isPomodoro
+        ? (pomodoro ? formatTime(Math.ceil(pomodoroRemainingMs / 1000)) : '--:--')
+        : isChessClock
+        ? (chessClock?.state.finished ? 'Flag' : chessClock ? formatTime(Math.ceil(chessClockRemainingMs / 1000)) : '--:--')
+        : overtimeSince !== null
+        ? `-${formatTime(overtimeSeconds)}`
+        : isRandom && !timer.startTime
+        ? '?:??'
+        : formatTime(timer.remaining);
+
+  const lapText = lastLap ? `L${lastLap.index} +${formatTime(Math.round(lastLap.deltaMs / 1000))}` : null;
+  const chargeText = isCharges && charges?.nextChargeAt ? formatTime(Math.ceil(chargeRemainingMs / 1000)) : null;
+  const pomodoroText = pomodoro
+    ? `${pomodoro.state.phase === 'focus'
+      ? `Focus ${pomodoro.state.cycle}/${pomodoro.state.longBreakEvery}`
+      : POMODORO_PHASE_LABELS[pomodoro.state.phase]} · ${pomodoro.state.completedToday}`
+    : null;
+  const chessClockText = activePlayer ? `${activePlayer.name}${activePlayer.flagged ? ' (out)' : ''}` : null;
+  const subText = lapText ?? chargeText ?? pomodoroText ?? chessClockText;
+
+  const progress = isAlarm
+    ? 1
+    : isReset
+      ? (resetTarget && resetTarget.duration > 0 ? Math.min(1, resetRemaining / resetTarget.duration) : 0)
+      : isCharges
+        ? (charges?.nextChargeAt && charges.rechargeSeconds > 0
+          ? Math.min(1, 1 - chargeRemainingMs / (charges.rechargeSeconds * 1000))
+          : 1)
+        : isChessClock
+        ? (chessClock && chessClock.state.bankSeconds > 0
+          ? Math.min(1, chessClockRemainingMs / (chessClock.state.bankSeconds * 1000))
+          : 0)
+        : isPomodoro
+        ? (pomodoro && pomodoro.state.phaseSeconds > 0
+          ? Math.min(1, pomodoroRemainingMs / (pomodoro.state.phaseSeconds * 1000))
+          : 0)
+        : timer.duration > 0 ? (timer.remaining / timer.duration) : 0;
   const circumference = 2 * Math.PI * 36;
   const strokeDashoffset = circumference - (progress * circumference);
 
@@ -160,6 +834,26 @@ export function OverlayIcon({
     : Icons.Timer;
 
   const getDisplayColor = () => {
+    if (isAlarm) {
+      return alarmRinging ? timerRunningColor : timerColor;
+    }
+    if (isReset) {
+      return timerRunningColor;
+    }
+    if (isCharges) {
+      return charges && charges.available > 0 ? timerRunningColor : timerColor;
+    }
+    if (isChessClock) {
+      if (chessClock?.state.finished) return OVERTIME_COLOR;
+      return chessClockRunning ? timerRunningColor : timerColor;
+    }
+    if (isPomodoro) {
+      if (!pomodoroRunning) return timerColor;
+      return pomodoro?.state.phase === 'focus' ? timerRunningColor : BREAK_COLOR;
+    }
+    if (overtimeSince !== null) {
+      return OVERTIME_COLOR;
+    }
     if (timer.isInInterval && config.repeat?.intervalColor) {
       return config.repeat.intervalColor;
     }
@@ -201,8 +895,19 @@ export function OverlayIcon({
             textShadow: '0 1px 2px rgba(0, 0, 0, 0.8), 0 0 4px rgba(0, 0, 0, 0.5)'
           }}
         >
-          {formatTime(timer.remaining)}
+          {displayText}
         </span>
+        {subText && (
+          <span
+            style={{
+              fontSize: '10px',
+              color: displayColor,
+              textShadow: '0 1px 2px rgba(0, 0, 0, 0.8), 0 0 4px rgba(0, 0, 0, 0.5)'
+            }}
+          >
+            {subText}
+          </span>
+        )}
       </div>
     );
   }
@@ -272,8 +977,13 @@ export function OverlayIcon({
             color: displayColor
           }}
         >
-          {formatTime(timer.remaining)}
+          {displayText}
         </span>
+        {subText && (
+          <span style={{ fontSize: '8px', color: displayColor, marginTop: '-4px' }}>
+            {subText}
+          </span>
+        )}
       </div>
     </div>
   );
@@ -0,0 +1,182 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Text, Stack, TextInput, Select, Switch, SegmentedControl } from '@mantine/core';
+import { IconConfig, ProcessRule, TimerGroup, TimerProfile } from '../types/config';
+
+const TARGET_ERRORS: Record<ProcessRule['target']['type'], string> = {
+  timer: 'Choose the timer to control',
+  group: 'Choose the group to control',
+  profile: 'Choose the profile to switch to'
+};
+
+const TARGET_PLACEHOLDERS: Record<ProcessRule['target']['type'], string> = {
+  timer: 'Select a timer',
+  group: 'Select a group',
+  profile: 'Select a profile'
+};
+
+function targetIdOf(target: ProcessRule['target']): string {
+  if (target.type === 'timer') return target.timerId;
+  if (target.type === 'group') return target.groupId;
+  return target.profileId;
+}
+
+interface ProcessRuleModalProps {
+  opened: boolean;
+  onClose: () => void;
+  rule: ProcessRule | null;
+  timers: IconConfig[];
+  groups: TimerGroup[];
+  profiles: TimerProfile[];
+  onSave: (rule: ProcessRule) => void;
+}
+
+export function ProcessRuleModal({ opened, onClose, rule, timers, groups, profiles, onSave }: ProcessRuleModalProps) {
+  const [name, setName] = useState<string>('');
+  const [pattern, setPattern] = useState<string>('');
+  const [event, setEvent] = useState<ProcessRule['event']>('start');
+  const [action, setAction] = useState<ProcessRule['action']>('start');
+  const [targetType, setTargetType] = useState<ProcessRule['target']['type']>('timer');
+  const [targetId, setTargetId] = useState<string | null>(null);
+  const [enabled, setEnabled] = useState<boolean>(true);
+  const [error, setError] = useState<string>('');
+
+  useEffect(() => {
+    setName(rule?.name || '');
+    setPattern(rule?.pattern || '');
+    setEvent(rule?.event || 'start');
+    setAction(rule?.action || 'start');
+    setTargetType(rule?.target.type || 'timer');
+    setTargetId(rule ? targetIdOf(rule.target) : null);
+    setEnabled(rule?.enabled !== false);
+    setError('');
+  }, [rule, opened]);
+
+  const handleSave = () => {
+    if (!pattern.trim()) {
+      setError('Enter the program name or path');
+      return;
+    }
+    if (!targetId) {
+      setError(TARGET_ERRORS[targetType]);
+      return;
+    }
+
+    onSave({
+      id: rule?.id || `process-${Date.now()}`,
+      name: name.trim() || undefined,
+      pattern: pattern.trim(),
+      event,
+      action,
+      target: targetType === 'timer'
+        ? { type: 'timer', timerId: targetId }
+        : targetType === 'group'
+          ? { type: 'group', groupId: targetId }
+          : { type: 'profile', profileId: targetId },
+      enabled
+    });
+    onClose();
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={rule ? 'Edit Process Rule' : 'Create Process Rule'}
+      size="md"
+    >
+      <Stack gap="md">
+        <TextInput
+          label="Name"
+          placeholder="e.g., Raid night"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+
+        <TextInput
+          label="Program"
+          description="Executable name like Wow.exe, or a full path. Use * and ? as wildcards"
+          placeholder="Wow.exe"
+          value={pattern}
+          onChange={(e) => setPattern(e.target.value)}
+        />
+
+        <Group grow align="flex-start">
+          <Select
+            label="When the program"
+            value={event}
+            onChange={(value) => setEvent((value as ProcessRule['event']) || 'start')}
+            data={[
+              { value: 'start', label: 'Starts' },
+              { value: 'exit', label: 'Exits' }
+            ]}
+          />
+          <Select
+            label="Action"
+            value={action}
+            onChange={(value) => {
+              const next = (value as ProcessRule['action']) || 'start';
+              // Trocar de perfil só faz sentido com um perfil como alvo
+              if ((next === 'profile') !== (targetType === 'profile')) {
+                setTargetType(next === 'profile' ? 'profile' : 'timer');
+                setTargetId(null);
+              }
+              setAction(next);
+            }}
+            data={[
+              { value: 'start', label: 'Start / resume' },
+              { value: 'stop', label: 'Stop (pause)' },
+              { value: 'reset', label: 'Reset' },
+              { value: 'profile', label: 'Switch profile' }
+            ]}
+          />
+        </Group>
+
+        {action !== 'profile' && (
+          <div>
+            <Text size="sm" fw={500} mb="xs">Target</Text>
+            <SegmentedControl
+              value={targetType}
+              onChange={(value) => {
+                setTargetType(value as ProcessRule['target']['type']);
+                setTargetId(null);
+              }}
+              data={[
+                { value: 'timer', label: 'Timer' },
+                { value: 'group', label: 'Timer Group' }
+              ]}
+              fullWidth
+            />
+          </div>
+        )}
+
+        <Select
+          label={targetType === 'profile' ? 'Profile' : undefined}
+          placeholder={TARGET_PLACEHOLDERS[targetType]}
+          value={targetId}
+          onChange={setTargetId}
+          data={targetType === 'timer'
+            ? timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))
+            : targetType === 'group'
+              ? groups.map(group => ({ value: group.id, label: group.name }))
+              : profiles.map(profile => ({ value: profile.id, label: profile.name }))}
+          searchable
+        />
+
+        <Switch
+          label="Enabled"
+          checked={enabled}
+          onChange={(e) => setEnabled(e.currentTarget.checked)}
+        />
+
+        {error && <Text size="xs" c="red">{error}</Text>}
+
+        <Group justify="flex-end" mt="md">
+          <Button variant="subtle" onClick={onClose}>Cancel</Button>
+          <Button onClick={handleSave}>
+            {rule ? 'Update' : 'Create'}
+          </Button>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
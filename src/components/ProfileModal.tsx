@@ -0,0 +1,122 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Stack, TextInput, MultiSelect, Select } from '@mantine/core';
+import { AppConfig, IconConfig, TimerProfile } from '../types/config';
+
+interface ProfileModalProps {
+  opened: boolean;
+  onClose: () => void;
+  profile: TimerProfile | null;
+  timers: IconConfig[];
+  onSave: (profile: TimerProfile) => void;
+}
+
+export function ProfileModal({ opened, onClose, profile, timers, onSave }: ProfileModalProps) {
+  const [name, setName] = useState<string>('');
+  const [timerIds, setTimerIds] = useState<string[]>([]);
+  const [corner, setCorner] = useState<string | null>(null);
+  const [orientation, setOrientation] = useState<string | null>(null);
+  const [compact, setCompact] = useState<string | null>(null);
+
+  useEffect(() => {
+    setName(profile?.name || '');
+    setTimerIds(profile?.timerIds || []);
+    setCorner(profile?.overlayCorner || null);
+    setOrientation(profile?.overlayOrientation || null);
+    setCompact(profile?.compactMode === undefined ? null : profile.compactMode ? 'compact' : 'full');
+  }, [profile, opened]);
+
+  const handleSave = () => {
+    if (!name.trim()) {
+      return;
+    }
+
+    onSave({
+      id: profile?.id || `profile-${Date.now()}`,
+      name: name.trim(),
+      timerIds,
+      overlayCorner: (corner as AppConfig['overlayCorner']) || undefined,
+      overlayOrientation: (orientation as AppConfig['overlayOrientation']) || undefined,
+      compactMode: compact === null ? undefined : compact === 'compact'
+    });
+    onClose();
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={profile ? 'Edit Profile' : 'Create Profile'}
+      size="md"
+    >
+      <Stack gap="md">
+        <TextInput
+          label="Profile Name"
+          placeholder="e.g., Raid"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          required
+        />
+
+        <MultiSelect
+          label="Timers"
+          description="Timers shown on the overlay while this profile is active. Leave empty to show all"
+          value={timerIds}
+          onChange={setTimerIds}
+          data={timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))}
+          searchable
+        />
+
+        <Select
+          label="Overlay Corner"
+          placeholder="Keep current"
+          value={corner}
+          onChange={setCorner}
+          data={[
+            { value: 'top-left', label: 'Top left' },
+            { value: 'top-center', label: 'Top center' },
+            { value: 'top-right', label: 'Top right' },
+            { value: 'left-center', label: 'Left center' },
+            { value: 'center', label: 'Center' },
+            { value: 'right-center', label: 'Right center' },
+            { value: 'bottom-left', label: 'Bottom left' },
+            { value: 'bottom-center', label: 'Bottom center' },
+            { value: 'bottom-right', label: 'Bottom right' }
+          ]}
+          clearable
+        />
+
+        <Group grow align="flex-start">
+          <Select
+            label="Orientation"
+            placeholder="Keep current"
+            value={orientation}
+            onChange={setOrientation}
+            data={[
+              { value: 'horizontal', label: 'Horizontal' },
+              { value: 'vertical', label: 'Vertical' }
+            ]}
+            clearable
+          />
+          <Select
+            label="Icon Size"
+            placeholder="Keep current"
+            value={compact}
+            onChange={setCompact}
+            data={[
+              { value: 'full', label: 'Normal' },
+              { value: 'compact', label: 'Compact' }
+            ]}
+            clearable
+          />
+        </Group>
+
+        <Group justify="flex-end" mt="md">
+          <Button variant="subtle" onClick={onClose}>Cancel</Button>
+          <Button onClick={handleSave} disabled={!name.trim()}>
+            {profile ? 'Update' : 'Create'}
+          </Button>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
@@ -0,0 +1,89 @@
+import { useEffect, useState } from 'react';
+import { Stack, Switch, TextInput, Group, Text } from '@mantine/core';
+import { invoke } from '@tauri-apps/api/core';
+import { TimerSchedule } from '../types/config';
+
+interface ScheduleEditorProps {
+  value?: TimerSchedule;
+  onChange: (schedule: TimerSchedule | undefined) => void;
+  description?: string;
+}
+
+const DEFAULT_SCHEDULE: TimerSchedule = {
+  cron: '0 9 * * 1-5',
+  skipMissed: true,
+  enabled: true
+};
+
+export function ScheduleEditor({ value, onChange, description }: ScheduleEditorProps) {
+  const [nextRuns, setNextRuns] = useState<number[]>([]);
+  const [error, setError] = useState<string>('');
+
+  useEffect(() => {
+    if (!value?.cron.trim()) {
+      setNextRuns([]);
+      setError('');
+      return;
+    }
+
+    let cancelled = false;
+    invoke<number[]>('preview_schedule', { cron: value.cron, timeZone: value.timeZone || null, count: 3 })
+      .then((runs) => {
+        if (cancelled) return;
+        setNextRuns(runs);
+        setError('');
+      })
+      .catch((err) => {
+        if (cancelled) return;
+        setNextRuns([]);
+        setError(String(err));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [value?.cron, value?.timeZone]);
+
+  return (
+    <div>
+      <Switch
+        label="Start on a Schedule"
+        description={description || 'Start automatically at cron-style times'}
+        checked={Boolean(value)}
+        onChange={(e) => onChange(e.currentTarget.checked ? { ...DEFAULT_SCHEDULE } : undefined)}
+      />
+      {value && (
+        <Stack gap="sm" mt="md" pl="md" style={{ borderLeft: '2px solid #e9ecef' }}>
+          <Group grow align="flex-start">
+            <TextInput
+              label="Cron Expression"
+              description="minute hour day month weekday"
+              placeholder="0 9 * * 1-5"
+              value={value.cron}
+              onChange={(e) => onChange({ ...value, cron: e.target.value })}
+              error={error || undefined}
+            />
+            <TextInput
+              label="Time Zone"
+              description="IANA name (empty = system time zone)"
+              placeholder="Local"
+              value={value.timeZone || ''}
+              onChange={(e) => onChange({ ...value, timeZone: e.target.value || undefined })}
+            />
+          </Group>
+          {nextRuns.length > 0 && (
+            <Text size="xs" c="dimmed">
+              Next runs: {nextRuns.map((run) => new Date(run).toLocaleString()).join(' · ')}
+            </Text>
+          )}
+          <Switch
+            label="Skip Missed Runs"
+            description="Don't start runs that were due while the app was closed"
+            checked={value.skipMissed !== false}
+            onChange={(e) => onChange({ ...value, skipMissed: e.currentTarget.checked })}
+          />
+        </Stack>
+      )}
+    </div>
+  );
+}
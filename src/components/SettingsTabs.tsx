@@ -4,6 +4,10 @@ import { TimersTab } from './TimersTab';
 import { PositioningTab } from './PositioningTab';
 import { KeybindsTab } from './KeybindsTab';
 import { AppearanceTab } from './AppearanceTab';
+import { GroupsTab } from './GroupsTab';
+import { TriggersTab } from './TriggersTab';
+import { HistoryTab } from './HistoryTab';
+import { GoalsTab } from './GoalsTab';
 
 interface SettingsTabsProps {
   config: AppConfig;
@@ -17,6 +21,10 @@ export function SettingsTabs({ config, onConfigChange, onAddIcon, onIconClick }:
     <Tabs defaultValue="timers">
       <Tabs.List>
         <Tabs.Tab value="timers">Timers</Tabs.Tab>
+        <Tabs.Tab value="groups">Groups</Tabs.Tab>
+        <Tabs.Tab value="triggers">Triggers</Tabs.Tab>
+        <Tabs.Tab value="history">History</Tabs.Tab>
+        <Tabs.Tab value="goals">Goals</Tabs.Tab>
         <Tabs.Tab value="positioning">Positioning</Tabs.Tab>
         <Tabs.Tab value="keybinds">Keybinds</Tabs.Tab>
         <Tabs.Tab value="appearance">Appearance</Tabs.Tab>
@@ -31,6 +39,34 @@ export function SettingsTabs({ config, onConfigChange, onAddIcon, onIconClick }:
         />
       </Tabs.Panel>
 
+      <Tabs.Panel value="groups" pt="md">
+        <GroupsTab
+          config={config}
+          onConfigChange={onConfigChange}
+        />
+      </Tabs.Panel>
+
+      <Tabs.Panel value="triggers" pt="md">
+        <TriggersTab
+          config={config}
+          onConfigChange={onConfigChange}
+        />
+      </Tabs.Panel>
+
+      <Tabs.Panel value="history" pt="md">
+        <HistoryTab
+          config={config}
+          onConfigChange={onConfigChange}
+        />
+      </Tabs.Panel>
+
+      <Tabs.Panel value="goals" pt="md">
+        <GoalsTab
+          config={config}
+          onConfigChange={onConfigChange}
+        />
+      </Tabs.Panel>
+
       <Tabs.Panel value="positioning" pt="md">
         <PositioningTab
           config={config}
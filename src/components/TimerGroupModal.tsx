@@ -0,0 +1,136 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Text, Stack, TextInput, MultiSelect, Select } from '@mantine/core';
+import { IconConfig, TimerGroup, TimerGroupAction, TimerSchedule } from '../types/config';
+import { KeybindInput } from './KeybindInput';
+import { ScheduleEditor } from './ScheduleEditor';
+
+interface TimerGroupModalProps {
+  opened: boolean;
+  onClose: () => void;
+  group: TimerGroup | null;
+  timers: IconConfig[];
+  onSave: (group: TimerGroup) => void;
+  existingKeybinds: string[];
+}
+
+export function TimerGroupModal({ opened, onClose, group, timers, onSave, existingKeybinds }: TimerGroupModalProps) {
+  const [name, setName] = useState<string>('');
+  const [timerIds, setTimerIds] = useState<string[]>([]);
+  const [keybind, setKeybind] = useState<string>('');
+  const [keybindAction, setKeybindAction] = useState<TimerGroupAction>('start');
+  const [keybindError, setKeybindError] = useState<string>('');
+  const [schedule, setSchedule] = useState<TimerSchedule | undefined>(undefined);
+
+  useEffect(() => {
+    if (group) {
+      setName(group.name || '');
+      setTimerIds(group.timerIds || []);
+      setKeybind(group.keybind || '');
+      setKeybindAction(group.keybindAction || 'start');
+      setSchedule(group.schedule);
+    } else {
+      setName('');
+      setTimerIds([]);
+      setKeybind('');
+      setKeybindAction('start');
+      setSchedule(undefined);
+    }
+    setKeybindError('');
+  }, [group, opened]);
+
+  const handleSave = () => {
+    if (!name.trim()) {
+      return;
+    }
+
+    const trimmedKeybind = keybind.trim();
+    if (trimmedKeybind && existingKeybinds.includes(trimmedKeybind) && group?.keybind !== trimmedKeybind) {
+      setKeybindError('This keybind is already in use');
+      return;
+    }
+
+    const newGroup: TimerGroup = {
+      id: group?.id || `group-${Date.now()}`,
+      name: name.trim(),
+      timerIds,
+      keybind: trimmedKeybind || undefined,
+      keybindAction: trimmedKeybind ? keybindAction : undefined,
+      schedule
+    };
+
+    onSave(newGroup);
+    onClose();
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={group ? 'Edit Timer Group' : 'Create Timer Group'}
+      size="md"
+    >
+      <Stack gap="md">
+        <TextInput
+          label="Group Name"
+          placeholder="e.g., Raid Cooldowns"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          required
+        />
+
+        <MultiSelect
+          label="Timers"
+          description="Timers controlled together by this group"
+          value={timerIds}
+          onChange={setTimerIds}
+          data={timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))}
+          searchable
+        />
+
+        <div>
+          <Text size="sm" fw={500} mb="xs">Group Shortcut (optional)</Text>
+          <KeybindInput
+            value={keybind}
+            onChange={(value) => {
+              setKeybind(value);
+              setKeybindError('');
+            }}
+            onError={(error) => {
+              setKeybindError(error || '');
+            }}
+            optional
+          />
+          {keybindError && (
+            <Text size="xs" c="red" mt={4}>{keybindError}</Text>
+          )}
+        </div>
+
+        {keybind.trim() && (
+          <Select
+            label="Shortcut Action"
+            value={keybindAction}
+            onChange={(value) => setKeybindAction((value as TimerGroupAction) || 'start')}
+            data={[
+              { value: 'start', label: 'Start all timers' },
+              { value: 'pause', label: 'Pause/resume all timers' },
+              { value: 'reset', label: 'Reset all timers' }
+            ]}
+          />
+        )}
+
+        <ScheduleEditor
+          value={schedule}
+          onChange={setSchedule}
+          description="Start every timer in this group automatically at cron-style times"
+        />
+
+        <Group justify="flex-end" mt="md">
+          <Button variant="subtle" onClick={onClose}>Cancel</Button>
+          <Button onClick={handleSave} disabled={!name.trim()}>
+            {group ? 'Update' : 'Create'}
+          </Button>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
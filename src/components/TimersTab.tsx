@@ -39,7 +39,11 @@ function formatDuration(totalSeconds: number) {
 export function TimersTab({ config, onConfigChange, onAddIcon, onIconClick }: TimersTabProps) {
   const handleDeleteIcon = (iconId: string) => {
     const newIcons = config.icons.filter(icon => icon.id !== iconId);
-    onConfigChange({ ...config, icons: newIcons });
+    const newGroups = config.timerGroups?.map(group => ({
+      ...group,
+      timerIds: group.timerIds.filter(id => id !== iconId)
+    }));
+    onConfigChange({ ...config, icons: newIcons, timerGroups: newGroups });
   };
 
   const handleStartTimer = async (iconId: string) => {
@@ -0,0 +1,620 @@
+import { useEffect, useState } from 'react';
+import { Stack, Text, Card, Button, Group, ActionIcon, Badge, Switch, NumberInput } from '@mantine/core';
+import { invoke } from '@tauri-apps/api/core';
+import * as Icons from 'lucide-react';
+import { AppConfig, EventHook, LogWatcher, ProcessRule, WebhookTarget } from '../types/config';
+import { LogWatcherModal } from './LogWatcherModal';
+import { ProcessRuleModal } from './ProcessRuleModal';
+import { EventHookModal, HOOK_EVENT_LABELS } from './EventHookModal';
+import { WebhookModal, WEBHOOK_EVENT_LABELS } from './WebhookModal';
+
+interface TriggersTabProps {
+  config: AppConfig;
+  onConfigChange: (config: AppConfig) => void;
+}
+
+interface LogWatchStatus {
+  id: string;
+  watching: boolean;
+  matchCount: number;
+  lastMatchAt?: number;
+  lastLine?: string;
+  error?: string;
+}
+
+interface ProcessRuleStatus {
+  id: string;
+  processRunning: boolean;
+  lastFiredAt?: number;
+}
+
+interface EventHookStatus {
+  id: string;
+  runs: number;
+  running: number;
+  lastRunAt?: number;
+  lastDurationMs?: number;
+  lastExitCode?: number;
+  lastError?: string;
+  lastOutput: string[];
+}
+
+interface WebhookStatus {
+  id: string;
+  delivered: number;
+  failed: number;
+  pending: number;
+  lastEvent?: string;
+  lastAttemptAt?: number;
+  lastAttempts: number;
+  lastStatusCode?: number;
+  lastError?: string;
+  lastSuccessAt?: number;
+}
+
+const PROCESS_ACTION_LABELS: Record<ProcessRule['action'], string> = {
+  start: 'start',
+  stop: 'stop',
+  reset: 'reset',
+  profile: 'switch to'
+};
+
+const ACTION_LABELS: Record<LogWatcher['action'], string> = {
+  start: 'Start',
+  reset: 'Reset',
+  pause: 'Pause',
+  toggle: 'Pause/resume'
+};
+
+export function TriggersTab({ config, onConfigChange }: TriggersTabProps) {
+  const [modalOpened, setModalOpened] = useState(false);
+  const [selectedWatcher, setSelectedWatcher] = useState<LogWatcher | null>(null);
+  const [status, setStatus] = useState<Record<string, LogWatchStatus>>({});
+  const [ruleModalOpened, setRuleModalOpened] = useState(false);
+  const [selectedRule, setSelectedRule] = useState<ProcessRule | null>(null);
+  const [ruleStatus, setRuleStatus] = useState<Record<string, ProcessRuleStatus>>({});
+  const [hookModalOpened, setHookModalOpened] = useState(false);
+  const [selectedHook, setSelectedHook] = useState<{ hook: EventHook | null; timerId: string | null }>({ hook: null, timerId: null });
+  const [hookStatus, setHookStatus] = useState<Record<string, EventHookStatus>>({});
+  const [webhookModalOpened, setWebhookModalOpened] = useState(false);
+  const [selectedWebhook, setSelectedWebhook] = useState<WebhookTarget | null>(null);
+  const [webhookStatus, setWebhookStatus] = useState<Record<string, WebhookStatus>>({});
+
+  const watchers = config.logWatchers || [];
+  const rules = config.processRules || [];
+  const webhooks = config.webhooks || [];
+  // Hooks globais e os de cada timer, na mesma lista
+  const hooks: { hook: EventHook; timerId: string | null }[] = [
+    ...(config.eventHooks || []).map(hook => ({ hook, timerId: null })),
+    ...config.icons.flatMap(icon => (icon.eventHooks || []).map(hook => ({ hook, timerId: icon.id })))
+  ];
+
+  // O Rust lê os arquivos; aqui só mostramos o estado de cada gatilho
+  useEffect(() => {
+    const refresh = async () => {
+      try {
+        const [logStatus, processStatus, eventHookStatus, deliveryStatus] = await Promise.all([
+          invoke<LogWatchStatus[]>('get_log_watch_status'),
+          invoke<ProcessRuleStatus[]>('get_process_rule_status'),
+          invoke<EventHookStatus[]>('get_event_hook_status'),
+          invoke<WebhookStatus[]>('get_webhook_status')
+        ]);
+        setStatus(Object.fromEntries(logStatus.map(s => [s.id, s])));
+        setRuleStatus(Object.fromEntries(processStatus.map(s => [s.id, s])));
+        setHookStatus(Object.fromEntries(eventHookStatus.map(s => [s.id, s])));
+        setWebhookStatus(Object.fromEntries(deliveryStatus.map(s => [s.id, s])));
+      } catch (error) {
+        console.error('Failed to load trigger status', error);
+      }
+    };
+    refresh();
+    const interval = window.setInterval(refresh, 2000);
+    return () => clearInterval(interval);
+  }, []);
+
+  const handleSaveWatcher = (watcher: LogWatcher) => {
+    const existingIndex = watchers.findIndex(w => w.id === watcher.id);
+    if (existingIndex >= 0) {
+      const newWatchers = [...watchers];
+      newWatchers[existingIndex] = watcher;
+      onConfigChange({ ...config, logWatchers: newWatchers });
+    } else {
+      onConfigChange({ ...config, logWatchers: [...watchers, watcher] });
+    }
+    setSelectedWatcher(null);
+  };
+
+  const handleDeleteWatcher = (watcherId: string) => {
+    onConfigChange({ ...config, logWatchers: watchers.filter(w => w.id !== watcherId) });
+  };
+
+  const handleToggleWatcher = (watcher: LogWatcher, enabled: boolean) => {
+    handleSaveWatcher({ ...watcher, enabled });
+  };
+
+  const handleSaveRule = (rule: ProcessRule) => {
+    const existingIndex = rules.findIndex(r => r.id === rule.id);
+    if (existingIndex >= 0) {
+      const newRules = [...rules];
+      newRules[existingIndex] = rule;
+      onConfigChange({ ...config, processRules: newRules });
+    } else {
+      onConfigChange({ ...config, processRules: [...rules, rule] });
+    }
+    setSelectedRule(null);
+  };
+
+  const handleDeleteRule = (ruleId: string) => {
+    onConfigChange({ ...config, processRules: rules.filter(r => r.id !== ruleId) });
+  };
+
+  // Tira o hook de onde estava (global ou de um timer) e grava no dono escolhido
+  const handleSaveHook = (hook: EventHook, timerId: string | null) => {
+    const replaceIn = (list: EventHook[] | undefined, keep: boolean) => {
+      const without = (list || []).filter(h => h.id !== hook.id);
+      if (!keep) return without;
+      const index = (list || []).findIndex(h => h.id === hook.id);
+      if (index < 0) return [...without, hook];
+      const updated = [...(list || [])];
+      updated[index] = hook;
+      return updated;
+    };
+    onConfigChange({
+      ...config,
+      eventHooks: replaceIn(config.eventHooks, timerId === null),
+      icons: config.icons.map(icon => {
+        const has = (icon.eventHooks || []).some(h => h.id === hook.id);
+        if (!has && icon.id !== timerId) return icon;
+        return { ...icon, eventHooks: replaceIn(icon.eventHooks, icon.id === timerId) };
+      })
+    });
+    setSelectedHook({ hook: null, timerId: null });
+  };
+
+  const handleDeleteHook = (hookId: string) => {
+    onConfigChange({
+      ...config,
+      eventHooks: (config.eventHooks || []).filter(h => h.id !== hookId),
+      icons: config.icons.map(icon =>
+        (icon.eventHooks || []).some(h => h.id === hookId)
+          ? { ...icon, eventHooks: (icon.eventHooks || []).filter(h => h.id !== hookId) }
+          : icon
+      )
+    });
+  };
+
+  const handleSaveWebhook = (webhook: WebhookTarget) => {
+    const existingIndex = webhooks.findIndex(w => w.id === webhook.id);
+    if (existingIndex >= 0) {
+      const newWebhooks = [...webhooks];
+      newWebhooks[existingIndex] = webhook;
+      onConfigChange({ ...config, webhooks: newWebhooks });
+    } else {
+      onConfigChange({ ...config, webhooks: [...webhooks, webhook] });
+    }
+    setSelectedWebhook(null);
+  };
+
+  const handleDeleteWebhook = (webhookId: string) => {
+    onConfigChange({ ...config, webhooks: webhooks.filter(w => w.id !== webhookId) });
+  };
+
+  const renderWebhookStatus = (webhook: WebhookTarget) => {
+    const current = webhookStatus[webhook.id];
+    if (webhook.enabled === false) {
+      return <Badge color="gray" variant="light">Disabled</Badge>;
+    }
+    if (!current) {
+      return <Badge color="gray" variant="light">Nothing sent yet</Badge>;
+    }
+    if (current.pending > 0 && current.lastError) {
+      return <Badge color="yellow" variant="light" title={current.lastError}>Retrying</Badge>;
+    }
+    if (current.lastError) {
+      return <Badge color="red" variant="light" title={current.lastError}>Failed</Badge>;
+    }
+    return <Badge color="green" variant="light">Delivered</Badge>;
+  };
+
+  const renderHookStatus = (hook: EventHook) => {
+    const current = hookStatus[hook.id];
+    if (hook.enabled === false) {
+      return <Badge color="gray" variant="light">Disabled</Badge>;
+    }
+    if (!current || current.runs + current.running === 0) {
+      return <Badge color="gray" variant="light">Not run yet</Badge>;
+    }
+    if (current.running > 0) {
+      return <Badge color="blue" variant="light">Running</Badge>;
+    }
+    if (current.lastError) {
+      return <Badge color="red" variant="light" title={current.lastError}>Failed</Badge>;
+    }
+    return <Badge color="green" variant="light">OK</Badge>;
+  };
+
+  const targetName = (target: ProcessRule['target']) => {
+    if (target.type === 'timer') {
+      return `timer "${timerName(target.timerId)}"`;
+    }
+    if (target.type === 'profile') {
+      const profile = (config.profiles || []).find(p => p.id === target.profileId);
+      return `profile "${profile ? profile.name : 'Missing profile'}"`;
+    }
+    const group = (config.timerGroups || []).find(g => g.id === target.groupId);
+    return `group "${group ? group.name : 'Missing group'}"`;
+  };
+
+  const timerName = (timerId: string) => {
+    const timer = config.icons.find(icon => icon.id === timerId);
+    return timer ? timer.name || timer.iconName : 'Missing timer';
+  };
+
+  const renderStatus = (watcher: LogWatcher) => {
+    const current = status[watcher.id];
+    if (watcher.enabled === false) {
+      return <Badge color="gray" variant="light">Disabled</Badge>;
+    }
+    if (!current) {
+      return <Badge color="gray" variant="light">Starting</Badge>;
+    }
+    if (current.error) {
+      return <Badge color="red" variant="light" title={current.error}>Error</Badge>;
+    }
+    return <Badge color="green" variant="light">Watching</Badge>;
+  };
+
+  return (
+    <Stack gap="md">
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Log Triggers</Text>
+            <Text size="xs" c="dimmed">
+              Start, pause or reset timers when a game or tool writes a matching line to its log file
+            </Text>
+          </div>
+          <Button size="xs" onClick={() => {
+            setSelectedWatcher(null);
+            setModalOpened(true);
+          }}>
+            Add Trigger
+          </Button>
+        </Group>
+        <Stack gap="xs">
+          {watchers.length > 0 ? (
+            watchers.map((watcher) => {
+              const current = status[watcher.id];
+              return (
+                <Card key={watcher.id} padding="sm" withBorder>
+                  <Group justify="space-between" wrap="nowrap">
+                    <div style={{ flex: 1, minWidth: 0 }}>
+                      <Group gap="xs">
+                        <Text size="sm" fw={500}>{watcher.name || watcher.pattern}</Text>
+                        {renderStatus(watcher)}
+                      </Group>
+                      <Text size="xs" c="dimmed" truncate="end">
+                        {ACTION_LABELS[watcher.action]} "{timerName(watcher.timerId)}" | {watcher.path}
+                      </Text>
+                      {current?.error && watcher.enabled !== false && (
+                        <Text size="xs" c="red">{current.error}</Text>
+                      )}
+                      {current && current.matchCount > 0 && (
+                        <Text size="xs" c="dimmed" truncate="end">
+                          {current.matchCount} matches, last at {new Date(current.lastMatchAt ?? 0).toLocaleTimeString()}: {current.lastLine}
+                        </Text>
+                      )}
+                    </div>
+                    <Group gap="xs" wrap="nowrap">
+                      <Switch
+                        size="xs"
+                        checked={watcher.enabled !== false}
+                        onChange={(e) => handleToggleWatcher(watcher, e.currentTarget.checked)}
+                      />
+                      <Button size="xs" variant="light" onClick={() => {
+                        setSelectedWatcher(watcher);
+                        setModalOpened(true);
+                      }}>
+                        Edit
+                      </Button>
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        onClick={() => handleDeleteWatcher(watcher.id)}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  </Group>
+                </Card>
+              );
+            })
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No log triggers configured. Point a trigger at a log file to start timers from game events.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Process Rules</Text>
+            <Text size="xs" c="dimmed">
+              Start, stop or reset timers and groups when a program opens or closes
+            </Text>
+          </div>
+          <Button size="xs" onClick={() => {
+            setSelectedRule(null);
+            setRuleModalOpened(true);
+          }}>
+            Add Rule
+          </Button>
+        </Group>
+        <Stack gap="xs">
+          {rules.length > 0 ? (
+            rules.map((rule) => {
+              const current = ruleStatus[rule.id];
+              return (
+                <Card key={rule.id} padding="sm" withBorder>
+                  <Group justify="space-between" wrap="nowrap">
+                    <div style={{ flex: 1, minWidth: 0 }}>
+                      <Group gap="xs">
+                        <Text size="sm" fw={500}>{rule.name || rule.pattern}</Text>
+                        {rule.enabled === false ? (
+                          <Badge color="gray" variant="light">Disabled</Badge>
+                        ) : current?.processRunning ? (
+                          <Badge color="green" variant="light">Running</Badge>
+                        ) : (
+                          <Badge color="gray" variant="light">Not running</Badge>
+                        )}
+                      </Group>
+                      <Text size="xs" c="dimmed" truncate="end">
+                        When {rule.pattern} {rule.event === 'start' ? 'starts' : 'exits'}: {PROCESS_ACTION_LABELS[rule.action]} {targetName(rule.target)}
+                      </Text>
+                      {current?.lastFiredAt && (
+                        <Text size="xs" c="dimmed">
+                          Last fired at {new Date(current.lastFiredAt).toLocaleTimeString()}
+                        </Text>
+                      )}
+                    </div>
+                    <Group gap="xs" wrap="nowrap">
+                      <Switch
+                        size="xs"
+                        checked={rule.enabled !== false}
+                        onChange={(e) => handleSaveRule({ ...rule, enabled: e.currentTarget.checked })}
+                      />
+                      <Button size="xs" variant="light" onClick={() => {
+                        setSelectedRule(rule);
+                        setRuleModalOpened(true);
+                      }}>
+                        Edit
+                      </Button>
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        onClick={() => handleDeleteRule(rule.id)}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  </Group>
+                </Card>
+              );
+            })
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No process rules configured. Add one to start a session stopwatch when your game launches.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Event Hooks</Text>
+            <Text size="xs" c="dimmed">
+              Run your own scripts when timers start, finish or repeat: dim lights, pause music, post to chat
+            </Text>
+          </div>
+          <Group gap="xs" align="flex-end">
+            <NumberInput
+              size="xs"
+              w={150}
+              label="Max Running At Once"
+              min={1}
+              value={config.eventHookConcurrency ?? 4}
+              onChange={(value) => onConfigChange({
+                ...config,
+                eventHookConcurrency: typeof value === 'number' ? value : undefined
+              })}
+            />
+            <Button size="xs" onClick={() => {
+              setSelectedHook({ hook: null, timerId: null });
+              setHookModalOpened(true);
+            }}>
+              Add Hook
+            </Button>
+          </Group>
+        </Group>
+        <Stack gap="xs">
+          {hooks.length > 0 ? (
+            hooks.map(({ hook, timerId }) => {
+              const current = hookStatus[hook.id];
+              return (
+                <Card key={hook.id} padding="sm" withBorder>
+                  <Group justify="space-between" wrap="nowrap">
+                    <div style={{ flex: 1, minWidth: 0 }}>
+                      <Group gap="xs">
+                        <Text size="sm" fw={500}>{hook.name || hook.command}</Text>
+                        {renderHookStatus(hook)}
+                      </Group>
+                      <Text size="xs" c="dimmed" truncate="end">
+                        On {hook.events.length > 0 ? hook.events.map(e => HOOK_EVENT_LABELS[e].toLowerCase()).join(', ') : 'any event'} of{' '}
+                        {timerId ? `"${timerName(timerId)}"` : 'any timer'} | {[hook.command, ...(hook.args || [])].join(' ')}
+                      </Text>
+                      {current?.lastError && hook.enabled !== false && (
+                        <Text size="xs" c="red" truncate="end">{current.lastError}</Text>
+                      )}
+                      {current && current.lastOutput.length > 0 && (
+                        <Text size="xs" c="dimmed" truncate="end">
+                          {current.lastOutput[current.lastOutput.length - 1]}
+                        </Text>
+                      )}
+                    </div>
+                    <Group gap="xs" wrap="nowrap">
+                      <Switch
+                        size="xs"
+                        checked={hook.enabled !== false}
+                        onChange={(e) => handleSaveHook({ ...hook, enabled: e.currentTarget.checked }, timerId)}
+                      />
+                      <Button size="xs" variant="light" onClick={() => {
+                        setSelectedHook({ hook, timerId });
+                        setHookModalOpened(true);
+                      }}>
+                        Edit
+                      </Button>
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        onClick={() => handleDeleteHook(hook.id)}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  </Group>
+                </Card>
+              );
+            })
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No event hooks configured. Add one to run a command when a timer finishes.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <Card withBorder p="md">
+        <Group justify="space-between" mb="md">
+          <div>
+            <Text size="lg" fw={600}>Webhooks</Text>
+            <Text size="xs" c="dimmed">
+              POST a JSON payload to your team tools when timers start, finish or get close to zero
+            </Text>
+          </div>
+          <Button size="xs" onClick={() => {
+            setSelectedWebhook(null);
+            setWebhookModalOpened(true);
+          }}>
+            Add Webhook
+          </Button>
+        </Group>
+        <Stack gap="xs">
+          {webhooks.length > 0 ? (
+            webhooks.map((webhook) => {
+              const current = webhookStatus[webhook.id];
+              return (
+                <Card key={webhook.id} padding="sm" withBorder>
+                  <Group justify="space-between" wrap="nowrap">
+                    <div style={{ flex: 1, minWidth: 0 }}>
+                      <Group gap="xs">
+                        <Text size="sm" fw={500}>{webhook.name || webhook.url}</Text>
+                        {renderWebhookStatus(webhook)}
+                      </Group>
+                      <Text size="xs" c="dimmed" truncate="end">
+                        On {webhook.events.length > 0 ? webhook.events.map(e => WEBHOOK_EVENT_LABELS[e].toLowerCase()).join(', ') : 'any event'}
+                        {webhook.secret ? ' | signed' : ''} | {webhook.url}
+                      </Text>
+                      {current?.lastAttemptAt && (
+                        <Text size="xs" c={current.lastError ? 'red' : 'dimmed'} truncate="end">
+                          {current.lastEvent} at {new Date(current.lastAttemptAt).toLocaleTimeString()}
+                          {current.lastStatusCode ? ` (HTTP ${current.lastStatusCode})` : ''}
+                          {current.lastAttempts > 1 ? `, attempt ${current.lastAttempts}` : ''}
+                          {current.lastError ? `: ${current.lastError}` : ''}
+                          {` | ${current.delivered} delivered, ${current.failed} failed`}
+                        </Text>
+                      )}
+                    </div>
+                    <Group gap="xs" wrap="nowrap">
+                      <Switch
+                        size="xs"
+                        checked={webhook.enabled !== false}
+                        onChange={(e) => handleSaveWebhook({ ...webhook, enabled: e.currentTarget.checked })}
+                      />
+                      <Button size="xs" variant="light" onClick={() => {
+                        setSelectedWebhook(webhook);
+                        setWebhookModalOpened(true);
+                      }}>
+                        Edit
+                      </Button>
+                      <ActionIcon
+                        color="red"
+                        variant="light"
+                        onClick={() => handleDeleteWebhook(webhook.id)}
+                      >
+                        <Icons.Trash2 size={16} />
+                      </ActionIcon>
+                    </Group>
+                  </Group>
+                </Card>
+              );
+            })
+          ) : (
+            <Text size="sm" c="dimmed" ta="center" py="md">
+              No webhooks configured. Add one to notify a chat or dashboard when a timer finishes.
+            </Text>
+          )}
+        </Stack>
+      </Card>
+
+      <LogWatcherModal
+        opened={modalOpened}
+        onClose={() => {
+          setModalOpened(false);
+          setSelectedWatcher(null);
+        }}
+        watcher={selectedWatcher}
+        timers={config.icons}
+        onSave={handleSaveWatcher}
+      />
+
+      <ProcessRuleModal
+        opened={ruleModalOpened}
+        onClose={() => {
+          setRuleModalOpened(false);
+          setSelectedRule(null);
+        }}
+        rule={selectedRule}
+        timers={config.icons}
+        groups={config.timerGroups || []}
+        profiles={config.profiles || []}
+        onSave={handleSaveRule}
+      />
+
+      <WebhookModal
+        opened={webhookModalOpened}
+        onClose={() => {
+          setWebhookModalOpened(false);
+          setSelectedWebhook(null);
+        }}
+        webhook={selectedWebhook}
+        timers={config.icons}
+        onSave={handleSaveWebhook}
+      />
+
+      <EventHookModal
+        opened={hookModalOpened}
+        onClose={() => {
+          setHookModalOpened(false);
+          setSelectedHook({ hook: null, timerId: null });
+        }}
+        hook={selectedHook.hook}
+        timerId={selectedHook.timerId}
+        timers={config.icons}
+        onSave={handleSaveHook}
+      />
+    </Stack>
+  );
+}
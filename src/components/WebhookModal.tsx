@@ -0,0 +1,188 @@
+import { useState, useEffect } from 'react';
+import { Modal, Button, Group, Stack, TextInput, Textarea, MultiSelect, NumberInput, PasswordInput, Text } from '@mantine/core';
+import { invoke } from '@tauri-apps/api/core';
+import { IconConfig, WebhookTarget } from '../types/config';
+import { HOOK_EVENT_LABELS } from './EventHookModal';
+
+interface WebhookModalProps {
+  opened: boolean;
+  onClose: () => void;
+  webhook: WebhookTarget | null;
+  timers: IconConfig[];
+  onSave: (webhook: WebhookTarget) => void;
+}
+
+export const WEBHOOK_EVENT_LABELS: Record<WebhookTarget['events'][number], string> = {
+  ...HOOK_EVENT_LABELS,
+  warning: 'Warning threshold'
+};
+
+// "Nome: valor" por linha
+function parseHeaders(text: string): Record<string, string> | undefined {
+  const entries = text
+    .split('\n')
+    .filter(line => line.includes(':'))
+    .map(line => {
+      const index = line.indexOf(':');
+      return [line.slice(0, index).trim(), line.slice(index + 1).trim()];
+    })
+    .filter(([name]) => name);
+  return entries.length > 0 ? Object.fromEntries(entries) : undefined;
+}
+
+export function WebhookModal({ opened, onClose, webhook, timers, onSave }: WebhookModalProps) {
+  const [name, setName] = useState<string>('');
+  const [url, setUrl] = useState<string>('');
+  const [events, setEvents] = useState<string[]>([]);
+  const [timerIds, setTimerIds] = useState<string[]>([]);
+  const [warnAt, setWarnAt] = useState<string>('');
+  const [headers, setHeaders] = useState<string>('');
+  const [secret, setSecret] = useState<string>('');
+  const [maxAttempts, setMaxAttempts] = useState<number>(5);
+  const [testMessage, setTestMessage] = useState<string>('');
+
+  useEffect(() => {
+    setName(webhook?.name || '');
+    setUrl(webhook?.url || '');
+    setEvents(webhook?.events || ['started', 'finished']);
+    setTimerIds(webhook?.timerIds || []);
+    setWarnAt((webhook?.warnAtSeconds || []).join(', '));
+    setHeaders(Object.entries(webhook?.headers || {}).map(([k, v]) => `${k}: ${v}`).join('\n'));
+    setSecret(webhook?.secret || '');
+    setMaxAttempts(webhook?.maxAttempts ?? 5);
+    setTestMessage('');
+  }, [webhook, opened]);
+
+  const validUrl = /^https?:\/\/\S+$/i.test(url.trim());
+
+  const buildWebhook = (): WebhookTarget => {
+    const thresholds = warnAt
+      .split(',')
+      .map(v => parseInt(v.trim(), 10))
+      .filter(v => Number.isFinite(v) && v > 0);
+    return {
+      id: webhook?.id || `webhook-${Date.now()}`,
+      name: name.trim() || undefined,
+      url: url.trim(),
+      events: events as WebhookTarget['events'],
+      timerIds: timerIds.length > 0 ? timerIds : undefined,
+      warnAtSeconds: thresholds.length > 0 ? thresholds : undefined,
+      headers: parseHeaders(headers),
+      secret: secret || undefined,
+      maxAttempts,
+      enabled: webhook?.enabled ?? true
+    };
+  };
+
+  const handleSave = () => {
+    if (!validUrl) {
+      return;
+    }
+    onSave(buildWebhook());
+    onClose();
+  };
+
+  const handleTest = async () => {
+    try {
+      await invoke('test_webhook', { webhook: buildWebhook() });
+      setTestMessage('Test delivery queued. Its status shows up in the webhook list.');
+    } catch (error) {
+      setTestMessage(String(error));
+    }
+  };
+
+  return (
+    <Modal
+      opened={opened}
+      onClose={onClose}
+      title={webhook ? 'Edit Webhook' : 'Create Webhook'}
+      size="md"
+    >
+      <Stack gap="md">
+        <TextInput
+          label="Name"
+          placeholder="e.g., Team dashboard"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+
+        <TextInput
+          label="URL"
+          placeholder="https://example.com/hooks/timers"
+          value={url}
+          onChange={(e) => setUrl(e.target.value)}
+          error={url.trim() && !validUrl ? 'Use an http:// or https:// URL' : undefined}
+          required
+        />
+
+        <MultiSelect
+          label="Events"
+          placeholder={events.length === 0 ? 'All events' : undefined}
+          value={events}
+          onChange={setEvents}
+          data={Object.entries(WEBHOOK_EVENT_LABELS).map(([value, label]) => ({ value, label }))}
+        />
+
+        <MultiSelect
+          label="Timers"
+          placeholder={timerIds.length === 0 ? 'All timers' : undefined}
+          value={timerIds}
+          onChange={setTimerIds}
+          data={timers.map(timer => ({ value: timer.id, label: timer.name || timer.iconName || timer.id }))}
+          searchable
+        />
+
+        {(events.length === 0 || events.includes('warning')) && (
+          <TextInput
+            label="Warn At (seconds left)"
+            description="Comma-separated, e.g. 60, 10. Sent once per countdown when the time left drops below each value."
+            value={warnAt}
+            onChange={(e) => setWarnAt(e.target.value)}
+          />
+        )}
+
+        <Textarea
+          label="Headers"
+          description="One per line, e.g. Authorization: Bearer abc"
+          autosize
+          minRows={2}
+          value={headers}
+          onChange={(e) => setHeaders(e.currentTarget.value)}
+        />
+
+        <Group grow>
+          <PasswordInput
+            label="Signing Secret"
+            description="Adds X-Timer-Signature-256"
+            value={secret}
+            onChange={(e) => setSecret(e.currentTarget.value)}
+          />
+          <NumberInput
+            label="Max Attempts"
+            description="Retries back off exponentially"
+            min={1}
+            max={10}
+            value={maxAttempts}
+            onChange={(value) => setMaxAttempts(typeof value === 'number' ? value : 5)}
+          />
+        </Group>
+
+        {testMessage && (
+          <Text size="xs" c="dimmed">{testMessage}</Text>
+        )}
+
+        <Group justify="space-between" mt="md">
+          <Button variant="light" onClick={handleTest} disabled={!validUrl}>
+            Send Test
+          </Button>
+          <Group gap="xs">
+            <Button variant="subtle" onClick={onClose}>Cancel</Button>
+            <Button onClick={handleSave} disabled={!validUrl}>
+              {webhook ? 'Update' : 'Create'}
+            </Button>
+          </Group>
+        </Group>
+      </Stack>
+    </Modal>
+  );
+}
@@ -1,5 +1,6 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { timerLogger } from '../utils/logger';
+import { isClockHeld } from '../utils/clockGuard';
 
 export interface TimerState {
   running: boolean;
@@ -7,6 +8,8 @@ export interface TimerState {
   startTime: number | null;
   duration: number;
   isInInterval?: boolean;
+  // Duração do intervalo atual (pode variar a cada repetição)
+  intervalLength?: number;
   repeatCount?: number;
 }
 
@@ -22,6 +25,9 @@ interface TimerCallbacks {
   onTimerComplete?: (info: { repeatCount: number }) => void;
   onIntervalComplete?: (info: { repeatCount: number }) => void;
   onFinalComplete?: (info: { repeatCount: number }) => void;
+  onPhaseStart?: (info: { phase: number; duration: number }) => void;
+  // Duração e intervalo da próxima repetição; ausente = os da configuração
+  nextRepeat?: () => { duration?: number; interval?: number } | undefined;
 }
 
 export function useTimer(
@@ -48,9 +54,14 @@ export function useTimer(
   stateRef.current = state; // Sempre manter a referência atualizada
   const callbacksRef = useRef<TimerCallbacks | undefined>(callbacks);
   callbacksRef.current = callbacks;
+  // Duração configurada; `start` pode usar outra só naquela execução
+  const configuredDurationRef = useRef(initialDuration);
+  // Duração da próxima repetição, escolhida quando a anterior termina
+  const nextDurationRef = useRef(initialDuration);
 
   // Atualizar duração quando initialDuration mudar
   useEffect(() => {
+    configuredDurationRef.current = initialDuration;
     if (initialDuration !== state.duration) {
       timerLogger.info(`Timer ${timerId.current} duration updated from config: ${state.duration}s -> ${initialDuration}s`);
       setState(prev => ({
@@ -62,7 +73,8 @@ export function useTimer(
   }, [initialDuration, timerType]);
 
   useEffect(() => {
-    if (state.isInInterval && repeatConfig?.interval && state.startTime) {
+    const intervalLength = state.intervalLength ?? repeatConfig?.interval ?? 0;
+    if (state.isInInterval && intervalLength > 0 && state.startTime) {
       if (intervalRef.current) {
         clearInterval(intervalRef.current);
         intervalRef.current = null;
@@ -77,9 +89,11 @@ export function useTimer(
           }
           return;
         }
+        // Relógio saltou: espera o backend dizer como corrigir antes de contar
+        if (isClockHeld()) return;
         
         const elapsed = Math.floor((Date.now() - currentState.startTime!) / 1000);
-        const intervalRemaining = Math.max(0, repeatConfig.interval! - elapsed);
+        const intervalRemaining = Math.max(0, intervalLength - elapsed);
         
         setState(prev => ({
           ...prev,
@@ -90,6 +104,8 @@ export function useTimer(
         if (intervalRemaining === 0) {
           timerLogger.info(`Timer ${timerId.current} interval completed, restarting...`);
           repeatCountRef.current += 1;
+          const duration = nextDurationRef.current;
+          callbacksRef.current?.onPhaseStart?.({ phase: repeatCountRef.current + 1, duration });
           if (intervalRef.current) {
             clearInterval(intervalRef.current);
             intervalRef.current = null;
@@ -97,7 +113,8 @@ export function useTimer(
           setState(prev => ({
             ...prev,
             isInInterval: false,
-            remaining: prev.duration,
+            duration,
+            remaining: duration,
             running: true,
             startTime: Date.now()
           }));
@@ -129,6 +146,8 @@ export function useTimer(
           }
           return;
         }
+        // Relógio saltou: espera o backend dizer como corrigir antes de contar
+        if (isClockHeld()) return;
         
         const elapsed = Math.floor((Date.now() - currentState.startTime!) / 1000);
         let remaining: number;
@@ -152,16 +171,20 @@ export function useTimer(
           
           const shouldRepeat = repeatConfig?.enabled && 
             (repeatConfig.times === 0 || repeatCountRef.current < (repeatConfig.times || 1));
+          const next = shouldRepeat ? callbacksRef.current?.nextRepeat?.() : undefined;
+          const nextInterval = next?.interval ?? repeatConfig?.interval ?? 0;
+          nextDurationRef.current = next?.duration ?? currentState.duration;
           
-          if (shouldRepeat && repeatConfig.interval && repeatConfig.interval > 0) {
-            timerLogger.info(`Timer ${timerId.current} entering interval period of ${repeatConfig.interval}s`);
+          if (shouldRepeat && nextInterval > 0) {
+            timerLogger.info(`Timer ${timerId.current} entering interval period of ${nextInterval}s`);
             const intervalStartTime = Date.now();
             setState(prev => ({
               ...prev,
               running: false,
               startTime: intervalStartTime,
               isInInterval: true,
-              remaining: repeatConfig.interval!
+              intervalLength: nextInterval,
+              remaining: nextInterval
             }));
             
             if (intervalRef.current) {
@@ -178,9 +201,10 @@ export function useTimer(
                 }
                 return;
               }
+              if (isClockHeld()) return;
               
               const elapsed = Math.floor((Date.now() - currentState.startTime!) / 1000);
-              const intervalRemaining = Math.max(0, repeatConfig.interval! - elapsed);
+              const intervalRemaining = Math.max(0, nextInterval - elapsed);
               
               setState(prev => ({
                 ...prev,
@@ -192,6 +216,8 @@ export function useTimer(
                 timerLogger.info(`Timer ${timerId.current} interval completed, restarting silently...`);
                 callbacksRef.current?.onIntervalComplete?.({ repeatCount: repeatCountRef.current });
                 repeatCountRef.current += 1;
+                const duration = nextDurationRef.current;
+                callbacksRef.current?.onPhaseStart?.({ phase: repeatCountRef.current + 1, duration });
                 if (intervalRef.current) {
                   clearInterval(intervalRef.current);
                   intervalRef.current = null;
@@ -199,7 +225,8 @@ export function useTimer(
                 setState(prev => ({
                   ...prev,
                   isInInterval: false,
-                  remaining: prev.duration,
+                  duration,
+                  remaining: duration,
                   running: true,
                   startTime: Date.now()
                 }));
@@ -208,12 +235,15 @@ export function useTimer(
           } else if (shouldRepeat) {
             repeatCountRef.current += 1;
             timerLogger.info(`Timer ${timerId.current} restarting immediately (repeat ${repeatCountRef.current})`);
+            const duration = nextDurationRef.current;
+            callbacksRef.current?.onPhaseStart?.({ phase: repeatCountRef.current + 1, duration });
             setTimeout(() => {
               setState(prev => ({
                 ...prev,
                 running: true,
                 startTime: Date.now(),
-                remaining: prev.duration,
+                duration,
+                remaining: duration,
                 isInInterval: false
               }));
             }, 50);
@@ -253,8 +283,9 @@ export function useTimer(
     };
   }, [state.running, state.startTime, state.duration, state.isInInterval, repeatConfig]);
 
-  const start = useCallback(() => {
+  const start = useCallback((durationSeconds?: number) => {
     const currentState = stateRef.current;
+    const duration = durationSeconds ?? configuredDurationRef.current;
     if (currentState.running) {
       timerLogger.warn(`Timer ${timerId.current} already running, resetting...`);
       repeatCountRef.current = 0;
@@ -269,12 +300,13 @@ export function useTimer(
       // Aguardar um pouco antes de reiniciar
       setTimeout(() => {
         const startTime = Date.now();
-        timerLogger.info(`Timer ${timerId.current} starting (after reset), duration: ${stateRef.current.duration}s`);
+        timerLogger.info(`Timer ${timerId.current} starting (after reset), duration: ${duration}s`);
         setState(prev => ({
           ...prev,
           running: true,
           startTime,
-          remaining: timerType === 'stopwatch' ? 0 : prev.duration,
+          duration,
+          remaining: timerType === 'stopwatch' ? 0 : duration,
           isInInterval: false,
           repeatCount: 0
         }));
@@ -282,12 +314,13 @@ export function useTimer(
     } else {
       repeatCountRef.current = 0;
       const startTime = Date.now();
-      timerLogger.info(`Timer ${timerId.current} starting, duration: ${currentState.duration}s`);
+      timerLogger.info(`Timer ${timerId.current} starting, duration: ${duration}s`);
       setState(prev => ({
         ...prev,
         running: true,
         startTime,
-        remaining: timerType === 'stopwatch' ? 0 : prev.duration,
+        duration,
+        remaining: timerType === 'stopwatch' ? 0 : duration,
         isInInterval: false,
         repeatCount: 0
       }));
@@ -314,7 +347,10 @@ export function useTimer(
   const resume = useCallback(() => {
     const currentState = stateRef.current;
     if (!currentState.running && currentState.remaining > 0) {
-      const startTime = Date.now() - (currentState.duration - currentState.remaining) * 1000;
+      const elapsed = timerType === 'stopwatch'
+        ? currentState.remaining
+        : currentState.duration - currentState.remaining;
+      const startTime = Date.now() - elapsed * 1000;
       timerLogger.info(`Timer ${timerId.current} resumed`);
       setState(prev => ({
         ...prev,
@@ -322,6 +358,69 @@ export function useTimer(
         startTime
       }));
     }
+  }, [timerType]);
+
+  const togglePause = useCallback(() => {
+    const currentState = stateRef.current;
+    if (currentState.running) {
+      pause();
+    } else if (currentState.startTime && !currentState.isInInterval) {
+      resume();
+    }
+  }, [pause, resume]);
+
+  // Soma segundos ao tempo restante (ou decorrido, no cronômetro) sem reiniciar o timer.
+  // Se a contagem chegar a zero, o próximo tick dispara a lógica de conclusão normalmente.
+  const adjust = useCallback((deltaSeconds: number) => {
+    const currentState = stateRef.current;
+    if (!currentState.startTime) {
+      timerLogger.warn(`Timer ${timerId.current} is not active, ignoring adjustment`);
+      return;
+    }
+
+    const now = Date.now();
+    if (currentState.isInInterval) {
+      const total = currentState.intervalLength ?? repeatConfig?.interval ?? 0;
+      const remaining = Math.max(0, currentState.remaining + deltaSeconds);
+      timerLogger.info(`Timer ${timerId.current} interval adjusted by ${deltaSeconds}s -> ${remaining}s`);
+      setState(prev => ({ ...prev, remaining, startTime: now - (total - remaining) * 1000 }));
+      return;
+    }
+
+    if (timerType === 'stopwatch') {
+      const elapsed = Math.max(0, currentState.remaining + deltaSeconds);
+      timerLogger.info(`Stopwatch ${timerId.current} adjusted by ${deltaSeconds}s -> ${elapsed}s`);
+      setState(prev => ({
+        ...prev,
+        remaining: elapsed,
+        startTime: prev.running ? now - elapsed * 1000 : prev.startTime
+      }));
+      return;
+    }
+
+    const remaining = Math.max(0, currentState.remaining + deltaSeconds);
+    timerLogger.info(`Timer ${timerId.current} adjusted by ${deltaSeconds}s -> ${remaining}s`);
+    setState(prev => ({
+      ...prev,
+      remaining,
+      // Pausado em zero: volta a rodar para concluir imediatamente
+      running: prev.running || remaining === 0,
+      startTime: prev.running || remaining === 0 ? now - (prev.duration - remaining) * 1000 : prev.startTime
+    }));
+  }, [repeatConfig, timerType]);
+
+  // Move o início da execução (ou do intervalo) sem mexer no resto do estado.
+  // Usado quando o backend corrige o timer após suspensão ou mudança no relógio.
+  const shiftStart = useCallback((shiftMs: number) => {
+    const currentState = stateRef.current;
+    if (!currentState.startTime || (!currentState.running && !currentState.isInInterval)) {
+      return;
+    }
+    timerLogger.info(`Timer ${timerId.current} start shifted by ${shiftMs}ms`);
+    setState(prev => ({
+      ...prev,
+      startTime: prev.startTime === null ? null : prev.startTime + shiftMs
+    }));
   }, []);
 
   const reset = useCallback(() => {
@@ -331,10 +430,12 @@ export function useTimer(
       clearTimeout(intervalTimeoutRef.current);
       intervalTimeoutRef.current = null;
     }
+    // Volta para a duração configurada caso a última execução tenha usado outra
     setState(prev => ({
       ...prev,
       running: false,
-      remaining: timerType === 'stopwatch' ? 0 : prev.duration,
+      duration: configuredDurationRef.current,
+      remaining: timerType === 'stopwatch' ? 0 : configuredDurationRef.current,
       startTime: null,
       isInInterval: false,
       repeatCount: 0
@@ -342,6 +443,7 @@ export function useTimer(
   }, [timerType]);
 
   const updateDuration = useCallback((newDuration: number) => {
+    configuredDurationRef.current = newDuration;
     timerLogger.info(`Timer ${timerId.current} duration updated: ${stateRef.current.duration}s -> ${newDuration}s`);
     setState(prev => ({
       ...prev,
@@ -356,6 +458,9 @@ export function useTimer(
     stop,
     pause,
     resume,
+    togglePause,
+    adjust,
+    shiftStart,
     reset,
     updateDuration
   };
@@ -1,3 +1,72 @@
+export interface ChainRule {
+  id: string;
+  // 'phase' dispara quando o ciclo de repetição `phase` começa (1 = primeira execução)
+  trigger: 'finished' | 'phase' | 'cancelled';
+  phase?: number;
+  delay?: number;
+  target: { type: 'timer'; timerId: string } | { type: 'preset'; presetId: string };
+  // Limite de disparos por cadeia; obrigatório para regras que formam ciclos
+  maxRuns?: number;
+}
+
+export interface AlarmConfig {
+  // Horário local no formato HH:MM
+  time: string;
+  recurrence: 'once' | 'daily' | 'weekly';
+  // Dias da semana para alarmes semanais (0 = domingo ... 6 = sábado)
+  weekdays?: number[];
+  // Nome IANA do fuso (ex.: 'Europe/Berlin'); vazio = fuso local
+  timeZone?: string;
+  snoozeMinutes?: number;
+  enabled?: boolean;
+}
+
+// Contagem até um reset recorrente (ex.: raid semanal terça 15:00 UTC)
+export interface ResetConfig {
+  time: string;
+  // Dias do reset (0 = domingo ... 6 = sábado); vazio = todo dia
+  weekdays?: number[];
+  // Reset mensal nesse dia (1-31, último dia em meses mais curtos); ignora `weekdays`
+  monthDay?: number;
+  timeZone?: string;
+}
+
+// Cargas que recarregam uma de cada vez; cada recarga dura `timerDuration` segundos
+export interface ChargesConfig {
+  max: number;
+}
+
+// Ciclo Pomodoro: o foco dura `timerDuration` segundos; pausas também em segundos
+export interface PomodoroConfig {
+  shortBreak: number;
+  longBreak: number;
+  // Pausa longa depois de quantos focos
+  longBreakEvery: number;
+  // Começa a próxima fase sozinho ao fim da atual
+  autoAdvance?: boolean;
+}
+
+// Relógio de xadrez: cada jogador tem um banco de `timerDuration` segundos
+export interface ChessClockConfig {
+  players: string[];
+  // Segundos somados ao banco a cada jogada (Fischer)
+  increment?: number;
+  // Segundos no início de cada jogada que não descontam do banco
+  delay?: number;
+}
+
+// Duração sorteada a cada execução entre `min` e `max` segundos (treinos de reação).
+// `gapMin`/`gapMax` sorteiam o intervalo entre repetições; `seed` reproduz a sessão
+export interface RandomDurationConfig {
+  min: number;
+  max: number;
+  gapMin?: number;
+  gapMax?: number;
+  seed?: number;
+}
+
+export type TriggerPolicy = 'restart' | 'ignore' | 'pause' | 'reset' | 'stack';
+
 export interface IconConfig {
   id: string;
   name?: string;
@@ -6,7 +75,25 @@ export interface IconConfig {
   timerDuration: number;
   notificationType: 'none' | 'sound' | 'notification' | 'both';
   soundPath?: string;
-  timerType?: 'countdown' | 'stopwatch';
+  timerType?: 'countdown' | 'stopwatch' | 'alarm' | 'reset' | 'charges' | 'pomodoro' | 'chessClock';
+  alarm?: AlarmConfig;
+  charges?: ChargesConfig;
+  pomodoro?: PomodoroConfig;
+  chessClock?: ChessClockConfig;
+  randomDuration?: RandomDurationConfig;
+  reset?: ResetConfig;
+  schedule?: TimerSchedule;
+  // Continua contando como tempo negativo depois de zerar, até ser reconhecido
+  overtime?: boolean;
+  // Atalho para registrar uma volta (apenas cronômetros)
+  lapKeybind?: string;
+  adjustKeybinds?: AdjustKeybind[];
+  // O que fazer quando o atalho é acionado com o timer já rodando (padrão: restart)
+  triggerPolicy?: TriggerPolicy;
+  // Durante a suspensão do computador: continua contando (padrão) ou congela
+  suspendPolicy?: 'realTime' | 'pause';
+  // Timer rápido que só existe na memória do backend (não vem do config.json)
+  ephemeral?: boolean;
   repeat?: {
     enabled: boolean;
     times?: number;
@@ -16,6 +103,43 @@ export interface IconConfig {
     intervalNotificationText?: string;
   };
   completionNotificationText?: string;
+  chain?: ChainRule[];
+  // Comandos executados nos eventos deste timer (além dos globais)
+  eventHooks?: EventHook[];
+}
+
+export type HookEvent = 'started' | 'phase' | 'finished' | 'cancelled' | 'paused' | 'resumed' | 'acknowledged';
+
+// Comando externo executado num evento de timer; recebe TIMER_* no ambiente e o evento em JSON no stdin
+export interface EventHook {
+  id: string;
+  name?: string;
+  // Vazio = todos os eventos
+  events: HookEvent[];
+  command: string;
+  args?: string[];
+  workingDir?: string;
+  // Padrão 30s; o processo é encerrado ao passar do limite
+  timeoutSeconds?: number;
+  enabled?: boolean;
+}
+
+// POST com JSON para uma URL nos eventos escolhidos, com novas tentativas e backoff
+export interface WebhookTarget {
+  id: string;
+  name?: string;
+  url: string;
+  // Eventos de timer e 'warning'; vazio = todos
+  events: (HookEvent | 'warning')[];
+  // Vazio = todos os timers
+  timerIds?: string[];
+  // Segundos restantes que disparam 'warning' nas contagens regressivas
+  warnAtSeconds?: number[];
+  headers?: Record<string, string>;
+  // Segredo do HMAC-SHA256 enviado em X-Timer-Signature-256
+  secret?: string;
+  maxAttempts?: number;
+  enabled?: boolean;
 }
 
 export interface TimerPreset {
@@ -35,6 +159,89 @@ export interface StopwatchConfig {
   notificationType: 'none' | 'sound' | 'notification' | 'both';
 }
 
+export type TimerGroupAction = 'start' | 'pause' | 'reset';
+
+// Início automático estilo cron (minuto hora dia mês dia-da-semana)
+export interface TimerSchedule {
+  cron: string;
+  timeZone?: string;
+  // Ignora os disparos perdidos enquanto o app estava fechado (padrão: true)
+  skipMissed?: boolean;
+  enabled?: boolean;
+}
+
+// Atalho que soma (ou subtrai, se negativo) segundos a um timer em andamento
+export interface AdjustKeybind {
+  keybind: string;
+  deltaSeconds: number;
+}
+
+export interface TimerGroup {
+  id: string;
+  name: string;
+  timerIds: string[];
+  keybind?: string;
+  keybindAction?: TimerGroupAction;
+  schedule?: TimerSchedule;
+}
+
+export type LogWatchAction = 'start' | 'reset' | 'pause' | 'toggle';
+
+// Linhas novas de um arquivo de log que casam com `pattern` executam `action` no timer
+export interface LogWatcher {
+  id: string;
+  name?: string;
+  path: string;
+  // Expressão regular (sintaxe do crate regex do Rust)
+  pattern: string;
+  action: LogWatchAction;
+  timerId: string;
+  // Grupo de captura (nome ou índice) com a duração, ex.: "90", "1:30" ou "2m"
+  durationGroup?: string;
+  enabled?: boolean;
+}
+
+// Quando um programa que casa com `pattern` abre ou fecha, executa `action` no alvo
+export interface ProcessRule {
+  id: string;
+  name?: string;
+  // Nome do executável (ex.: 'Wow.exe') ou caminho completo; aceita * e ?
+  pattern: string;
+  event: 'start' | 'exit';
+  // 'profile' troca para o perfil do alvo
+  action: 'start' | 'stop' | 'reset' | 'profile';
+  target: { type: 'timer'; timerId: string } | { type: 'group'; groupId: string } | { type: 'profile'; profileId: string };
+  enabled?: boolean;
+}
+
+// Conjunto de timers mostrados no overlay e layout opcional, ativado manualmente ou por regra de processo
+export interface TimerProfile {
+  id: string;
+  name: string;
+  // Vazio = todos os timers
+  timerIds: string[];
+  overlayCorner?: AppConfig['overlayCorner'];
+  overlayOrientation?: AppConfig['overlayOrientation'];
+  compactMode?: boolean;
+}
+
+// Meta (mínimo a atingir) ou limite (máximo a não passar) por dia ou semana
+export interface GoalDefinition {
+  id: string;
+  name?: string;
+  kind: 'goal' | 'budget';
+  period: 'day' | 'week';
+  scope: 'timer' | 'group' | 'label';
+  // Id do timer ou do grupo, ou o texto do rótulo
+  target: string;
+  // Execuções concluídas (limites contam todas) ou segundos contados
+  metric: 'runs' | 'seconds';
+  amount: number;
+  // Fração do limite que dispara o aviso (padrão 0.9)
+  warnAt?: number;
+  enabled?: boolean;
+}
+
 export interface AppConfig {
   icons: IconConfig[];
   overlayPosition: { x: number; y: number };
@@ -50,12 +257,53 @@ export interface AppConfig {
   resetAllTimersKeybind?: string;
   timerPresets?: TimerPreset[];
   quickCreateTimerKeybind?: string;
+  // Atalhos globais de ajuste, aplicados ao timer ativo iniciado mais recentemente
+  adjustKeybinds?: AdjustKeybind[];
   stopwatches?: StopwatchConfig[];
+  timerGroups?: TimerGroup[];
+  logWatchers?: LogWatcher[];
+  processRules?: ProcessRule[];
+  profiles?: TimerProfile[];
+  activeProfileId?: string;
+  eventHooks?: EventHook[];
+  // Hooks rodando ao mesmo tempo (padrão 4); os demais esperam a vez
+  eventHookConcurrency?: number;
+  webhooks?: WebhookTarget[];
+  // Dias mantidos no histórico persistente (0 = para sempre; padrão 90)
+  historyRetentionDays?: number;
+  goals?: GoalDefinition[];
   showWelcomeModal?: boolean;
 }
 
+// Mesmo efeito de `profiles::switch_profile` no Rust: o layout definido no perfil substitui o atual
+export function applyProfile(config: AppConfig, profile: TimerProfile): AppConfig {
+  return {
+    ...config,
+    activeProfileId: profile.id,
+    overlayCorner: profile.overlayCorner ?? config.overlayCorner,
+    overlayOrientation: profile.overlayOrientation ?? config.overlayOrientation,
+    compactMode: profile.compactMode ?? config.compactMode
+  };
+}
+
+// Timers mostrados no overlay pelo perfil ativo
+export function profileIcons(config: AppConfig): IconConfig[] {
+  const profile = config.profiles?.find((p) => p.id === config.activeProfileId);
+  if (!profile || profile.timerIds.length === 0) return config.icons;
+  return config.icons.filter((icon) => profile.timerIds.includes(icon.id));
+}
+
 export const DEFAULT_RESET_ALL_TIMERS_KEYBIND = 'Ctrl+Alt+P';
 
+// Prefixo do id especial usado para registrar atalhos de grupo no hook de baixo nível
+export const TIMER_GROUP_SHORTCUT_PREFIX = '__timer_group__:';
+
+// Prefixo do id especial usado para registrar atalhos de volta de cronômetros no hook de baixo nível
+export const TIMER_LAP_SHORTCUT_PREFIX = '__timer_lap__:';
+
+// Prefixo dos atalhos de ajuste no hook de baixo nível: `${prefixo}${delta}:${timerId | '*'}`
+export const TIMER_ADJUST_SHORTCUT_PREFIX = '__timer_adjust__:';
+
 export const DEFAULT_POMODORO_PRESETS: TimerPreset[] = [
   {
     id: 'preset-pomodoro-focus',
@@ -0,0 +1,35 @@
+import { listen } from '@tauri-apps/api/event';
+import { timerLogger } from './logger';
+
+// Ao voltar da suspensão (ou com o relógio do sistema mudando), o Date.now() salta antes
+// do Rust avisar como corrigir cada timer. Seguramos os ticks até o aviso (`clock-corrected`)
+// para um timer em modo "pausar" não concluir por engano com o tempo que passou dormindo.
+
+const GAP_THRESHOLD_MS = 3000;
+const MAX_HOLD_MS = 3000;
+
+let lastSeen = Date.now();
+let heldUntil = 0;
+
+function check() {
+  const now = Date.now();
+  const gap = now - lastSeen;
+  lastSeen = now;
+  if (gap > GAP_THRESHOLD_MS || gap < -GAP_THRESHOLD_MS) {
+    timerLogger.warn(`Clock gap of ${gap}ms detected, holding timers until the backend corrects them`);
+    heldUntil = now + MAX_HOLD_MS;
+  }
+}
+
+export function isClockHeld(): boolean {
+  check();
+  return Date.now() < heldUntil;
+}
+
+export function releaseClockHold() {
+  lastSeen = Date.now();
+  heldUntil = 0;
+}
+
+window.setInterval(check, 250);
+listen('clock-corrected', () => releaseClockHold());
@@ -0,0 +1,158 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type HistoryEventKind = 'started' | 'repeated' | 'paused' | 'resumed' | 'finished' | 'cancelled' | 'acknowledged';
+
+export type TriggerSource = 'keybind' | 'tray' | 'api' | 'schedule' | 'chain' | 'logWatch' | 'process' | 'cli';
+
+// Uma linha do histórico persistente (history.jsonl no diretório de dados do app)
+export interface HistoryEntry {
+  timerId: string;
+  name?: string;
+  event: HistoryEventKind;
+  // Milissegundos desde a epoch
+  at: number;
+  runStartedAt?: number;
+  plannedSeconds?: number;
+  // Tempo efetivamente contado na execução até o evento
+  actualSeconds?: number;
+  repeatCount: number;
+  // Ausente em eventos automáticos (fim da contagem, repetição)
+  source?: TriggerSource;
+  instance?: number;
+  // O que se fez na execução; editável pelo histórico
+  label?: string;
+  note?: string;
+  // Semente e primeiro sorteio de timers com duração aleatória
+  randomSeed?: number;
+  randomDraw?: number;
+}
+
+export interface HistoryQuery {
+  from?: number;
+  to?: number;
+  timerId?: string;
+  events?: HistoryEventKind[];
+  limit?: number;
+}
+
+export const HISTORY_EVENT_LABELS: Record<HistoryEventKind, string> = {
+  started: 'Started',
+  repeated: 'Repeated',
+  paused: 'Paused',
+  resumed: 'Resumed',
+  finished: 'Finished',
+  cancelled: 'Reset',
+  acknowledged: 'Acknowledged'
+};
+
+export const TRIGGER_SOURCE_LABELS: Record<TriggerSource, string> = {
+  keybind: 'Shortcut',
+  tray: 'Tray',
+  api: 'API',
+  schedule: 'Schedule',
+  chain: 'Chain',
+  logWatch: 'Log trigger',
+  process: 'Process rule',
+  cli: 'Command line'
+};
+
+// Eventos do mais recente para o mais antigo
+export function queryHistory(query: HistoryQuery = {}): Promise<HistoryEntry[]> {
+  return invoke<HistoryEntry[]>('query_history', { query });
+}
+
+export function formatSeconds(seconds: number): string {
+  const h = Math.floor(seconds / 3600);
+  const m = Math.floor((seconds % 3600) / 60);
+  const s = seconds % 60;
+  return h > 0
+    ? `${h}:${String(m).padStart(2, '0')}:${String(s).padStart(2, '0')}`
+    : `${m}:${String(s).padStart(2, '0')}`;
+}
+
+export type StatsGroupBy = 'timer' | 'day' | 'week' | 'label';
+
+export interface StatsSummary {
+  runs: number;
+  completed: number;
+  cancelled: number;
+  // De 0 a 1; ausente sem execuções
+  completionRate?: number;
+  totalSeconds: number;
+  averageSeconds?: number;
+  longestStreakDays: number;
+  // Execuções iniciadas em cada hora do dia (24 posições)
+  hours: number[];
+  topHours: number[];
+}
+
+export interface StatsReport {
+  from?: number;
+  to?: number;
+  groupBy: StatsGroupBy;
+  overall: StatsSummary;
+  groups: { key: string; label: string; summary: StatsSummary }[];
+}
+
+export function getStats(range: { from?: number; to?: number }, groupBy: StatsGroupBy): Promise<StatsReport> {
+  return invoke<StatsReport>('get_stats', { range, groupBy });
+}
+
+// Markdown da semana atual (0) ou de semanas anteriores (-1, -2...)
+export function getWeeklySummary(weekOffset = 0): Promise<string> {
+  return invoke<string>('get_weekly_summary', { weekOffset });
+}
+
+export type ExportFormat = 'csv' | 'jsonl' | 'ics' | 'timewarrior';
+
+export const EXPORT_FORMATS: { value: ExportFormat; label: string; extension: string }[] = [
+  { value: 'csv', label: 'CSV', extension: 'csv' },
+  { value: 'jsonl', label: 'JSON Lines', extension: 'jsonl' },
+  { value: 'ics', label: 'iCalendar', extension: 'ics' },
+  { value: 'timewarrior', label: 'Timewarrior', extension: 'json' }
+];
+
+// Escreve uma sessão por execução terminada; retorna quantas foram exportadas
+export function exportHistory(
+  path: string,
+  format: ExportFormat,
+  filter: { from?: number; to?: number; timerIds?: string[]; labels?: string[] } = {}
+): Promise<number> {
+  return invoke<number>('export_history', { path, format, filter });
+}
+
+// Sem runStartedAt, rotula a execução ativa do timer (ou a próxima, se estiver parado)
+export function labelRun(
+  timerId: string,
+  runStartedAt: number | undefined,
+  label: string,
+  note: string
+): Promise<number | null> {
+  return invoke<number | null>('label_run', { timerId, runStartedAt, label, note });
+}
+
+export function getRecentLabels(limit = 20): Promise<string[]> {
+  return invoke<string[]>('get_recent_labels', { limit });
+}
+
+export interface GoalProgress {
+  id: string;
+  name: string;
+  kind: 'goal' | 'budget';
+  period: 'day' | 'week';
+  metric: 'runs' | 'seconds';
+  amount: number;
+  // Valor no período atual (execuções em andamento entram nos segundos)
+  current: number;
+  fraction: number;
+  // Meta atingida, ou limite ainda respeitado
+  met: boolean;
+  warning: boolean;
+  streak: number;
+  periodStart: number;
+  periodEnd: number;
+}
+
+export function getGoalProgress(): Promise<GoalProgress[]> {
+  return invoke<GoalProgress[]>('get_goal_progress');
+}
@@ -0,0 +1,22 @@
+import { AppConfig, IconConfig } from '../types/config';
+
+// Todos os atalhos usados por um timer (início, volta e ajustes)
+export function iconKeybinds(icon: IconConfig): string[] {
+  return [
+    icon.keybind?.trim(),
+    icon.lapKeybind?.trim(),
+    ...(icon.adjustKeybinds?.map(a => a.keybind.trim()) || [])
+  ].filter((key): key is string => Boolean(key));
+}
+
+// Todos os atalhos configurados no app, para detectar conflitos
+export function allConfiguredKeybinds(config: AppConfig): string[] {
+  return [
+    ...(config.icons?.flatMap(iconKeybinds) || []),
+    ...(config.timerPresets?.map(p => p.keybind?.trim()) || []),
+    ...(config.timerGroups?.map(g => g.keybind?.trim()) || []),
+    ...(config.adjustKeybinds?.map(a => a.keybind.trim()) || []),
+    config.resetAllTimersKeybind?.trim(),
+    config.quickCreateTimerKeybind?.trim()
+  ].filter((key): key is string => Boolean(key));
+}
@@ -0,0 +1,118 @@
+import { invoke } from '@tauri-apps/api/core';
+import { timerLogger } from './logger';
+
+export type TimerEventKind = 'started' | 'phase' | 'finished' | 'cancelled' | 'paused' | 'resumed' | 'acknowledged';
+
+export interface TimerEventDetails {
+  phase?: number;
+  // Segundos restantes (ou decorridos, para cronômetros)
+  remaining?: number;
+  // Instância paralela (política stack); ausente = instância principal
+  instance?: number;
+  // Semente e sorteio da duração aleatória usada em `started`
+  seed?: number;
+  draw?: number;
+}
+
+export interface RandomRoll {
+  durationSeconds: number;
+  // Intervalo antes da próxima repetição; ausente = o da configuração de repetição
+  gapSeconds?: number;
+  seed: number;
+  draw: number;
+}
+
+// Correção enviada pelo Rust após suspensão ou mudança no relógio do sistema
+export interface TimerCorrection {
+  timerId: string;
+  instance: number;
+  reason: 'suspend' | 'clockJump';
+  policy: 'realTime' | 'pause';
+  // Quanto somar ao início da execução
+  shiftMs: number;
+}
+
+export interface TimerInstanceState {
+  instance: number;
+  status: 'idle' | 'running' | 'paused' | 'overtime';
+  remainingSeconds?: number;
+  elapsedSeconds?: number;
+}
+
+export interface ChargeSnapshot {
+  timerId: string;
+  available: number;
+  max: number;
+  rechargeSeconds: number;
+  // Progresso da recarga em andamento (0 a 1)
+  rechargeProgress?: number;
+  rechargeRemainingMs?: number;
+  // Quando a próxima carga fica pronta (ms desde a epoch)
+  nextChargeAt?: number;
+}
+
+export type PomodoroPhase = 'focus' | 'shortBreak' | 'longBreak';
+
+export interface PomodoroSnapshot {
+  timerId: string;
+  phase: PomodoroPhase;
+  status: 'idle' | 'running' | 'paused';
+  remainingMs: number;
+  phaseSeconds: number;
+  // Posição do foco no conjunto (1 a longBreakEvery)
+  cycle: number;
+  longBreakEvery: number;
+  completedToday: number;
+  autoAdvance: boolean;
+}
+
+export interface PlayerClockState {
+  index: number;
+  name: string;
+  remainingMs: number;
+  moves: number;
+  // Perdeu por tempo
+  flagged: boolean;
+}
+
+export interface ChessClockSnapshot {
+  timerId: string;
+  status: 'idle' | 'running' | 'paused';
+  activePlayer: number;
+  players: PlayerClockState[];
+  bankSeconds: number;
+  incrementSeconds: number;
+  delaySeconds: number;
+  delayRemainingMs: number;
+  finished: boolean;
+}
+
+export interface TimerStateSnapshot {
+  timerId: string;
+  name?: string;
+  timerType: string;
+  status: 'idle' | 'running' | 'paused' | 'overtime';
+  remainingSeconds?: number;
+  elapsedSeconds?: number;
+  durationSeconds?: number;
+  phase?: number;
+  overtimeSeconds?: number;
+  // Próximo reset (ms desde a epoch) para contagens até reset
+  nextResetAt?: number;
+  // Instâncias paralelas em andamento além da principal
+  stackedInstances: TimerInstanceState[];
+  charges?: ChargeSnapshot;
+  pomodoro?: PomodoroSnapshot;
+  chessClock?: ChessClockSnapshot;
+}
+
+// Reporta eventos do ciclo de vida do timer para o engine em Rust (cadeias, etc.)
+export function reportTimerEvent(timerId: string, event: TimerEventKind, details: TimerEventDetails = {}) {
+  invoke('report_timer_event', { event: { timerId, event, ...details } }).catch((error) => {
+    timerLogger.error(`[TIMER] Failed to report ${event} for ${timerId}:`, error);
+  });
+}
+
+export function getTimerStates(): Promise<TimerStateSnapshot[]> {
+  return invoke<TimerStateSnapshot[]>('get_timer_states');
+}